@@ -332,6 +332,12 @@ build_project() {
         build_flags="$build_flags --verbose"
     fi
 
+    # The Go tests need the test-only exports, don't ship a module built with them
+    if $RUN_TESTS || $RUN_ALL; then
+        build_flags="$build_flags --features test-exports"
+        log_warning "Building with the test-exports feature, rebuild without --test before release"
+    fi
+
     # Execute cargo build
     if $VERBOSE; then
         log_info "Running: cargo build $build_flags"
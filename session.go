@@ -2,16 +2,43 @@ package aleo_utils
 
 import (
 	"context"
+	"encoding/binary"
 	"errors"
 	"fmt"
 	"log"
+	"math"
+	"math/big"
+	"strconv"
 	"strings"
+	"time"
 
 	"github.com/tetratelabs/wazero/api"
 )
 
 var ErrNoModule = errors.New("session module is closed")
 
+// ErrRecordNoOwner is returned by RecordOwner for records without an owner entry
+var ErrRecordNoOwner = errors.New("record has no owner field")
+
+// ErrRecordDecrypt is returned by CiphertextsEqualPlaintext and DecryptAndVerifyRecord for
+// ciphertexts the view key can't decrypt
+var ErrRecordDecrypt = errors.New("record ciphertext doesn't decrypt with the view key")
+
+// ErrRecordCommitment is returned by DecryptAndVerifyRecord for records that don't match the
+// expected commitment
+var ErrRecordCommitment = errors.New("decrypted record doesn't match the commitment")
+
+// ErrAddressChecksum is returned by VerifyAddressChecksum for strings that aren't valid bech32m
+var ErrAddressChecksum = errors.New("invalid address checksum")
+
+// ErrAddressHRP is returned by VerifyAddressChecksum for valid bech32m strings with a
+// human-readable part other than "aleo"
+var ErrAddressHRP = errors.New("address has a valid checksum but isn't an aleo address")
+
+// ErrFunctionNotFound is returned by ProgramFunctionSignature for programs without the requested
+// function
+var ErrFunctionNotFound = errors.New("program has no function with that name")
+
 // Provides access to wrapper functionality. A session is not goroutine safe so
 // you need to create a new one for every goroutine
 type Session interface {
@@ -19,13 +46,225 @@ type Session interface {
 	// corresponding address. The caller is responsible for zeroizing the returned
 	// slice when it is no longer needed (see ZeroizePrivateKey).
 	NewPrivateKey() (key []byte, address string, err error)
+	// NewPrivateKeys generates count private keys in one call, reproducibly if seeded.
+	NewPrivateKeys(count uint32, seed []byte) (keys [][]byte, err error)
 	FormatMessage(message []byte, targetChunks int) (formattedMessage []byte, err error)
 	RecoverMessage(formattedMessage []byte) (message []byte, err error)
 	HashMessageToString(message []byte) (hash string, err error)
 	HashMessage(message []byte) (hash []byte, err error)
+	// HashToAddress hashes a message with Poseidon2 to a group element and returns it as an
+	// address, matching Leo's Poseidon2::hash_to_address.
+	HashToAddress(message []byte) (address string, err error)
+	// MaxInputBytes returns the maximum input size in bytes for a hash operation code (HASH_*),
+	// or UNBOUNDED_INPUT if the operation accepts inputs of any size.
+	MaxInputBytes(operation uint32) (limit uint64, err error)
+	// CheckHashInput reports whether an input of inputLen bytes is within a hash operation's capacity.
+	CheckHashInput(operation uint32, inputLen uint32) (ok bool, err error)
+	// HashBytes hashes raw bytes with a hash operation (HASH_*) and returns the digest.
+	HashBytes(operation uint32, input []byte) (digest []byte, err error)
+	// HashBenchmark measures how long an operation takes over zero bytes, for diagnostics only.
+	HashBenchmark(operation uint32, inputSize uint32, iterations uint32) (elapsed time.Duration, err error)
+	// HashConcat hashes the concatenation of several byte chunks with a hash operation.
+	HashConcat(operation uint32, parts [][]byte) (digest []byte, err error)
+	// HashWithDomain hashes an input prefixed with a length-prefixed domain tag.
+	HashWithDomain(operation uint32, domain []byte, input []byte) (digest []byte, err error)
+	// PedersenOpen commits to a short input and returns the commitment with its opening.
+	PedersenOpen(input []byte, randomizer string) (commitment string, bits []byte, openingRandomizer string, err error)
+	// PedersenVerify checks a commitment opening returned by PedersenOpen.
+	PedersenVerify(commitment string, bits []byte, randomizer string) (valid bool, err error)
+	// HashMulti hashes the same input with several operations in one call.
+	HashMulti(operations []uint32, input []byte) (digests [][]byte, err error)
+	// GetAddress returns the address of a private key.
+	GetAddress(key []byte) (address string, err error)
+	// GetAddressExt returns the address of a private key in an ADDRESS_FORMAT_* format.
+	GetAddressExt(key []byte, format uint32) (address []byte, err error)
+	// AddressForNetwork returns the address of a private key on a NETWORK_* network.
+	AddressForNetwork(key []byte, networkID uint32) (address string, err error)
+	// AddressShard deterministically assigns an address to one of numShards shards.
+	AddressShard(address string, numShards uint32) (shard uint32, err error)
+	// VerifyAddressChecksum checks only the bech32m checksum and human-readable part of an address.
+	VerifyAddressChecksum(address string) error
+	// GetComputeKey returns the pk_sig, pr_sig and sk_prf components of a private key's compute key.
+	GetComputeKey(key []byte) (pkSig, prSig, skPrf string, err error)
+	// PrivateKeyComponents returns the sk_sig and r_sig scalars of a private key.
+	PrivateKeyComponents(key []byte) (skSig, rSig string, err error)
+	// AddressFromComponents derives an address from the components of a compute key.
+	AddressFromComponents(pkSig, prSig, skPrf string) (address string, err error)
+	// ComputeKeyIsValid checks that compute key components form a consistent compute key.
+	ComputeKeyIsValid(pkSig, prSig, skPrf string) (valid bool, err error)
+	// GetViewKey returns the view key of a private key. The caller is responsible for zeroizing
+	// the returned slice.
+	GetViewKey(key []byte) (viewKey []byte, err error)
+	// ViewKeySecret returns the secret scalar of a view key.
+	ViewKeySecret(viewKey []byte) (secret []byte, err error)
+	// DeriveChildKey deterministically derives the private key at index from a 32-byte seed.
+	// The caller is responsible for zeroizing the returned slice.
+	DeriveChildKey(seed []byte, index uint32) (key []byte, err error)
+	// DeriveAddressRange derives the addresses of count child keys starting at index start.
+	DeriveAddressRange(seed []byte, start, count uint32) (addresses []string, err error)
+	// AddressFromLabel derives a well-known public address from a label.
+	AddressFromLabel(label string) (address string, err error)
+	// RecordLeafHash returns the transition tree leaf hash of a record output.
+	RecordLeafHash(commitment string, index uint8) (leafHash string, err error)
+	// MerkleRoot returns the root of a BHP Merkle tree over a list of leaf fields.
+	MerkleRoot(leaves []string) (root string, err error)
+	// MerkleMultiProof creates one proof for the leaves at several indices of a tree.
+	MerkleMultiProof(leaves []string, indices []uint32) (proof []byte, err error)
+	// MerkleVerifyMulti verifies a proof created with MerkleMultiProof against a root.
+	MerkleVerifyMulti(root string, leaves []string, indices []uint32, proof []byte) (valid bool, err error)
+	// MerkleFindLeaf returns the index of a leaf among the leaves of a tree.
+	MerkleFindLeaf(leaves []string, target string) (index uint64, found bool, err error)
+	// ComputeStateRoot returns the state root of a chain from the hashes of its blocks.
+	ComputeStateRoot(blockHashes []string) (root string, err error)
+	// StateRootsEqual checks whether two state roots encode the same field, whatever their formatting.
+	StateRootsEqual(a, b string) (equal bool, err error)
+	// NewIncrementalMerkle starts an append-only Merkle tree of a fixed depth.
+	NewIncrementalMerkle(depth uint32) (tree *IncrementalMerkle, err error)
+	// NewFieldChain starts a hash-linked log of field elements.
+	NewFieldChain(initial string) (chain *FieldChain, err error)
+	// CiphertextsEqualPlaintext checks whether two record ciphertexts hold the same record.
+	CiphertextsEqualPlaintext(viewKey []byte, ciphertextA, ciphertextB string) (equal bool, err error)
+	// RecordIsOwner checks whether a record ciphertext is owned by a view key without decrypting it.
+	RecordIsOwner(viewKey []byte, ciphertext string) (owned bool, err error)
+	// RecordsOwnershipBatch checks ownership of many record ciphertexts with a single view key.
+	RecordsOwnershipBatch(viewKey []byte, ciphertexts []string) (owned []bool, err error)
+	// VerifySignature verifies a signature created with Sign against an address.
+	VerifySignature(address string, signature string, message []byte) (valid bool, err error)
+	// StringToFields encodes a UTF-8 string as a list of Leo field literals.
+	StringToFields(str string) (fields []string, err error)
+	// ValueToBits returns snarkVM's bit decomposition of an Aleo value, one 0/1 byte per bit.
+	ValueToBits(value string, littleEndian bool) (bits []byte, err error)
+	// ValidateValueType checks that a plaintext value conforms to a type such as "u64".
+	ValidateValueType(value, expectedType string) error
+	// ValidateRecord checks that a plaintext record conforms to a program's record type.
+	ValidateRecord(record, recordType string) error
+	// GroupIsInSubgroup checks that an external group element is safe to use for ECDH.
+	GroupIsInSubgroup(group string) (valid bool, err error)
+	// NormalizeProgramID validates a program ID and returns its canonical form.
+	NormalizeProgramID(programID string) (normalized string, err error)
+	// ProgramFingerprint returns a local fingerprint of an Aleo program source.
+	ProgramFingerprint(programSource string) (fingerprint string, err error)
+	// ProgramFunctions returns the names of the functions an Aleo program exports.
+	ProgramFunctions(programSource string) (functions []string, err error)
+	// ProgramFunctionSignature returns the input and output types of a program's function.
+	ProgramFunctionSignature(programSource, functionName string) (inputs, outputs []string, err error)
+	// HKDFDerive derives outLen bytes of key material with HKDF over HMAC-SHA3-256.
+	HKDFDerive(ikm, salt, info []byte, outLen uint32) (key []byte, err error)
+	// MessageKey derives a symmetric key for off-chain messages between two accounts.
+	MessageKey(key []byte, peerAddress string, context []byte) (messageKey []byte, err error)
+	// EncryptToAddress encrypts an arbitrary payload so only the address's view key can read it.
+	EncryptToAddress(address string, plaintext []byte) (ciphertext []byte, err error)
+	// DecryptFromAddress decrypts a payload EncryptToAddress created for the address of key.
+	DecryptFromAddress(key []byte, ciphertext []byte) (plaintext []byte, err error)
+	// StealthAddress derives a one-time stealth address for a recipient from an ephemeral scalar.
+	StealthAddress(recipientAddress, ephemeralScalar string) (stealthAddress, ephemeralPubkey string, err error)
+	// StealthScan checks whether a stealth address belongs to the account of a view key.
+	StealthScan(viewKey []byte, ephemeralPubkey, stealthAddress string) (mine bool, err error)
+	// FieldCountForBytes returns the number of fields StringToFields produces for byteLen bytes.
+	FieldCountForBytes(byteLen uint32) (count uint64, err error)
+	// FieldsToString decodes a list of field literals created by StringToFields.
+	FieldsToString(fields []string) (str string, err error)
+	// BytesToFieldArray splits bytes into chunks of chunkBytes and converts each into a field literal.
+	BytesToFieldArray(data []byte, chunkBytes uint32) (fields []string, err error)
+	// LiteralToBytes returns the native little-endian bytes of a boolean or integer literal.
+	LiteralToBytes(literal string) (data []byte, err error)
+	// BytesToLiteral converts native little-endian bytes into a boolean or integer literal.
+	BytesToLiteral(data []byte, typeCode uint32) (literal string, err error)
+	// ValuesEqual checks whether two Aleo values are semantically equal, whatever their formatting.
+	ValuesEqual(a, b string) (equal bool, err error)
+	// ClassifyBech32 reports what kind of Aleo string, e.g. an address or a private key, input is.
+	ClassifyBech32(input string) (kind uint64, err error)
+	// BaseFieldModulus returns the modulus of the base field (Leo field type).
+	BaseFieldModulus() (modulus *big.Int, err error)
+	// ScalarFieldModulus returns the modulus of the scalar field (Leo scalar type).
+	ScalarFieldModulus() (modulus *big.Int, err error)
+	// DecimalToField converts a base-10 integer string into a field, reducing it modulo the base field.
+	DecimalToField(decimal string) (field string, err error)
+	// FieldToDecimal returns the value of a field as a base-10 integer string.
+	FieldToDecimal(field string) (decimal string, err error)
+	// ParseProgramInputs validates and canonicalizes a list of Aleo literal program inputs.
+	ParseProgramInputs(inputs string) (values []string, err error)
+	// ComputeRecordCommitment computes the commitment of a plaintext record.
+	ComputeRecordCommitment(record, programID, recordName string) (commitment string, err error)
+	// ComputeRecordTag computes the tag of a record commitment for a view key.
+	ComputeRecordTag(viewKey []byte, commitment string) (tag string, err error)
+	// ComputeSerialNumber computes the serial number of a record commitment for a private key.
+	ComputeSerialNumber(key []byte, commitment string) (serialNumber string, err error)
+	// ComputeSerialAndTag computes the serial number and the tag of a record commitment at once.
+	ComputeSerialAndTag(key []byte, commitment string) (serialNumber, tag string, err error)
+	// NewScanSession caches a parsed view key for computing the tags of many commitments.
+	NewScanSession(viewKey []byte) (scan *ScanSession, err error)
+	// DecryptRecord decrypts a record ciphertext with the view key of its owner.
+	DecryptRecord(ciphertext string, viewKey []byte) (record string, err error)
+	// DecryptAndVerifyRecord decrypts a record ciphertext and checks it against its commitment.
+	DecryptAndVerifyRecord(viewKey []byte, ciphertext, commitment, programID, recordName string) (record string, err error)
+	// RerandomizeCiphertext moves a record ciphertext to a new nonce, keeping it decryptable by its owner.
+	RerandomizeCiphertext(ciphertext string, viewKey []byte, delta string) (rerandomized string, err error)
+	// CiphertextCommitment returns the checksum the chain stores for a record ciphertext.
+	CiphertextCommitment(ciphertext string) (checksum string, err error)
+	// ComputeFeeCommitment computes the commitment of a credits record holding amount microcredits.
+	ComputeFeeCommitment(amount uint64, key []byte, nonce string) (commitment string, err error)
+	// SumRecordBalances returns the total microcredits of a list of plaintext credits records.
+	SumRecordBalances(records []string) (total uint64, err error)
+	// RecordOwner returns the owner address of a plaintext record.
+	RecordOwner(record string) (owner string, err error)
+	// RecordMatchesCommitment checks that a decrypted record recomputes to a commitment.
+	RecordMatchesCommitment(record, commitment, programID, recordName string) (matches bool, err error)
+	// AllocationCount returns the number of WASM buffers that haven't been deallocated yet.
+	AllocationCount() (count uint64, err error)
+	// EnableBufferPool turns reuse of deallocated WASM buffers on or off.
+	EnableBufferPool(on bool) error
+	// SetMaxAlloc limits the size of a single WASM buffer allocated for an argument.
+	SetMaxAlloc(maxBytes uint64) error
+	// HeapAllocationCount returns the number of WASM buffers that were requested from the allocator.
+	HeapAllocationCount() (count uint64, err error)
+	// ABIInfo returns the FFI contract the WASM module was built with.
+	ABIInfo() (info ABIInfo, err error)
+	// LastError returns the message of the last error logged by the WASM module, if any.
+	LastError() (message string, err error)
+	// SignFields signs a message given as a list of field literals.
+	SignFields(key []byte, fields []string) (signature string, err error)
+	// VerifySignatureFields verifies a signature created with SignFields against an address.
+	VerifySignatureFields(address string, signature string, fields []string) (valid bool, err error)
+	// SignMessageWithNonce signs like Sign, but binds a replay-protection nonce into the signature.
+	SignMessageWithNonce(key []byte, message []byte, nonce []byte) (signature string, err error)
+	// VerifySignatureWithNonce verifies a signature created with SignMessageWithNonce.
+	VerifySignatureWithNonce(address string, signature string, message []byte, nonce []byte) (valid bool, err error)
+	// SignHashedMessage hashes a message with a HASH_ algorithm and deterministically signs the digest.
+	SignHashedMessage(key []byte, message []byte, hashAlgorithm uint32, seed []byte) (signature string, err error)
+	// VerifyHashedMessage verifies a signature created with SignHashedMessage.
+	VerifyHashedMessage(address string, signature string, message []byte, hashAlgorithm uint32) (valid bool, err error)
+	// VerifyAllSignatures verifies that every signature is valid for the address at the same index.
+	VerifyAllSignatures(message []byte, addresses, signatures []string) (valid bool, err error)
+	// VerifyAgainstAny returns the index of the first address a signature is valid for.
+	VerifyAgainstAny(addresses []string, signature string, message []byte) (index uint64, found bool, err error)
+	// SignatureInspect returns the signer address and challenge a signature commits to.
+	SignatureInspect(signature string) (address string, challenge string, err error)
+	// SignatureToJSON returns a signature as a JSON object for structured logs.
+	SignatureToJSON(signature string) (json string, err error)
+	// AccountToJSON derives all keys of an account and returns them as one JSON object for wallet export.
+	AccountToJSON(key []byte) (json []byte, err error)
+	// SignatureToBytes converts a signature string into its SIGNATURE_BYTES_SIZE binary encoding.
+	SignatureToBytes(signature string) (bytes []byte, err error)
+	// SignatureFromBytes converts a binary signature encoding back into its string form.
+	SignatureFromBytes(bytes []byte) (signature string, err error)
+	// ComputeTPK recovers the transition public key of a transition signed with key from its tvk.
+	ComputeTPK(key []byte, tvk string) (tpk string, err error)
+	// ComputeTVK derives the transition view key of a transition signed with key from its tpk.
+	ComputeTVK(key []byte, tpk string) (tvk string, err error)
+	// DeriveOutputRandomizers derives the encryption randomizer and nonce of an output record.
+	DeriveOutputRandomizers(key []byte, tvk string, index uint64) (randomizer, nonce string, err error)
+	// TransactionTransitionIDs returns the transition IDs of a transaction in its canonical bytes.
+	TransactionTransitionIDs(txBytes []byte) (transitionIDs []string, err error)
+	// VerifyBalanceInvariant checks that credits records consumed by a transfer cover its outputs and fee.
+	VerifyBalanceInvariant(inputs, outputs []string, fee uint64) (balanced bool, err error)
+	// VerifyRequest checks the signature and inputs of a request against the function's input types.
+	VerifyRequest(request string, inputTypes []string) (valid bool, err error)
 	// Sign creates an Aleo-compatible Schnorr signature. The private key is not
 	// copied as a string and is wiped from WASM memory immediately after use.
 	Sign(key []byte, message []byte) (signature string, err error)
+	// DeterministicSign signs like Sign, but derives the nonce from the key and the message.
+	DeterministicSign(key []byte, message []byte) (signature string, err error)
 
 	Close()
 }
@@ -47,6 +286,9 @@ type aleoWrapperSession struct {
 	hashMessageBytes api.Function
 	formatMessage    api.Function
 	recoverMessage   api.Function
+
+	// all resolved exports by name, used by the generic call helpers
+	exports map[string]api.Function
 }
 
 func (s *aleoWrapperSession) Close() {
@@ -62,6 +304,10 @@ func (s *aleoWrapperSession) allocateSafe(size uint64) (ptr uint64, actualCapaci
 	if err != nil {
 		return 0, 0, err
 	}
+	if result[0] == 0 {
+		// alloc rejected the size, see SetMaxAlloc
+		return 0, 0, fmt.Errorf("failed to allocate %d bytes", size)
+	}
 	// We don't know the actual capacity (it's stored internally). Return size for logging only.
 	return result[0], size, nil
 }
@@ -76,6 +322,202 @@ func decodeLenPtr(encoded uint64) (ptr uint32, length uint32) {
 	return uint32(encoded), uint32(encoded >> 32)
 }
 
+// encodeFrames encodes a list of items as a framed buffer, where every item is prefixed with its
+// length as a 4-byte little-endian integer. Lists are passed to the WASM module in this format.
+func encodeFrames(items [][]byte) []byte {
+	size := 0
+	for _, item := range items {
+		size += 4 + len(item)
+	}
+
+	buf := make([]byte, 0, size)
+	for _, item := range items {
+		buf = binary.LittleEndian.AppendUint32(buf, uint32(len(item)))
+		buf = append(buf, item...)
+	}
+
+	return buf
+}
+
+// decodeFrames splits a framed buffer returned by the WASM module into its items.
+func decodeFrames(buf []byte) ([][]byte, error) {
+	items := make([][]byte, 0)
+	for len(buf) > 0 {
+		if len(buf) < 4 {
+			return nil, errors.New("malformed framed buffer: truncated length")
+		}
+		itemLen := binary.LittleEndian.Uint32(buf)
+		buf = buf[4:]
+		if uint64(len(buf)) < uint64(itemLen) {
+			return nil, errors.New("malformed framed buffer: item exceeds buffer")
+		}
+		items = append(items, buf[:itemLen])
+		buf = buf[itemLen:]
+	}
+
+	return items, nil
+}
+
+// decodeStringFrames splits a framed buffer into strings.
+func decodeStringFrames(buf []byte) ([]string, error) {
+	items, err := decodeFrames(buf)
+	if err != nil {
+		return nil, err
+	}
+
+	strs := make([]string, len(items))
+	for i, item := range items {
+		strs[i] = string(item)
+	}
+
+	return strs, nil
+}
+
+// encodeStringFrames encodes a list of strings as a framed buffer.
+func encodeStringFrames(strs []string) []byte {
+	items := make([][]byte, len(strs))
+	for i, str := range strs {
+		items[i] = []byte(str)
+	}
+
+	return encodeFrames(items)
+}
+
+func recoveredError(r any) error {
+	switch x := r.(type) {
+	case string:
+		return errors.New(x)
+	case error:
+		return x
+	default:
+		return errors.New("unknown panic")
+	}
+}
+
+// call invokes the named export. Every []byte argument is copied into WASM memory and passed as
+// a (pointer, length) pair, integer arguments are passed as is. Input regions are wiped before
+// they are deallocated, so it's safe to pass secrets. Returns the raw result of the export.
+func (s *aleoWrapperSession) call(name string, args ...any) (result uint64, err error) {
+	if s.mod == nil || s.mod.IsClosed() {
+		return 0, ErrNoModule
+	}
+
+	defer func() {
+		if r := recover(); r != nil {
+			err = recoveredError(r)
+			result = 0
+		}
+	}()
+
+	fn, ok := s.exports[name]
+	if !ok || fn == nil {
+		return 0, fmt.Errorf("missing wasm export: %s", name)
+	}
+
+	params := make([]uint64, 0, len(args)*2)
+	for _, arg := range args {
+		switch v := arg.(type) {
+		case []byte:
+			ptr, _, err := s.allocateSafe(uint64(len(v)))
+			if err != nil {
+				log.Println(name, "allocate error:", err)
+				return 0, fmt.Errorf("failed to allocate memory for %s", name)
+			}
+			defer func(ptr uint64, length int) {
+				_ = s.mod.Memory().Write(uint32(ptr), make([]byte, length))
+				if err := s.deallocateSafe(ptr, 0); err != nil {
+					log.Printf("Failed to deallocate %s argument memory: %v", name, err)
+				}
+			}(ptr, len(v))
+
+			if !s.mod.Memory().Write(uint32(ptr), v) {
+				return 0, fmt.Errorf("failed to write %s argument to memory", name)
+			}
+			params = append(params, ptr, uint64(len(v)))
+		case uint64:
+			params = append(params, v)
+		case uint32:
+			params = append(params, uint64(v))
+		case int:
+			params = append(params, uint64(v))
+		default:
+			return 0, fmt.Errorf("unsupported %s argument type %T", name, arg)
+		}
+	}
+
+	out, err := fn.Call(s.ctx, params...)
+	if err != nil {
+		log.Println(name, "error:", err)
+		return 0, fmt.Errorf("failed to call %s", name)
+	}
+	if len(fn.Definition().ResultTypes()) == 0 {
+		// exports without a result, like scan_session_close
+		return 0, nil
+	}
+	if len(out) == 0 {
+		return 0, fmt.Errorf("%s: empty return", name)
+	}
+
+	return out[0], nil
+}
+
+// callOutput invokes the named export (see call) and returns a copy of the buffer it returned.
+// A null return is reported as an error.
+func (s *aleoWrapperSession) callOutput(name string, args ...any) ([]byte, error) {
+	result, err := s.call(name, args...)
+	if err != nil {
+		return nil, err
+	}
+	if result == 0 {
+		return nil, fmt.Errorf("%s failed", name)
+	}
+
+	return s.readOutput(result, false)
+}
+
+// callSecretOutput is like callOutput, but wipes the returned buffer in WASM memory before
+// deallocating it. Use it for exports returning private keys or other secrets.
+func (s *aleoWrapperSession) callSecretOutput(name string, args ...any) ([]byte, error) {
+	result, err := s.call(name, args...)
+	if err != nil {
+		return nil, err
+	}
+	if result == 0 {
+		return nil, fmt.Errorf("%s failed", name)
+	}
+
+	return s.readOutput(result, true)
+}
+
+// readOutput copies a buffer returned by an export as (length << 32) | pointer out of WASM memory
+// and deallocates it. With wipe set the buffer is released with free_secret, which zeroizes it
+// before deallocating.
+func (s *aleoWrapperSession) readOutput(packed uint64, wipe bool) ([]byte, error) {
+	ptr, length := decodeLenPtr(packed)
+	defer func() {
+		if wipe {
+			if _, err := s.exports["free_secret"].Call(s.ctx, packed); err != nil {
+				log.Printf("Failed to free secret output memory: %v", err)
+			}
+			return
+		}
+		if err := s.deallocateSafe(uint64(ptr), 0); err != nil {
+			log.Printf("Failed to deallocate output memory: %v", err)
+		}
+	}()
+
+	buf, ok := s.mod.Memory().Read(ptr, length)
+	if !ok {
+		return nil, errors.New("failed to read output from memory")
+	}
+
+	// memory read returns a slice of wasm memory buffer, it needs to be copied
+	output := make([]byte, len(buf))
+	copy(output, buf)
+
+	return output, nil
+}
+
 // NewPrivateKey generates a new Aleo private key, returns it's string representation and the address derived from that private key.
 func (s *aleoWrapperSession) NewPrivateKey() (key []byte, address string, err error) {
 	if s.mod == nil || s.mod.IsClosed() {
@@ -115,15 +557,12 @@ func (s *aleoWrapperSession) NewPrivateKey() (key []byte, address string, err er
 	if keyLen != PRIVATE_KEY_SIZE {
 		log.Printf("unexpected private key length %d (expected %d)", keyLen, PRIVATE_KEY_SIZE)
 	}
-	defer func(ptr uint32, length uint32) {
-		// Zero out the memory first
-		zero := make([]byte, int(length))
-		_ = s.mod.Memory().Write(ptr, zero)
-		// Then deallocate
-		if err := s.deallocateSafe(uint64(ptr), 0); err != nil {
-			log.Printf("Failed to deallocate private key memory: %v", err)
+	defer func(packed uint64) {
+		// Zero out the memory and deallocate it in one call
+		if _, err := s.exports["free_secret"].Call(s.ctx, packed); err != nil {
+			log.Printf("Failed to free private key memory: %v", err)
 		}
-	}(keyPtr, keyLen)
+	}(privKeyPtr[0])
 
 	// read wasm memory at pointer for the private key string
 	privKeyWasm, ok := s.mod.Memory().Read(keyPtr, keyLen)
@@ -602,3 +1041,1842 @@ func (s *aleoWrapperSession) Sign(key []byte, message []byte) (signature string,
 
 	return
 }
+
+// HashToAddress hashes a message (a string representation of a Leo value) to a group element using
+// Poseidon2 and returns the resulting address, matching Leo's Poseidon2::hash_to_address.
+func (s *aleoWrapperSession) HashToAddress(message []byte) (address string, err error) {
+	out, err := s.callOutput("hash_to_address", message)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// MaxInputBytes returns the maximum number of input bytes a hash or commitment operation (one of
+// the HASH_* codes) accepts, or UNBOUNDED_INPUT if it accepts inputs of any size. Use it to reject
+// oversized inputs before calling the operation.
+func (s *aleoWrapperSession) MaxInputBytes(operation uint32) (limit uint64, err error) {
+	limit, err = s.call("max_input_bytes", operation)
+	if err != nil {
+		return 0, err
+	}
+	if limit == 0 {
+		return 0, fmt.Errorf("unknown operation code %d", operation)
+	}
+
+	return limit, nil
+}
+
+// HashBytes hashes raw bytes with a hash operation (HASH_*) and returns the digest. Keccak and
+// SHA-3 are the standard byte-oriented functions. BHP and Pedersen hash the little-endian bits of
+// the input, Poseidon hashes the input packed into fields like StringToFields does. Their field
+// digests are returned as 32 little-endian bytes.
+func (s *aleoWrapperSession) HashBytes(operation uint32, input []byte) (digest []byte, err error) {
+	return s.callOutput("hash_bytes", operation, input)
+}
+
+// HashConcat hashes the concatenation of parts with a hash operation (HASH_*) and returns the same
+// digest HashBytes returns for the joined bytes, without joining them in Go first.
+func (s *aleoWrapperSession) HashConcat(operation uint32, parts [][]byte) (digest []byte, err error) {
+	return s.callOutput("hash_concat", operation, encodeFrames(parts))
+}
+
+// HashWithDomain hashes input under a domain tag with a hash operation (HASH_*), so services using
+// the same hash for several purposes get unrelated digests for each. The hashed bytes are the
+// domain length as a 4-byte little-endian integer, the domain and then the input, the digest is
+// the one HashBytes returns for those bytes.
+func (s *aleoWrapperSession) HashWithDomain(operation uint32, domain []byte, input []byte) (digest []byte, err error) {
+	return s.callOutput("hash_with_domain", operation, domain, input)
+}
+
+// HashMulti hashes the same input with every operation in operations (see HashBytes) and returns
+// the digests in the same order. The input crosses the FFI boundary once, which saves copies when
+// several digests of the same data are needed.
+func (s *aleoWrapperSession) HashMulti(operations []uint32, input []byte) (digests [][]byte, err error) {
+	if len(operations) == 0 {
+		return nil, errors.New("no hash operations")
+	}
+
+	spec := make([][]byte, len(operations))
+	for i, operation := range operations {
+		spec[i] = binary.LittleEndian.AppendUint32(nil, operation)
+	}
+
+	out, err := s.callOutput("hash_multi", encodeFrames(spec), input)
+	if err != nil {
+		return nil, err
+	}
+
+	return decodeFrames(out)
+}
+
+// GetAddress returns the address derived from a private key.
+func (s *aleoWrapperSession) GetAddress(key []byte) (address string, err error) {
+	out, err := s.callOutput("get_address", key)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// GetAddressExt returns the address derived from a private key in the given format:
+// ADDRESS_FORMAT_BECH32 for the aleo1 string (as bytes, same as GetAddress) or ADDRESS_FORMAT_RAW
+// for the ADDRESS_RAW_SIZE byte little-endian x-coordinate of the address group element.
+func (s *aleoWrapperSession) GetAddressExt(key []byte, format uint32) (address []byte, err error) {
+	if format != ADDRESS_FORMAT_BECH32 && format != ADDRESS_FORMAT_RAW {
+		return nil, fmt.Errorf("unknown address format %d", format)
+	}
+
+	return s.callOutput("get_address_ext", key, format)
+}
+
+// GetComputeKey returns the components of the compute key of a private key: the pk_sig and
+// pr_sig group elements and the sk_prf scalar, as strings.
+func (s *aleoWrapperSession) GetComputeKey(key []byte) (pkSig, prSig, skPrf string, err error) {
+	out, err := s.callOutput("get_compute_key", key)
+	if err != nil {
+		return "", "", "", err
+	}
+
+	items, err := decodeStringFrames(out)
+	if err != nil {
+		return "", "", "", err
+	}
+	if len(items) != 3 {
+		return "", "", "", fmt.Errorf("get_compute_key: expected 3 components, got %d", len(items))
+	}
+
+	return items[0], items[1], items[2], nil
+}
+
+// PrivateKeyComponents returns the secret scalars a private key consists of: sk_sig, the signature
+// secret key, and r_sig, the randomizer of the compute key's pr_sig, as scalar strings.
+//
+// The components are as sensitive as the private key: they're all signing needs, so anyone holding
+// them can sign as the account. Never log or store them unencrypted. The output is wiped from WASM
+// memory after being copied, but the returned strings can't be zeroized, so only use this for
+// research and integrations that need the raw material.
+func (s *aleoWrapperSession) PrivateKeyComponents(key []byte) (skSig, rSig string, err error) {
+	out, err := s.callSecretOutput("private_key_components", key)
+	if err != nil {
+		return "", "", err
+	}
+
+	items, err := decodeStringFrames(out)
+	if err != nil {
+		return "", "", err
+	}
+	if len(items) != 2 {
+		return "", "", fmt.Errorf("private_key_components: expected 2 components, got %d", len(items))
+	}
+
+	return items[0], items[1], nil
+}
+
+// AddressFromComponents derives an address from compute key components, e.g. ones returned by
+// GetComputeKey or stored separately by a key manager. snarkVM derives sk_prf from pk_sig and
+// pr_sig, so an sk_prf that doesn't match them is rejected.
+func (s *aleoWrapperSession) AddressFromComponents(pkSig, prSig, skPrf string) (address string, err error) {
+	out, err := s.callOutput("address_from_components", []byte(pkSig), []byte(prSig), []byte(skPrf))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// GetViewKey returns the view key derived from a private key. The caller is responsible for
+// zeroizing the returned slice when it is no longer needed.
+func (s *aleoWrapperSession) GetViewKey(key []byte) (viewKey []byte, err error) {
+	return s.callSecretOutput("get_view_key", key)
+}
+
+// ViewKeySecret returns the secret scalar of a view key as a scalar string. Records are decrypted
+// with the record view key (nonce * secret).x and the address is G * secret, so this is the
+// material a custom, ECDH-style decryption pipeline needs. The secret is as sensitive as the view
+// key: it decrypts every record the account owns. The caller is responsible for zeroizing the
+// returned slice when it is no longer needed.
+func (s *aleoWrapperSession) ViewKeySecret(viewKey []byte) (secret []byte, err error) {
+	return s.callSecretOutput("view_key_secret", viewKey)
+}
+
+// DeriveChildKey deterministically derives the child private key at index from a 32-byte seed.
+// The child key seed is Poseidon2(domain, seed[0:16], seed[16:32], index) with the seed halves and
+// index read as little-endian integers and "AleoUtilsChildKey0" as the domain separator.
+// The caller is responsible for zeroizing the returned slice.
+func (s *aleoWrapperSession) DeriveChildKey(seed []byte, index uint32) (key []byte, err error) {
+	if len(seed) != SEED_SIZE {
+		return nil, fmt.Errorf("seed must be %d bytes long", SEED_SIZE)
+	}
+
+	return s.callSecretOutput("derive_child_key", seed, index)
+}
+
+// DeriveAddressRange derives count child keys starting at index start (see DeriveChildKey) and
+// returns their addresses. At most MAX_DERIVE_RANGE addresses can be derived in one call.
+func (s *aleoWrapperSession) DeriveAddressRange(seed []byte, start, count uint32) (addresses []string, err error) {
+	if len(seed) != SEED_SIZE {
+		return nil, fmt.Errorf("seed must be %d bytes long", SEED_SIZE)
+	}
+	if count < 1 || count > MAX_DERIVE_RANGE {
+		return nil, fmt.Errorf("number of addresses must be between 1 and %d", MAX_DERIVE_RANGE)
+	}
+
+	out, err := s.callOutput("derive_address_range", seed, start, count)
+	if err != nil {
+		return nil, err
+	}
+
+	return decodeStringFrames(out)
+}
+
+// RecordLeafHash computes the leaf hash a record output has in the transition tree of the
+// transition that created it, the first step of the state path proving the record's inclusion in
+// a block's state root. commitment is the record commitment (a field string, e.g. "123field") and
+// index the position of the output in the transition: the number of inputs plus the index of the
+// output. The leaf is snarkVM's TransitionLeaf (version 1, index, variant 3 for records,
+// commitment) hashed with BHP1024 over a false domain bit followed by its little-endian bits.
+func (s *aleoWrapperSession) RecordLeafHash(commitment string, index uint8) (leafHash string, err error) {
+	out, err := s.callOutput("record_leaf_hash", []byte(commitment), uint32(index))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// VerifySignature verifies a signature created with Sign over message (the little-endian byte
+// representation of a Leo u128, see HashMessage) against an address. Invalid or unparsable
+// inputs are reported as an invalid signature. Verification doesn't short-circuit on the first
+// rejected input, see the verify_signature export for the guarantees and their limits.
+func (s *aleoWrapperSession) VerifySignature(address string, signature string, message []byte) (valid bool, err error) {
+	result, err := s.call("verify_signature", []byte(address), []byte(signature), message)
+	if err != nil {
+		return false, err
+	}
+
+	return result == 1, nil
+}
+
+// StringToFields encodes a UTF-8 string as Leo field literals. The first field holds the length
+// of the string in bytes, followed by the bytes split into 31-byte chunks, each read as a
+// little-endian integer.
+func (s *aleoWrapperSession) StringToFields(str string) (fields []string, err error) {
+	out, err := s.callOutput("string_to_fields", []byte(str))
+	if err != nil {
+		return nil, err
+	}
+
+	return decodeStringFrames(out)
+}
+
+// FieldsToString decodes field literals created by StringToFields back into the string.
+func (s *aleoWrapperSession) FieldsToString(fields []string) (str string, err error) {
+	out, err := s.callOutput("fields_to_string", encodeStringFrames(fields))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// BaseFieldModulus returns the modulus of the base field, the field of Leo field values.
+// Use it to range check values before passing them as field literals.
+func (s *aleoWrapperSession) BaseFieldModulus() (modulus *big.Int, err error) {
+	return s.modulus("base_field_modulus")
+}
+
+// ScalarFieldModulus returns the modulus of the scalar field, the field of Leo scalar values.
+func (s *aleoWrapperSession) ScalarFieldModulus() (modulus *big.Int, err error) {
+	return s.modulus("scalar_field_modulus")
+}
+
+func (s *aleoWrapperSession) modulus(name string) (*big.Int, error) {
+	out, err := s.callOutput(name)
+	if err != nil {
+		return nil, err
+	}
+
+	modulus, ok := new(big.Int).SetString(string(out), 10)
+	if !ok {
+		return nil, fmt.Errorf("%s returned an invalid modulus: %s", name, out)
+	}
+
+	return modulus, nil
+}
+
+// ComputeRecordCommitment computes the commitment of a plaintext record (including its _nonce) the
+// way snarkVM does for transition outputs. programID is the program defining the record, e.g.
+// "credits.aleo", and recordName the name of its record type, e.g. "credits". Returns the
+// commitment as a field string.
+func (s *aleoWrapperSession) ComputeRecordCommitment(record, programID, recordName string) (commitment string, err error) {
+	out, err := s.callOutput("compute_record_commitment", []byte(record), []byte(programID), []byte(recordName))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// ComputeRecordTag computes the tag of a record commitment for a view key, the value the ledger
+// stores for spent records. Every call parses the view key again, use NewScanSession to compute
+// the tags of many commitments. Returns the tag as a field string.
+func (s *aleoWrapperSession) ComputeRecordTag(viewKey []byte, commitment string) (tag string, err error) {
+	out, err := s.callOutput("compute_record_tag", viewKey, []byte(commitment))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// ScanSession holds a parsed view key and its tag secret inside the WASM module, so tags can be
+// computed for many commitments without parsing the view key for every one of them. It belongs to
+// the session that created it and shares its goroutine restrictions. Call Close to release it.
+type ScanSession struct {
+	s      *aleoWrapperSession
+	handle uint64
+}
+
+// NewScanSession starts a scan session for a view key.
+func (s *aleoWrapperSession) NewScanSession(viewKey []byte) (scan *ScanSession, err error) {
+	handle, err := s.call("scan_session_new", viewKey)
+	if err != nil {
+		return nil, err
+	}
+	if handle == 0 {
+		return nil, errors.New("scan_session_new failed")
+	}
+
+	return &ScanSession{s: s, handle: handle}, nil
+}
+
+// Tag computes the tag of a record commitment, the result is the same as ComputeRecordTag for
+// the view key of the scan session.
+func (scan *ScanSession) Tag(commitment string) (tag string, err error) {
+	if scan.handle == 0 {
+		return "", errors.New("scan session is closed")
+	}
+
+	out, err := scan.s.callOutput("scan_session_tag", scan.handle, []byte(commitment))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// Close releases the scan session. It's safe to call Close more than once.
+func (scan *ScanSession) Close() error {
+	if scan.handle == 0 {
+		return nil
+	}
+
+	handle := scan.handle
+	scan.handle = 0
+	_, err := scan.s.call("scan_session_close", handle)
+
+	return err
+}
+
+// LastError returns the message of the last error logged by the WASM module, or an empty string
+// if there was none since it was cleared.
+func (s *aleoWrapperSession) LastError() (message string, err error) {
+	result, err := s.call("last_error")
+	if err != nil {
+		return "", err
+	}
+	if result == 0 {
+		return "", nil
+	}
+
+	out, err := s.readOutput(result, false)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// RecordMatchesCommitment recomputes the commitment of a decrypted record (see
+// ComputeRecordCommitment) and compares it to commitment. A mismatch is reported as false
+// without an error and means the record was decrypted with the wrong view key or tampered with.
+// An input that fails to parse is reported as an error.
+func (s *aleoWrapperSession) RecordMatchesCommitment(record, commitment, programID, recordName string) (matches bool, err error) {
+	result, err := s.call("record_matches_commitment", []byte(record), []byte(commitment), []byte(programID), []byte(recordName))
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	if message != "" {
+		return false, fmt.Errorf("record_matches_commitment: %s", message)
+	}
+
+	return false, nil
+}
+
+// SignatureToBytes converts a signature string into snarkVM's fixed-width binary encoding of
+// SIGNATURE_BYTES_SIZE bytes: the challenge and response scalars followed by the pk_sig and pr_sig
+// group elements of the signer's compute key.
+func (s *aleoWrapperSession) SignatureToBytes(signature string) (bytes []byte, err error) {
+	return s.callOutput("signature_to_bytes", []byte(signature))
+}
+
+// SignatureFromBytes converts a binary signature encoding created by SignatureToBytes back into
+// its string form.
+func (s *aleoWrapperSession) SignatureFromBytes(bytes []byte) (signature string, err error) {
+	if len(bytes) != SIGNATURE_BYTES_SIZE {
+		return "", fmt.Errorf("signature bytes must be %d bytes long, got %d", SIGNATURE_BYTES_SIZE, len(bytes))
+	}
+
+	out, err := s.callOutput("signature_from_bytes", bytes)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// VerifyAllSignatures verifies signatures created with Sign over the same message, where
+// signatures[i] must be valid for addresses[i]. Returns true only if every pair verifies, which
+// lets callers enforce m-of-n policies on the set of signers with a single check. The lists must be
+// non-empty and have the same length.
+func (s *aleoWrapperSession) VerifyAllSignatures(message []byte, addresses, signatures []string) (valid bool, err error) {
+	if len(addresses) == 0 || len(addresses) != len(signatures) {
+		return false, fmt.Errorf("expected the same non-zero number of addresses and signatures, got %d and %d", len(addresses), len(signatures))
+	}
+
+	result, err := s.call("verify_all_signatures", message, encodeStringFrames(addresses), encodeStringFrames(signatures))
+	if err != nil {
+		return false, err
+	}
+
+	return result == 1, nil
+}
+
+// DeterministicSign creates a signature like Sign, but instead of sampling the nonce from entropy
+// it derives it from the private key and the message, in the spirit of RFC 6979. Signing the same
+// message with the same key always returns the same signature. The nonce RNG is a ChaCha20 stream
+// seeded with Poseidon2(domain, private key seed, message fields), see the sign module for details.
+func (s *aleoWrapperSession) DeterministicSign(key []byte, message []byte) (signature string, err error) {
+	out, err := s.callOutput("deterministic_sign", key, message)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// ParseProgramInputs splits CLI-style program inputs separated by whitespace and/or commas, e.g.
+// "5u64 aleo1... true", validates every input as the Aleo literal type it implies and returns the
+// canonical form of each. String literals containing whitespace or commas aren't supported. The
+// error of a malformed input names the offending token.
+func (s *aleoWrapperSession) ParseProgramInputs(inputs string) (values []string, err error) {
+	result, err := s.call("parse_program_inputs", []byte(inputs))
+	if err != nil {
+		return nil, err
+	}
+	if result == 0 {
+		message, err := s.LastError()
+		if err != nil {
+			return nil, err
+		}
+		return nil, fmt.Errorf("parse_program_inputs failed: %s", message)
+	}
+
+	out, err := s.readOutput(result, false)
+	if err != nil {
+		return nil, err
+	}
+
+	return decodeStringFrames(out)
+}
+
+// DecimalToField parses a non-negative base-10 integer string of any size and returns it as a
+// field string, reduced modulo the base field (see BaseFieldModulus). Only ASCII digits are
+// accepted.
+func (s *aleoWrapperSession) DecimalToField(decimal string) (field string, err error) {
+	out, err := s.callOutput("decimal_to_field", []byte(decimal))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// FieldToDecimal returns the value of a field string as a base-10 integer string.
+func (s *aleoWrapperSession) FieldToDecimal(field string) (decimal string, err error) {
+	out, err := s.callOutput("field_to_decimal", []byte(field))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// ABIInfo describes the FFI contract of the WASM module.
+type ABIInfo struct {
+	// PointerWidth is the width of a WASM pointer in bits
+	PointerWidth uint32
+	// LittleEndian is true if the module uses little-endian byte order
+	LittleEndian bool
+	// AllocHeaderSize is the size of the capacity header preceding allocated buffers, in bytes
+	AllocHeaderSize uint32
+	// PackedPtrShift and PackedLenShift are the bit offsets of the pointer and length in the
+	// packed values returned by exports
+	PackedPtrShift uint32
+	PackedLenShift uint32
+}
+
+// ABIInfo returns the FFI contract the WASM module was built with.
+func (s *aleoWrapperSession) ABIInfo() (info ABIInfo, err error) {
+	out, err := s.callOutput("abi_info")
+	if err != nil {
+		return ABIInfo{}, err
+	}
+
+	items, err := decodeFrames(out)
+	if err != nil {
+		return ABIInfo{}, err
+	}
+	if len(items) != 5 {
+		return ABIInfo{}, fmt.Errorf("abi_info: expected 5 values, got %d", len(items))
+	}
+	values := make([]uint32, len(items))
+	for i, item := range items {
+		if len(item) != 4 {
+			return ABIInfo{}, fmt.Errorf("abi_info: value %d is %d bytes long, expected 4", i, len(item))
+		}
+		values[i] = binary.LittleEndian.Uint32(item)
+	}
+
+	return ABIInfo{
+		PointerWidth:    values[0],
+		LittleEndian:    values[1] == 1,
+		AllocHeaderSize: values[2],
+		PackedPtrShift:  values[3],
+		PackedLenShift:  values[4],
+	}, nil
+}
+
+// checkABI fails if the FFI contract of the module differs from the one the bindings expect.
+func (s *aleoWrapperSession) checkABI() error {
+	info, err := s.ABIInfo()
+	if err != nil {
+		return fmt.Errorf("failed to read wasm module ABI: %w", err)
+	}
+
+	expected := ABIInfo{
+		PointerWidth:    ABI_POINTER_WIDTH,
+		LittleEndian:    true,
+		AllocHeaderSize: ABI_ALLOC_HEADER_SIZE,
+		PackedPtrShift:  ABI_PACKED_PTR_SHIFT,
+		PackedLenShift:  ABI_PACKED_LEN_SHIFT,
+	}
+	if info != expected {
+		return fmt.Errorf("wasm module ABI mismatch: got %+v, expected %+v", info, expected)
+	}
+
+	return nil
+}
+
+// ComputeTPK computes the transition public key (tpk) of a transition whose request was signed with
+// key, from its transition view key (tvk, a field string). snarkVM sets tpk = G * r and
+// tvk = (address * r).x for a random nonce r, so the tpk is recovered as the subgroup point with
+// x-coordinate tvk multiplied by the inverse of the view key. Returns the tpk as a group string.
+func (s *aleoWrapperSession) ComputeTPK(key []byte, tvk string) (tpk string, err error) {
+	out, err := s.callOutput("compute_tpk", key, []byte(tvk))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// CiphertextCommitment returns the checksum the chain stores next to a record ciphertext in a
+// transition output: BHP1024 over the little-endian bits of the ciphertext. The output commitment
+// is computed over the plaintext and can't be derived without decrypting, the checksum is what
+// binds a ciphertext to its output. Returns the checksum as a field string.
+func (s *aleoWrapperSession) CiphertextCommitment(ciphertext string) (checksum string, err error) {
+	out, err := s.callOutput("ciphertext_commitment", []byte(ciphertext))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// AllocationCount returns the number of buffers allocated in WASM memory, either for export
+// arguments or for their results, that haven't been deallocated yet. It stays constant across
+// calls that don't leak memory.
+func (s *aleoWrapperSession) AllocationCount() (count uint64, err error) {
+	return s.call("allocation_count")
+}
+
+// SignFields signs a message given as a list of field literals, the way snarkVM signs a field
+// array, for messages longer than the single u128 Sign accepts. Returns the signature string.
+func (s *aleoWrapperSession) SignFields(key []byte, fields []string) (signature string, err error) {
+	out, err := s.callOutput("sign_fields", key, encodeStringFrames(fields))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// VerifySignatureFields verifies a signature over a message given as a list of field literals
+// (see SignFields) against an address.
+func (s *aleoWrapperSession) VerifySignatureFields(address string, signature string, fields []string) (valid bool, err error) {
+	result, err := s.call("verify_signature_fields", []byte(address), []byte(signature), encodeStringFrames(fields))
+	if err != nil {
+		return false, err
+	}
+
+	return result == 1, nil
+}
+
+// RecordOwner returns the owner address of a decrypted plaintext record, whether its owner entry
+// is public or private. Returns ErrRecordNoOwner if the record lacks an owner entry, and a parse
+// error if it's malformed otherwise.
+func (s *aleoWrapperSession) RecordOwner(record string) (owner string, err error) {
+	result, err := s.call("record_owner", []byte(record))
+	if err != nil {
+		return "", err
+	}
+	if result == 0 {
+		message, err := s.LastError()
+		if err != nil {
+			return "", err
+		}
+		if message == ErrRecordNoOwner.Error() {
+			return "", ErrRecordNoOwner
+		}
+		return "", fmt.Errorf("record_owner failed: %s", message)
+	}
+
+	out, err := s.readOutput(result, false)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// EnableBufferPool turns the buffer pool of the WASM module on or off. With the pool on,
+// deallocated buffers are kept in a freelist by power of two size bucket and reused for following
+// allocations, which reduces allocator churn for repeated calls such as signing under load.
+// Turning it off releases the pooled buffers. The pool is off by default.
+func (s *aleoWrapperSession) EnableBufferPool(on bool) error {
+	var flag uint32
+	if on {
+		flag = 1
+	}
+
+	_, err := s.call("enable_buffer_pool", flag)
+	return err
+}
+
+// HeapAllocationCount returns the number of buffers, for export arguments or results, that the
+// WASM module requested from its allocator since the session was created. Buffers reused from the
+// pool (see EnableBufferPool) aren't counted.
+func (s *aleoWrapperSession) HeapAllocationCount() (count uint64, err error) {
+	return s.call("heap_allocation_count")
+}
+
+// FieldCountForBytes returns the number of field elements a payload of byteLen bytes occupies
+// under the packing scheme of StringToFields: one length field plus one field per started 31-byte
+// chunk. Use it to pre-size field arrays.
+func (s *aleoWrapperSession) FieldCountForBytes(byteLen uint32) (count uint64, err error) {
+	return s.call("field_count_for_bytes", byteLen)
+}
+
+// SignMessageWithNonce signs message (see Sign) bound to a replay-protection nonce such as a
+// counter or session id. The signature covers the message fields followed by the nonce packed like
+// StringToFields packs bytes, so the same message signed under different nonces yields distinct
+// signatures, each only valid for its own nonce.
+func (s *aleoWrapperSession) SignMessageWithNonce(key []byte, message []byte, nonce []byte) (signature string, err error) {
+	out, err := s.callOutput("sign_message_with_nonce", key, message, nonce)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// VerifySignatureWithNonce verifies a signature created with SignMessageWithNonce over message and
+// nonce against an address. A signature made under a different nonce is reported as invalid.
+func (s *aleoWrapperSession) VerifySignatureWithNonce(address string, signature string, message []byte, nonce []byte) (valid bool, err error) {
+	result, err := s.call("verify_signature_with_nonce", []byte(address), []byte(signature), message, nonce)
+	if err != nil {
+		return false, err
+	}
+
+	return result == 1, nil
+}
+
+// ValueToBits returns the bits snarkVM decomposes an Aleo value (a plaintext or a record) into,
+// one byte per bit holding 0 or 1, in little-endian (to_bits_le) or big-endian (to_bits_be)
+// order. Plaintext bits start with snarkVM's type header, so this is the exact input on-chain
+// hashes see, useful when debugging why an off-chain hash diverges.
+func (s *aleoWrapperSession) ValueToBits(value string, littleEndian bool) (bits []byte, err error) {
+	var endianness uint32
+	if littleEndian {
+		endianness = 1
+	}
+
+	return s.callOutput("value_to_bits", []byte(value), endianness)
+}
+
+// NormalizeProgramID validates a program ID such as "token.aleo" against snarkVM's rules and
+// returns its canonical form. The name must start with a letter and contain only letters, digits
+// and underscores, and the network suffix must be ".aleo".
+func (s *aleoWrapperSession) NormalizeProgramID(programID string) (normalized string, err error) {
+	out, err := s.callOutput("normalize_program_id", []byte(programID))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// HKDFDerive derives outLen bytes of key material from ikm with HKDF (RFC 5869), using
+// HMAC-SHA3-256 as the PRF. The salt may be empty, and distinct info values derive independent keys
+// from the same secret, e.g. an encryption key for off-chain payloads from an Aleo-derived
+// secret. outLen must be between 1 and 8160 bytes. The output is wiped from WASM memory after
+// being copied.
+func (s *aleoWrapperSession) HKDFDerive(ikm, salt, info []byte, outLen uint32) (key []byte, err error) {
+	return s.callSecretOutput("hkdf_derive", ikm, salt, info, outLen)
+}
+
+// SignatureInspect parses a signature and returns what it commits to without needing the signed
+// message: the signer's address, derived from the compute key embedded in the signature, and the
+// challenge scalar. Useful to audit who produced a signature.
+func (s *aleoWrapperSession) SignatureInspect(signature string) (address string, challenge string, err error) {
+	out, err := s.callOutput("signature_inspect", []byte(signature))
+	if err != nil {
+		return "", "", err
+	}
+
+	items, err := decodeStringFrames(out)
+	if err != nil {
+		return "", "", err
+	}
+	if len(items) != 2 {
+		return "", "", fmt.Errorf("signature_inspect: expected 2 items, got %d", len(items))
+	}
+
+	return items[0], items[1], nil
+}
+
+// SetMaxAlloc limits the size of a single buffer the WASM module allocates for an argument to
+// maxBytes, so services passing untrusted input can't be made to exhaust the module's memory.
+// Calls with a larger argument fail instead. 0, the default, removes the limit.
+func (s *aleoWrapperSession) SetMaxAlloc(maxBytes uint64) error {
+	_, err := s.call("set_max_alloc", maxBytes)
+	return err
+}
+
+// VerifyAddressChecksum checks the bech32m encoding of an address, its checksum and the "aleo"
+// human-readable part, without decoding it into a curve point. Returns nil if both checks pass,
+// ErrAddressChecksum if the string isn't valid bech32m and ErrAddressHRP if it's valid bech32m
+// with a different human-readable part. All Aleo networks use the "aleo" prefix for addresses, so
+// the check can't tell which network an address belongs to.
+func (s *aleoWrapperSession) VerifyAddressChecksum(address string) error {
+	result, err := s.call("verify_address_checksum", []byte(address))
+	if err != nil {
+		return err
+	}
+	if result == 1 {
+		return nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return err
+	}
+	switch message {
+	case ErrAddressChecksum.Error():
+		return ErrAddressChecksum
+	case ErrAddressHRP.Error():
+		return ErrAddressHRP
+	default:
+		return fmt.Errorf("verify_address_checksum failed: %s", message)
+	}
+}
+
+// NewPrivateKeys generates count private keys in a single call, which is far cheaper than count
+// NewPrivateKey calls. With an empty seed the keys are sampled from entropy, with a SEED_SIZE seed
+// from a ChaCha20 RNG seeded with it, so the same seed always yields the same keys. Seeded keys are
+// only as secret as the seed, use them for load testing and reproducible provisioning. At most
+// MAX_NEW_KEYS keys can be generated in one call. The output is wiped from WASM memory after being
+// copied, the caller is responsible for zeroizing the returned keys when no longer needed.
+func (s *aleoWrapperSession) NewPrivateKeys(count uint32, seed []byte) (keys [][]byte, err error) {
+	if len(seed) != 0 && len(seed) != SEED_SIZE {
+		return nil, fmt.Errorf("seed must be empty or %d bytes long", SEED_SIZE)
+	}
+	if count < 1 || count > MAX_NEW_KEYS {
+		return nil, fmt.Errorf("number of private keys must be between 1 and %d", MAX_NEW_KEYS)
+	}
+
+	out, err := s.callSecretOutput("new_private_keys", count, seed)
+	if err != nil {
+		return nil, err
+	}
+
+	return decodeFrames(out)
+}
+
+// SumRecordBalances sums the microcredits entries of decrypted plaintext credits records, e.g. the
+// records a wallet owns, to display its balance. Records without a microcredits entry, like the
+// records of other programs, are skipped. Fails if a record is malformed, its microcredits entry
+// isn't a u64, or the total overflows a u64.
+func (s *aleoWrapperSession) SumRecordBalances(records []string) (total uint64, err error) {
+	out, err := s.callOutput("sum_record_balances", encodeStringFrames(records))
+	if err != nil {
+		return 0, err
+	}
+
+	return strconv.ParseUint(string(out), 10, 64)
+}
+
+// ComputeFeeCommitment computes the commitment of the credits.aleo credits record
+// "{ owner: <address>.private, microcredits: <amount>u64.private, _nonce: <nonce>.public }", e.g.
+// the record a private fee is paid from or a fee's change record, where address is the address of
+// the private key and nonce a group string. Returns the commitment as a field string, the same
+// ComputeRecordCommitment returns for that record.
+func (s *aleoWrapperSession) ComputeFeeCommitment(amount uint64, key []byte, nonce string) (commitment string, err error) {
+	out, err := s.callOutput("compute_fee_commitment", amount, key, []byte(nonce))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// ValidateValueType checks that a plaintext value conforms to expectedType, e.g. "5u64" to "u64"
+// or "[1u8, 2u8]" to "[u8; 2u32]", to enforce type contracts before submitting program inputs.
+// Struct values don't carry their struct's name, so a struct type such as "MyStruct" only checks
+// that the value is a struct. Returns nil if the value conforms, and an error describing the
+// mismatch or parse failure otherwise.
+func (s *aleoWrapperSession) ValidateValueType(value, expectedType string) error {
+	result, err := s.call("validate_value_type", []byte(value), []byte(expectedType))
+	if err != nil {
+		return err
+	}
+	if result == 1 {
+		return nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return err
+	}
+	return fmt.Errorf("validate_value_type failed: %s", message)
+}
+
+// DecryptRecord decrypts a record ciphertext, e.g. one observed in a transition output, with the
+// view key of its owner and returns the plaintext record. Fails if the record isn't owned by the
+// view key.
+func (s *aleoWrapperSession) DecryptRecord(ciphertext string, viewKey []byte) (record string, err error) {
+	out, err := s.callOutput("decrypt_record", []byte(ciphertext), viewKey)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// encodeIndices encodes leaf indices as a framed list of 4-byte little-endian integers.
+func encodeIndices(indices []uint32) []byte {
+	items := make([][]byte, len(indices))
+	for i, index := range indices {
+		items[i] = binary.LittleEndian.AppendUint32(nil, index)
+	}
+
+	return encodeFrames(items)
+}
+
+// MerkleRoot computes the root of a BHP Merkle tree over leaf field strings, e.g. record
+// commitments. Leaves are hashed with BHP1024 over a false domain bit followed by their bits and
+// inner nodes like in snarkVM's BHP Merkle trees, but the tree is only as deep as the number of
+// leaves requires, with the leaf level padded to a power of two, so roots aren't comparable to
+// those of snarkVM's fixed-depth trees.
+func (s *aleoWrapperSession) MerkleRoot(leaves []string) (root string, err error) {
+	out, err := s.callOutput("merkle_root", encodeStringFrames(leaves))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// MerkleMultiProof creates a single proof for the leaves at indices of the tree over leaves (see
+// MerkleRoot), sharing the sibling hashes the proven leaves have in common instead of proving
+// each of them separately. The proof is a framed list: the tree depth as a 4-byte little-endian
+// integer, then the field strings of the sibling hashes that can't be computed from the proven
+// leaves, level by level from the leaves up and left to right within a level.
+func (s *aleoWrapperSession) MerkleMultiProof(leaves []string, indices []uint32) (proof []byte, err error) {
+	return s.callOutput("merkle_multi_proof", encodeStringFrames(leaves), encodeIndices(indices))
+}
+
+// MerkleVerifyMulti verifies a proof created with MerkleMultiProof that leaves are at indices of
+// the tree with the given root. leaves and indices must be in the same order. A proof with unused
+// sibling hashes is invalid.
+func (s *aleoWrapperSession) MerkleVerifyMulti(root string, leaves []string, indices []uint32, proof []byte) (valid bool, err error) {
+	result, err := s.call("merkle_verify_multi", []byte(root), encodeStringFrames(leaves), encodeIndices(indices), proof)
+	if err != nil {
+		return false, err
+	}
+
+	return result == 1, nil
+}
+
+// MerkleFindLeaf returns the index of the first leaf equal to target, e.g. the index of a record
+// commitment to pass to MerkleMultiProof. Leaves are compared as fields rather than as strings.
+// found is false without an error if target isn't among the leaves, and a leaf or target that
+// fails to parse is reported as an error.
+func (s *aleoWrapperSession) MerkleFindLeaf(leaves []string, target string) (index uint64, found bool, err error) {
+	result, err := s.call("merkle_find_leaf", encodeStringFrames(leaves), []byte(target))
+	if err != nil {
+		return 0, false, err
+	}
+	if result != MERKLE_LEAF_NOT_FOUND {
+		return result, true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return 0, false, err
+	}
+	if message != "" {
+		return 0, false, fmt.Errorf("merkle_find_leaf: %s", message)
+	}
+
+	return 0, false, nil
+}
+
+// SignatureToJSON returns a signature as a canonical JSON object with the string keys "address"
+// (the signer), "challenge" and "response", in that order, for structured logs.
+func (s *aleoWrapperSession) SignatureToJSON(signature string) (json string, err error) {
+	out, err := s.callOutput("signature_to_json", []byte(signature))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// CiphertextsEqualPlaintext decrypts two record ciphertexts with viewKey and reports whether they
+// hold the same record, i.e. the same owner and entries with the same visibilities, whatever their
+// nonces. This detects a record presented twice under different nonces. Returns ErrRecordDecrypt
+// if a ciphertext isn't owned by viewKey, and a parse error for malformed inputs.
+func (s *aleoWrapperSession) CiphertextsEqualPlaintext(viewKey []byte, ciphertextA, ciphertextB string) (equal bool, err error) {
+	result, err := s.call("ciphertexts_equal_plaintext", viewKey, []byte(ciphertextA), []byte(ciphertextB))
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	switch message {
+	case "":
+		return false, nil
+	case ErrRecordDecrypt.Error():
+		return false, ErrRecordDecrypt
+	default:
+		return false, fmt.Errorf("ciphertexts_equal_plaintext failed: %s", message)
+	}
+}
+
+// PedersenOpen commits to an input of at most 16 bytes with a Pedersen commitment (commit.ped128
+// over the little-endian bits of the input) under a randomizer scalar, e.g. "123scalar", and
+// returns the commitment field with its opening: the committed bits as one byte per bit, each 0
+// or 1, least significant bit first, and the randomizer. The commitment only hides the input if
+// the randomizer is uniformly random and kept secret until the commitment is opened.
+func (s *aleoWrapperSession) PedersenOpen(input []byte, randomizer string) (commitment string, bits []byte, openingRandomizer string, err error) {
+	out, err := s.callOutput("pedersen_open", input, []byte(randomizer))
+	if err != nil {
+		return "", nil, "", err
+	}
+
+	items, err := decodeFrames(out)
+	if err != nil {
+		return "", nil, "", err
+	}
+	if len(items) != 3 {
+		return "", nil, "", fmt.Errorf("pedersen_open: expected 3 items, got %d", len(items))
+	}
+
+	return string(items[0]), items[1], string(items[2]), nil
+}
+
+// PedersenVerify checks a commitment opening returned by PedersenOpen by recommitting to bits
+// under randomizer and comparing the result to commitment. A malformed opening is reported as
+// invalid.
+func (s *aleoWrapperSession) PedersenVerify(commitment string, bits []byte, randomizer string) (valid bool, err error) {
+	result, err := s.call("pedersen_verify", []byte(commitment), bits, []byte(randomizer))
+	if err != nil {
+		return false, err
+	}
+
+	return result == 1, nil
+}
+
+// AddressFromLabel derives a deterministic address from a label, so services can reproduce the
+// same well-known account, e.g. a faucet, from a shared string. The private key is Poseidon2
+// derived from the label alone: anyone who knows the label controls the address, so these
+// addresses are public and must never hold private funds.
+func (s *aleoWrapperSession) AddressFromLabel(label string) (address string, err error) {
+	out, err := s.callOutput("address_from_label", []byte(label))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// VerifyBalanceInvariant pre-validates a credits transfer before proving it: the microcredits of
+// the plaintext input records must equal those of the output records plus fee. An imbalance is
+// reported as false without an error. Records that fail to parse or lack a u64 microcredits entry,
+// and totals that overflow a uint64, are reported as errors.
+func (s *aleoWrapperSession) VerifyBalanceInvariant(inputs, outputs []string, fee uint64) (balanced bool, err error) {
+	result, err := s.call("verify_balance_invariant", encodeStringFrames(inputs), encodeStringFrames(outputs), fee)
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	if message != "" {
+		return false, fmt.Errorf("verify_balance_invariant: %s", message)
+	}
+
+	return false, nil
+}
+
+// VerifyRequest validates a request, the signed part of an authorization, before a relayer
+// forwards it for proving. request is the JSON string snarkVM serializes requests to and
+// inputTypes are the input types of the called function in order, e.g. "address.public" or
+// "credits.record". The caller's signature, the transition view key commitment and every input
+// are checked as snarkVM's Request::verify does for the root request of an authorization, so
+// requests of nested calls are reported as invalid. An invalid request is reported as false
+// without an error, inputs that fail to parse as an error.
+func (s *aleoWrapperSession) VerifyRequest(request string, inputTypes []string) (valid bool, err error) {
+	result, err := s.call("verify_request", []byte(request), encodeStringFrames(inputTypes))
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	if message != "" {
+		return false, fmt.Errorf("verify_request: %s", message)
+	}
+
+	return false, nil
+}
+
+// ComputeTVK derives the transition view key (tvk) of a transition whose request was signed with
+// key from its transition public key (tpk, a group string), as snarkVM does when building the
+// transition. For tpk = G * r it computes (tpk * view key).x, which equals snarkVM's
+// tvk = (address * r).x. This is the inverse of ComputeTPK. Returns the tvk as a field string.
+func (s *aleoWrapperSession) ComputeTVK(key []byte, tpk string) (tvk string, err error) {
+	out, err := s.callOutput("compute_tvk", key, []byte(tpk))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// TransactionTransitionIDs deserializes a transaction from its canonical little-endian bytes, as
+// nodes store and serve it, and returns the IDs of its transitions (au1... strings) in order: the
+// transitions of an execution followed by the fee transition, if any.
+func (s *aleoWrapperSession) TransactionTransitionIDs(txBytes []byte) (transitionIDs []string, err error) {
+	out, err := s.callOutput("transaction_transition_ids", txBytes)
+	if err != nil {
+		return nil, err
+	}
+
+	return decodeStringFrames(out)
+}
+
+// AddressForNetwork returns the address of a private key on the given network, NETWORK_MAINNET or
+// NETWORK_TESTNET, independently of the network the module is built for, e.g. to show a key's
+// address on both networks.
+func (s *aleoWrapperSession) AddressForNetwork(key []byte, networkID uint32) (address string, err error) {
+	if networkID != NETWORK_MAINNET && networkID != NETWORK_TESTNET {
+		return "", fmt.Errorf("unknown network ID %d", networkID)
+	}
+
+	out, err := s.callOutput("address_for_network", key, networkID)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// DeriveOutputRandomizers derives the encryption randomizer and nonce of the output record at index
+// of a transition from its transition view key, as snarkVM does: the randomizer is
+// HashToScalarPSD2(tvk, index) and the nonce G * randomizer. index counts all inputs and outputs
+// of the transition, i.e. it is the number of inputs plus the output's index, and must fit a
+// uint16. key is unused, as both values depend on the tvk only, and is kept for symmetry with
+// ComputeTPK and ComputeTVK. Returns the randomizer as a scalar string and the nonce as a group
+// string.
+func (s *aleoWrapperSession) DeriveOutputRandomizers(key []byte, tvk string, index uint64) (randomizer, nonce string, err error) {
+	if index > math.MaxUint16 {
+		return "", "", fmt.Errorf("output index %d exceeds %d", index, math.MaxUint16)
+	}
+
+	out, err := s.callOutput("derive_output_randomizers", key, []byte(tvk), index)
+	if err != nil {
+		return "", "", err
+	}
+
+	items, err := decodeStringFrames(out)
+	if err != nil {
+		return "", "", err
+	}
+	if len(items) != 2 {
+		return "", "", fmt.Errorf("derive_output_randomizers: expected 2 items, got %d", len(items))
+	}
+
+	return items[0], items[1], nil
+}
+
+// ValidateRecord checks that a decrypted plaintext record conforms to a record type definition,
+// e.g. "record token: owner as address.private; amount as u64.private;", before it's used as a
+// transition input: the owner must have the declared visibility, and the record exactly the
+// declared entries with the declared visibilities and types. The name of the record type isn't
+// checked, records don't carry it. Returns nil if the record conforms, and an error describing the
+// mismatch or parse failure otherwise.
+func (s *aleoWrapperSession) ValidateRecord(record, recordType string) error {
+	result, err := s.call("validate_record", []byte(record), []byte(recordType))
+	if err != nil {
+		return err
+	}
+	if result == 1 {
+		return nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return err
+	}
+	return fmt.Errorf("validate_record failed: %s", message)
+}
+
+// ComputeStateRoot computes the state root of a chain as nodes do, the root of snarkVM's depth 32
+// block tree over the block hashes (ab1... strings) in height order, starting with the genesis
+// block. Leaves are hashed with BHP1024 over a false domain bit followed by their bits, and
+// missing leaves take snarkVM's empty hash. Returns the root as the sr1... string nodes report, so
+// a light client can compare it with the node's root for the same height (see StateRootsEqual).
+func (s *aleoWrapperSession) ComputeStateRoot(blockHashes []string) (root string, err error) {
+	out, err := s.callOutput("compute_state_root", encodeStringFrames(blockHashes))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// GroupIsInSubgroup checks that a group element received from an external party, e.g. a record
+// nonce or tpk, is a point of the prime-order subgroup other than the identity before it's used
+// in an ECDH step with a secret from ViewKeySecret. Multiplying a small-order point by the secret
+// leaks the secret modulo the point's order to whoever chose the point, and the identity gives a
+// shared secret that doesn't depend on the secret at all. Malformed strings are reported as
+// invalid rather than as an error.
+func (s *aleoWrapperSession) GroupIsInSubgroup(group string) (valid bool, err error) {
+	result, err := s.call("group_is_in_subgroup", []byte(group))
+	if err != nil {
+		return false, err
+	}
+
+	return result == 1, nil
+}
+
+// IncrementalMerkle is an append-only Merkle tree of a fixed depth kept inside the WASM module,
+// for services maintaining a growing set of commitments. Appending hashes only the path of the new
+// leaf instead of rebuilding the tree. Leaves and nodes are hashed like in MerkleRoot, with the
+// leaves not appended yet as padding, so once more than half of the tree's leaves are appended the
+// root equals MerkleRoot over the same leaves. It belongs to the session that created it and
+// shares its goroutine restrictions. Call Close to release it.
+type IncrementalMerkle struct {
+	s      *aleoWrapperSession
+	handle uint64
+}
+
+// NewIncrementalMerkle starts an empty tree of depth between 1 and 32, which holds up to 2^depth
+// leaves.
+func (s *aleoWrapperSession) NewIncrementalMerkle(depth uint32) (tree *IncrementalMerkle, err error) {
+	handle, err := s.call("incr_merkle_new", depth)
+	if err != nil {
+		return nil, err
+	}
+	if handle == 0 {
+		return nil, errors.New("incr_merkle_new failed")
+	}
+
+	return &IncrementalMerkle{s: s, handle: handle}, nil
+}
+
+// Append appends a leaf field string, e.g. a record commitment, and returns the new root. Fails if
+// the tree is full.
+func (tree *IncrementalMerkle) Append(leaf string) (root string, err error) {
+	if tree.handle == 0 {
+		return "", errors.New("incremental merkle tree is closed")
+	}
+
+	out, err := tree.s.callOutput("incr_merkle_append", tree.handle, []byte(leaf))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// Close releases the tree. It's safe to call Close more than once.
+func (tree *IncrementalMerkle) Close() error {
+	if tree.handle == 0 {
+		return nil
+	}
+
+	handle := tree.handle
+	tree.handle = 0
+	_, err := tree.s.call("incr_merkle_free", handle)
+
+	return err
+}
+
+// DecryptAndVerifyRecord decrypts a record ciphertext with the view key of its owner and returns
+// the plaintext record only if its commitment, computed for the record type recordName of
+// programID (e.g. "credits.aleo" and "credits"), matches commitment, the value the chain stores
+// for the record. Returns ErrRecordDecrypt if the view key doesn't decrypt the ciphertext,
+// ErrRecordCommitment if the record doesn't match the commitment, and a parse error for malformed
+// inputs.
+func (s *aleoWrapperSession) DecryptAndVerifyRecord(viewKey []byte, ciphertext, commitment, programID, recordName string) (record string, err error) {
+	result, err := s.call("decrypt_and_verify_record", viewKey, []byte(ciphertext), []byte(commitment), []byte(programID), []byte(recordName))
+	if err != nil {
+		return "", err
+	}
+	if result == 0 {
+		message, err := s.LastError()
+		if err != nil {
+			return "", err
+		}
+		switch message {
+		case ErrRecordDecrypt.Error():
+			return "", ErrRecordDecrypt
+		case ErrRecordCommitment.Error():
+			return "", ErrRecordCommitment
+		default:
+			return "", fmt.Errorf("decrypt_and_verify_record failed: %s", message)
+		}
+	}
+
+	out, err := s.readOutput(result, false)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// MessageKey derives a 32-byte symmetric key for off-chain messages between the account of key and
+// peerAddress, which the peer derives as well from its own private key and the account's address.
+// The ECDH shared secret is the x-coordinate of peerAddress * view key, G * viewKeyA * viewKeyB
+// for both sides, and the key is HKDF-SHA3-256 (see HKDFDerive) over its 32 little-endian bytes
+// with the salt "AleoUtilsMessageKey0" and context as info. The key only depends on the two
+// accounts and context, so one-time keys need a context that is never reused, e.g. one including a
+// message ID. Either view key derives the key. The caller is responsible for zeroizing the returned
+// key when it is no longer needed.
+func (s *aleoWrapperSession) MessageKey(key []byte, peerAddress string, context []byte) (messageKey []byte, err error) {
+	return s.callSecretOutput("message_key", key, []byte(peerAddress), context)
+}
+
+// ProgramFingerprint parses an Aleo program source and returns a local fingerprint of it, BHP1024
+// over the program's canonical byte encoding, as a field string. The fingerprint is this library's
+// own scheme and has no on-chain equivalent: it doesn't match any ID or hash a node reports, only
+// fingerprints computed by this method. Formatting and comments don't change it, the program's ID,
+// imports and definitions do. Returns the parse error for invalid programs.
+func (s *aleoWrapperSession) ProgramFingerprint(programSource string) (fingerprint string, err error) {
+	result, err := s.call("program_fingerprint", []byte(programSource))
+	if err != nil {
+		return "", err
+	}
+	if result == 0 {
+		message, err := s.LastError()
+		if err != nil {
+			return "", err
+		}
+		return "", fmt.Errorf("program_fingerprint failed: %s", message)
+	}
+
+	out, err := s.readOutput(result, false)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// ProgramFunctions parses an Aleo program source and returns the names of the functions it
+// exports, the ones a transition can call, in declaration order. Closures are internal to the
+// program and aren't listed. Returns the parse error for invalid programs.
+func (s *aleoWrapperSession) ProgramFunctions(programSource string) (functions []string, err error) {
+	result, err := s.call("program_functions", []byte(programSource))
+	if err != nil {
+		return nil, err
+	}
+	if result == 0 {
+		message, err := s.LastError()
+		if err != nil {
+			return nil, err
+		}
+		return nil, fmt.Errorf("program_functions failed: %s", message)
+	}
+
+	out, err := s.readOutput(result, false)
+	if err != nil {
+		return nil, err
+	}
+
+	return decodeStringFrames(out)
+}
+
+// ProgramFunctionSignature parses an Aleo program source and returns the input and output types of
+// its function functionName, in declaration order and with their visibility, e.g. "u64.public" or
+// "token.record". Returns ErrFunctionNotFound if the program has no such function and a parse
+// error for a malformed program or function name.
+func (s *aleoWrapperSession) ProgramFunctionSignature(programSource, functionName string) (inputs, outputs []string, err error) {
+	result, err := s.call("program_function_signature", []byte(programSource), []byte(functionName))
+	if err != nil {
+		return nil, nil, err
+	}
+	if result == 0 {
+		message, err := s.LastError()
+		if err != nil {
+			return nil, nil, err
+		}
+		if message == ErrFunctionNotFound.Error() {
+			return nil, nil, ErrFunctionNotFound
+		}
+		return nil, nil, fmt.Errorf("program_function_signature failed: %s", message)
+	}
+
+	out, err := s.readOutput(result, false)
+	if err != nil {
+		return nil, nil, err
+	}
+
+	items, err := decodeFrames(out)
+	if err != nil {
+		return nil, nil, err
+	}
+	if len(items) != 2 {
+		return nil, nil, fmt.Errorf("program_function_signature: expected 2 type lists, got %d", len(items))
+	}
+	if inputs, err = decodeStringFrames(items[0]); err != nil {
+		return nil, nil, err
+	}
+	if outputs, err = decodeStringFrames(items[1]); err != nil {
+		return nil, nil, err
+	}
+
+	return inputs, outputs, nil
+}
+
+// ComputeSerialNumber computes the serial number of a record commitment for the private key of the
+// record owner, the value a transition spending the record reveals. Returns the serial number as a
+// field string.
+func (s *aleoWrapperSession) ComputeSerialNumber(key []byte, commitment string) (serialNumber string, err error) {
+	out, err := s.callOutput("compute_serial_number", key, []byte(commitment))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// ComputeSerialAndTag computes both the serial number and the tag of a record commitment for the
+// private key of the record owner. The key is parsed and derived once, so this is cheaper than
+// calling ComputeSerialNumber and ComputeRecordTag. Returns both values as field strings.
+func (s *aleoWrapperSession) ComputeSerialAndTag(key []byte, commitment string) (serialNumber, tag string, err error) {
+	out, err := s.callOutput("compute_serial_and_tag", key, []byte(commitment))
+	if err != nil {
+		return "", "", err
+	}
+
+	items, err := decodeStringFrames(out)
+	if err != nil {
+		return "", "", err
+	}
+	if len(items) != 2 {
+		return "", "", fmt.Errorf("compute_serial_and_tag: expected 2 values, got %d", len(items))
+	}
+
+	return items[0], items[1], nil
+}
+
+// BytesToFieldArray splits data into chunks of chunkBytes bytes, between 1 and 31, and converts
+// each into a field literal, read as a little-endian integer, for program inputs of type
+// [field; N] chunked the way the program expects. No length field is prepended. A shorter last
+// chunk is zero-padded at the most significant end, so it gives the same field as the chunk with
+// trailing zero bytes and the length of data has to be known to reverse the conversion.
+func (s *aleoWrapperSession) BytesToFieldArray(data []byte, chunkBytes uint32) (fields []string, err error) {
+	out, err := s.callOutput("bytes_to_field_array", data, chunkBytes)
+	if err != nil {
+		return nil, err
+	}
+
+	return decodeStringFrames(out)
+}
+
+// LiteralToBytes parses a boolean or integer literal, e.g. "true" or "-5i16", and returns its
+// native little-endian form: a single 0 or 1 byte for booleans and the two's complement bytes of
+// the integer's width for integers. Other literal types are rejected.
+func (s *aleoWrapperSession) LiteralToBytes(literal string) (data []byte, err error) {
+	return s.callOutput("literal_to_bytes", []byte(literal))
+}
+
+// BytesToLiteral converts the native little-endian form of a boolean or integer into its literal
+// string, the reverse of LiteralToBytes. typeCode is one of the LITERAL_ constants and data has to
+// be exactly as wide as the type, a boolean byte has to be 0 or 1.
+func (s *aleoWrapperSession) BytesToLiteral(data []byte, typeCode uint32) (literal string, err error) {
+	out, err := s.callOutput("bytes_to_literal", data, typeCode)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// HashBenchmark runs the hash or commitment operation (one of the HASH_ constants, see HashBytes)
+// iterations times over inputSize zero bytes inside the WASM module and returns the elapsed
+// wall-clock time. It's meant for diagnostics only, e.g. profiling the module on the hardware a
+// deployment runs on. The time covers the hashing only, not the call into the module. Input sizes
+// above 1 MiB, MaxInputBytes of the operation or the limit set with SetMaxAlloc are rejected.
+func (s *aleoWrapperSession) HashBenchmark(operation uint32, inputSize uint32, iterations uint32) (elapsed time.Duration, err error) {
+	result, err := s.call("hash_benchmark", operation, inputSize, iterations)
+	if err != nil {
+		return 0, err
+	}
+	if result == 0 {
+		message, err := s.LastError()
+		if err != nil {
+			return 0, err
+		}
+		return 0, fmt.Errorf("hash_benchmark failed: %s", message)
+	}
+
+	return time.Duration(result), nil
+}
+
+// ValuesEqual checks whether two Aleo values, plaintext literals, structs, arrays or plaintext
+// records, are semantically equal, ignoring whitespace and the order struct members and record
+// entries are written in. Array elements are compared in order. Returns an error only if a value
+// fails to parse, so equal is false for values that parse but differ.
+func (s *aleoWrapperSession) ValuesEqual(a, b string) (equal bool, err error) {
+	result, err := s.call("values_equal", []byte(a), []byte(b))
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	if message != "" {
+		return false, fmt.Errorf("values_equal: %s", message)
+	}
+
+	return false, nil
+}
+
+// AddressShard deterministically assigns an address to one of numShards shards: the first 8 bytes
+// of the SHA3-256 digest of the canonical lowercase address string, read as a little-endian
+// uint64, modulo numShards. The mapping is fixed, so it's stable across versions and can be
+// recomputed without this library. Returns an error for invalid addresses and a zero shard count.
+func (s *aleoWrapperSession) AddressShard(address string, numShards uint32) (shard uint32, err error) {
+	result, err := s.call("address_shard", []byte(address), numShards)
+	if err != nil {
+		return 0, err
+	}
+	if result == 0 {
+		message, err := s.LastError()
+		if err != nil {
+			return 0, err
+		}
+		if message != "" {
+			return 0, fmt.Errorf("address_shard: %s", message)
+		}
+	}
+
+	return uint32(result), nil
+}
+
+// EncryptToAddress encrypts an arbitrary byte payload to an Aleo address with ECIES over the Aleo
+// curve: an ephemeral key R = G * r, the shared point address * r, HKDF-SHA3-256 over its
+// x-coordinate with the salt "AleoUtilsEncryptToAddress0" and R and the address as info, giving an
+// encryption key and a MAC key, an HMAC-SHA3-256 counter mode keystream and an HMAC-SHA3-256 tag
+// over R and the ciphertext. The result is R's 32-byte x-coordinate, the ciphertext, as long as
+// the plaintext, and the 32-byte tag. See encrypt_to_address in src/crypto.rs for the details.
+func (s *aleoWrapperSession) EncryptToAddress(address string, plaintext []byte) (ciphertext []byte, err error) {
+	return s.callOutput("encrypt_to_address", []byte(address), plaintext)
+}
+
+// DecryptFromAddress decrypts a payload EncryptToAddress created for the address of key. The tag is
+// checked first, so payloads for another address and modified payloads are rejected. The caller is
+// responsible for zeroizing the returned plaintext when it is no longer needed.
+func (s *aleoWrapperSession) DecryptFromAddress(key []byte, ciphertext []byte) (plaintext []byte, err error) {
+	return s.callSecretOutput("decrypt_from_address", key, ciphertext)
+}
+
+// StealthAddress derives a one-time stealth address for recipientAddress, which can't be linked to
+// it without the recipient's view key. With the recipient address A = G * viewKey and the
+// ephemeral scalar e, it returns the ephemeral public key R = G * e, to be published along with the
+// stealth address P = A + G * s, where s is Poseidon2 hashed to a scalar over the
+// "AleoUtilsStealth0" domain separator and the x-coordinate of A * e. ephemeralScalar has to be a
+// non-zero scalar literal sampled uniformly at random for every stealth address and kept secret.
+//
+// P belongs to the view key viewKey + s, which decrypts the records sent to P. Aleo derives view
+// keys from private keys by hashing, so no private key of P exists and P can't spend.
+func (s *aleoWrapperSession) StealthAddress(recipientAddress, ephemeralScalar string) (stealthAddress, ephemeralPubkey string, err error) {
+	out, err := s.callOutput("stealth_address", []byte(recipientAddress), []byte(ephemeralScalar))
+	if err != nil {
+		return "", "", err
+	}
+
+	items, err := decodeStringFrames(out)
+	if err != nil {
+		return "", "", err
+	}
+	if len(items) != 2 {
+		return "", "", fmt.Errorf("stealth_address: expected 2 values, got %d", len(items))
+	}
+
+	return items[0], items[1], nil
+}
+
+// StealthScan checks whether a stealth address created by StealthAddress for the ephemeral public
+// key ephemeralPubkey belongs to the account of viewKey, by recomputing the tweak from
+// ephemeralPubkey * viewKey. Returns an error only if an input fails to parse, so mine is false
+// for other accounts' stealth addresses.
+func (s *aleoWrapperSession) StealthScan(viewKey []byte, ephemeralPubkey, stealthAddress string) (mine bool, err error) {
+	result, err := s.call("stealth_scan", viewKey, []byte(ephemeralPubkey), []byte(stealthAddress))
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	if message != "" {
+		return false, fmt.Errorf("stealth_scan: %s", message)
+	}
+
+	return false, nil
+}
+
+// ComputeKeyIsValid checks the internal consistency of compute key components, e.g. ones returned
+// by GetComputeKey and stored or transmitted separately: pkSig and prSig have to be non-identity
+// points of the prime-order subgroup and skPrf has to be the scalar snarkVM derives from them, so
+// the components really belong to the account of the derived address. Returns an error only if a
+// component fails to parse, so valid is false for tampered or corrupted components that parse.
+func (s *aleoWrapperSession) ComputeKeyIsValid(pkSig, prSig, skPrf string) (valid bool, err error) {
+	result, err := s.call("compute_key_is_valid", encodeStringFrames([]string{pkSig, prSig, skPrf}))
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	if message != "" {
+		return false, fmt.Errorf("compute_key_is_valid: %s", message)
+	}
+
+	return false, nil
+}
+
+// FieldChain is a hash-linked log of field elements kept inside the WASM module, for services
+// maintaining a verifiable append-only log. Absorbing a field replaces the head with
+// Poseidon2(head, field), so the head commits to the initial value and every field absorbed since,
+// in order. It belongs to the session that created it and shares its goroutine restrictions. Call
+// Close to release it.
+type FieldChain struct {
+	s      *aleoWrapperSession
+	handle uint64
+}
+
+// NewFieldChain starts a field chain with the field string initial as its head.
+func (s *aleoWrapperSession) NewFieldChain(initial string) (chain *FieldChain, err error) {
+	handle, err := s.call("field_chain_new", []byte(initial))
+	if err != nil {
+		return nil, err
+	}
+	if handle == 0 {
+		message, err := s.LastError()
+		if err != nil {
+			return nil, err
+		}
+		return nil, fmt.Errorf("field_chain_new failed: %s", message)
+	}
+
+	return &FieldChain{s: s, handle: handle}, nil
+}
+
+// Absorb absorbs a field string and returns the new head, Poseidon2 over the previous head and the
+// field. The head is unchanged if the field fails to parse.
+func (chain *FieldChain) Absorb(field string) (head string, err error) {
+	if chain.handle == 0 {
+		return "", errors.New("field chain is closed")
+	}
+
+	out, err := chain.s.callOutput("field_chain_absorb", chain.handle, []byte(field))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// Close releases the chain. It's safe to call Close more than once.
+func (chain *FieldChain) Close() error {
+	if chain.handle == 0 {
+		return nil
+	}
+
+	handle := chain.handle
+	chain.handle = 0
+	_, err := chain.s.call("field_chain_free", handle)
+
+	return err
+}
+
+// ClassifyBech32 reports what kind of Aleo string input is, as one of the STRING_ constants: an
+// address (aleo1), private key (APrivateKey1), view key (AViewKey1), signature (sign1),
+// transaction ID (at1), transition ID (au1) or block hash (ab1). The kind is only reported if the
+// whole string parses as it, checksum included. Returns STRING_UNKNOWN for unrecognized prefixes,
+// and STRING_UNKNOWN with the parse error for malformed strings with a known prefix.
+func (s *aleoWrapperSession) ClassifyBech32(input string) (kind uint64, err error) {
+	result, err := s.call("classify_bech32", []byte(input))
+	if err != nil {
+		return STRING_UNKNOWN, err
+	}
+	if result != STRING_UNKNOWN {
+		return result, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return STRING_UNKNOWN, err
+	}
+	if message != "" {
+		return STRING_UNKNOWN, fmt.Errorf("classify_bech32: %s", message)
+	}
+
+	return STRING_UNKNOWN, nil
+}
+
+// SignHashedMessage hashes an arbitrary message with hashAlgorithm, one of the HASH_ constants,
+// reduces the digest to a field and signs it. Keccak and SHA-3 digests are truncated to 31 bytes,
+// the other algorithms already hash to a field. The nonce is derived from the private key, the
+// seed and the digest, so the same key, seed and message always give the same signature. The
+// seed may be empty.
+func (s *aleoWrapperSession) SignHashedMessage(key []byte, message []byte, hashAlgorithm uint32, seed []byte) (signature string, err error) {
+	out, err := s.callOutput("sign_hashed_message", key, message, hashAlgorithm, seed)
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
+
+// VerifyHashedMessage verifies a signature created with SignHashedMessage over message against an
+// address, hashing the message with the same algorithm. A signature over the same message hashed
+// with a different algorithm is reported as invalid.
+func (s *aleoWrapperSession) VerifyHashedMessage(address string, signature string, message []byte, hashAlgorithm uint32) (valid bool, err error) {
+	result, err := s.call("verify_hashed_message", []byte(address), []byte(signature), message, hashAlgorithm)
+	if err != nil {
+		return false, err
+	}
+
+	return result == 1, nil
+}
+
+// StateRootsEqual checks whether two state roots, given as field strings or as the sr1 strings
+// nodes report, encode the same field, so differently formatted roots aren't mistaken for a reorg.
+// Returns an error only if a root fails to parse, so equal is false for roots that parse but
+// differ.
+func (s *aleoWrapperSession) StateRootsEqual(a, b string) (equal bool, err error) {
+	result, err := s.call("state_roots_equal", []byte(a), []byte(b))
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	if message != "" {
+		return false, fmt.Errorf("state_roots_equal: %s", message)
+	}
+
+	return false, nil
+}
+
+// RecordIsOwner checks whether a record ciphertext is owned by a view key. Only the owner entry is
+// compared against the view key's address, the record's other entries aren't decrypted. Returns an
+// error only if an input fails to parse, so owned is false for records of other owners.
+func (s *aleoWrapperSession) RecordIsOwner(viewKey []byte, ciphertext string) (owned bool, err error) {
+	result, err := s.call("record_is_owner", viewKey, []byte(ciphertext))
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	if message != "" {
+		return false, fmt.Errorf("record_is_owner: %s", message)
+	}
+
+	return false, nil
+}
+
+// RecordsOwnershipBatch checks ownership of record ciphertexts like RecordIsOwner in a single call,
+// for wallet sync. The view key is parsed and its address derived once for the whole batch. Returns
+// one result per ciphertext in input order, or an error if the view key or any ciphertext fails to
+// parse.
+func (s *aleoWrapperSession) RecordsOwnershipBatch(viewKey []byte, ciphertexts []string) (owned []bool, err error) {
+	out, err := s.callOutput("records_ownership_batch", viewKey, encodeStringFrames(ciphertexts))
+	if err != nil {
+		return nil, err
+	}
+	if len(out) != len(ciphertexts) {
+		return nil, fmt.Errorf("records_ownership_batch returned %d results for %d ciphertexts", len(out), len(ciphertexts))
+	}
+
+	owned = make([]bool, len(out))
+	for i, result := range out {
+		owned[i] = result == 1
+	}
+
+	return owned, nil
+}
+
+// AccountToJSON derives the view key, compute key, graph key and address of a private key and
+// returns them, together with the private key, as one JSON object for wallet export:
+// {"private_key":...,"view_key":...,"compute_key":{"pk_sig":...,"pr_sig":...,"sk_prf":...},
+// "graph_key":{"sk_tag":...},"address":...}, all values as strings. The JSON holds the private
+// key, so it's wiped from WASM memory and returned as bytes the caller can wipe too.
+func (s *aleoWrapperSession) AccountToJSON(key []byte) (json []byte, err error) {
+	return s.callSecretOutput("account_to_json", key)
+}
+
+// VerifyAgainstAny verifies a signature created with Sign over message against candidate addresses,
+// for signers known to be one of a set, and returns the index of the first address it's valid for.
+// The signer address embedded in the signature is looked up among the candidates and verified
+// once, rather than verifying against every candidate. found is false without an error if the
+// signature is valid for none of them, and an input that fails to parse is reported as an error.
+func (s *aleoWrapperSession) VerifyAgainstAny(addresses []string, signature string, message []byte) (index uint64, found bool, err error) {
+	result, err := s.call("verify_against_any", encodeStringFrames(addresses), []byte(signature), message)
+	if err != nil {
+		return 0, false, err
+	}
+	if result != SIGNER_NOT_FOUND {
+		return result, true, nil
+	}
+
+	lastError, err := s.LastError()
+	if err != nil {
+		return 0, false, err
+	}
+	if lastError != "" {
+		return 0, false, fmt.Errorf("verify_against_any: %s", lastError)
+	}
+
+	return 0, false, nil
+}
+
+// CheckHashInput reports whether an input of inputLen bytes is within the capacity of a hash or
+// commitment operation (one of the HASH_* codes, see MaxInputBytes), so oversized inputs can be
+// rejected before they're copied into the module. Returns an error only for unknown operation
+// codes, so ok is false without an error for inputs that are too large.
+func (s *aleoWrapperSession) CheckHashInput(operation uint32, inputLen uint32) (ok bool, err error) {
+	result, err := s.call("check_hash_input", operation, inputLen)
+	if err != nil {
+		return false, err
+	}
+	if result == 1 {
+		return true, nil
+	}
+
+	message, err := s.LastError()
+	if err != nil {
+		return false, err
+	}
+	if message != "" {
+		return false, fmt.Errorf("check_hash_input: %s", message)
+	}
+
+	return false, nil
+}
+
+// RerandomizeCiphertext re-encrypts a record ciphertext the view key owns under a new nonce, so it
+// can't be linked to the original by its ciphertext or nonce. The nonce G*r becomes G*(r+delta)
+// for a non-zero scalar string delta, and the entries are encrypted again under the record view
+// key of the new nonce, so the owner's view key decrypts the result to the same record with only
+// the _nonce changed. The view key is needed to decrypt the entries before re-encrypting them.
+func (s *aleoWrapperSession) RerandomizeCiphertext(ciphertext string, viewKey []byte, delta string) (rerandomized string, err error) {
+	out, err := s.callOutput("rerandomize_ciphertext", []byte(ciphertext), viewKey, []byte(delta))
+	if err != nil {
+		return "", err
+	}
+
+	return string(out), nil
+}
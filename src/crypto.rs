@@ -0,0 +1,298 @@
+use alloc::{string::ToString, vec::Vec};
+use core::slice;
+
+use rand::{rngs::StdRng, SeedableRng};
+use snarkvm_console::{
+    account::{Address, PrivateKey, ViewKey},
+    prelude::{ensure, FromBytes, Network, Result, ToBytes, Uniform, Zero},
+    types::{Field, Group, Scalar},
+};
+
+use crate::{
+    kdf::{hkdf_sha3_256, hmac_sha3_256, DIGEST_SIZE},
+    log::{clear_last_error, ok_or_log},
+    memory::{encode_frames, forget_buf_ptr_len, parse_from_raw},
+    network::CurrentNetwork,
+};
+
+// HKDF salt of the payload keys, separates them from other uses of the shared secret
+const PAYLOAD_KEY_SALT: &[u8] = b"AleoUtilsEncryptToAddress0";
+// Size of the ephemeral public key encoding, the little-endian bytes of its x-coordinate
+const EPHEMERAL_KEY_SIZE: usize = 32;
+// Size of the authentication tag, an HMAC-SHA3-256 digest
+const TAG_SIZE: usize = DIGEST_SIZE;
+// Domain separator of the stealth address tweak
+const STEALTH_DOMAIN: &str = "AleoUtilsStealth0";
+
+// Derives the encryption and MAC keys of a payload from the ECDH shared point: HKDF-SHA3-256 over
+// the little-endian bytes of the point's x-coordinate, with the PAYLOAD_KEY_SALT salt and the
+// ephemeral public key encoding followed by the recipient address bytes as info, expanded to 64
+// bytes, the first 32 being the encryption key and the last 32 the MAC key
+fn payload_keys(
+    shared_point: Group<CurrentNetwork>,
+    ephemeral_key: &[u8],
+    recipient: &Address<CurrentNetwork>,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    ensure!(!shared_point.is_zero(), "the shared secret is the identity");
+    let shared_secret = shared_point.to_x_coordinate().to_bytes_le()?;
+    let info = [ephemeral_key, &recipient.to_bytes_le()?].concat();
+
+    let mut encryption_key =
+        hkdf_sha3_256(&shared_secret, PAYLOAD_KEY_SALT, &info, 2 * DIGEST_SIZE);
+    let mac_key = encryption_key.split_off(DIGEST_SIZE);
+
+    Ok((encryption_key, mac_key))
+}
+
+// XORs data with the keystream whose 32-byte blocks are HMAC-SHA3-256(key, counter), the counter
+// being the block index as an 8-byte little-endian integer starting at 0
+fn apply_keystream(key: &[u8], data: &mut [u8]) {
+    for (counter, chunk) in data.chunks_mut(DIGEST_SIZE).enumerate() {
+        let block = hmac_sha3_256(key, &[&(counter as u64).to_le_bytes()]);
+        for (byte, key_byte) in chunk.iter_mut().zip(block) {
+            *byte ^= key_byte;
+        }
+    }
+}
+
+// Compares two byte strings in time independent of where they differ
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn encrypt_payload(recipient: Address<CurrentNetwork>, plaintext: &[u8]) -> Result<Vec<u8>> {
+    let randomizer = Scalar::<CurrentNetwork>::rand(&mut StdRng::from_entropy());
+    let ephemeral_key = CurrentNetwork::g_scalar_multiply(&randomizer).to_bytes_le()?;
+    let (encryption_key, mac_key) =
+        payload_keys(*recipient * randomizer, &ephemeral_key, &recipient)?;
+
+    let mut blob = Vec::with_capacity(EPHEMERAL_KEY_SIZE + plaintext.len() + TAG_SIZE);
+    blob.extend_from_slice(&ephemeral_key);
+    blob.extend_from_slice(plaintext);
+    apply_keystream(&encryption_key, &mut blob[EPHEMERAL_KEY_SIZE..]);
+    let tag = hmac_sha3_256(&mac_key, &[&blob]);
+    blob.extend_from_slice(&tag);
+
+    Ok(blob)
+}
+
+fn decrypt_payload(private_key: PrivateKey<CurrentNetwork>, blob: &[u8]) -> Result<Vec<u8>> {
+    ensure!(
+        blob.len() >= EPHEMERAL_KEY_SIZE + TAG_SIZE,
+        "ciphertext is too short"
+    );
+    let (body, tag) = blob.split_at(blob.len() - TAG_SIZE);
+    let (ephemeral_key, ciphertext) = body.split_at(EPHEMERAL_KEY_SIZE);
+
+    // Parsing recovers one of the two points with this x-coordinate, both give the same shared
+    // secret since only the x-coordinate of the shared point is used
+    let ephemeral_point = Group::<CurrentNetwork>::from_bytes_le(ephemeral_key)?;
+    let view_key = ViewKey::try_from(private_key)?;
+    let recipient = Address::try_from(private_key)?;
+    let (encryption_key, mac_key) =
+        payload_keys(ephemeral_point * *view_key, ephemeral_key, &recipient)?;
+
+    ensure!(
+        constant_time_eq(&hmac_sha3_256(&mac_key, &[body]), tag),
+        "ciphertext authentication failed"
+    );
+
+    let mut plaintext = ciphertext.to_vec();
+    apply_keystream(&encryption_key, &mut plaintext);
+
+    Ok(plaintext)
+}
+
+/// Encrypts an arbitrary byte payload to an Aleo address, so only the holder of the address's view
+/// key can read it. The scheme is ECIES over the Aleo curve, built from the primitives of the `kdf`
+/// module:
+///
+/// 1. A random scalar r is sampled and the ephemeral public key is R = G * r.
+/// 2. The ECDH shared point is address * r, the recipient computes the same point as R * view_key.
+/// 3. HKDF-SHA3-256 (see `hkdf_derive`) over the 32 little-endian bytes of the shared point's
+///    x-coordinate, with the salt `AleoUtilsEncryptToAddress0` and as info the encoding of R
+///    followed by the 32-byte little-endian x-coordinate of the address, expands into a 32-byte
+///    encryption key followed by a 32-byte MAC key.
+/// 4. The plaintext is XORed with a keystream of HMAC-SHA3-256(encryption key, counter) blocks,
+///    the counter being the 8-byte little-endian block index starting at 0.
+/// 5. The tag is HMAC-SHA3-256(MAC key, R encoding || ciphertext).
+///
+/// The result is the 32-byte little-endian x-coordinate of R, followed by the ciphertext, as long
+/// as the plaintext, and the 32-byte tag. Returns 0 if the address fails to parse.
+#[no_mangle]
+pub extern "C" fn encrypt_to_address(
+    address: *const u8,
+    address_len: usize,
+    plaintext: *const u8,
+    plaintext_len: usize,
+) -> u64 {
+    let Some(address) = parse_from_raw::<Address<CurrentNetwork>>(address, address_len, "address")
+    else {
+        return 0;
+    };
+    let plaintext = unsafe { slice::from_raw_parts(plaintext, plaintext_len) };
+
+    let Some(blob) = ok_or_log(
+        encrypt_payload(address, plaintext),
+        "failed to encrypt payload",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(blob)
+}
+
+/// Decrypts a payload created by `encrypt_to_address` for the address of `private_key`. The tag is
+/// checked before anything is decrypted, so a payload encrypted to another address, or modified in
+/// any way, is rejected. The returned buffer should be released with `free_secret`. Returns 0 if an
+/// input fails to parse or the tag doesn't match.
+#[no_mangle]
+pub extern "C" fn decrypt_from_address(
+    private_key: *const u8,
+    private_key_len: usize,
+    ciphertext: *const u8,
+    ciphertext_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let ciphertext = unsafe { slice::from_raw_parts(ciphertext, ciphertext_len) };
+
+    let Some(plaintext) = ok_or_log(
+        decrypt_payload(private_key, ciphertext),
+        "failed to decrypt payload",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(plaintext)
+}
+
+// Derives the tweak of a stealth address from the ECDH shared point, Poseidon2 hashed to a scalar
+// over the STEALTH_DOMAIN separator and the point's x-coordinate
+fn stealth_tweak(shared_point: Group<CurrentNetwork>) -> Result<Scalar<CurrentNetwork>> {
+    ensure!(!shared_point.is_zero(), "the shared secret is the identity");
+
+    CurrentNetwork::hash_to_scalar_psd2(&[
+        Field::new_domain_separator(STEALTH_DOMAIN),
+        shared_point.to_x_coordinate(),
+    ])
+}
+
+// Computes the stealth address of a recipient for an ephemeral scalar and the ephemeral public key
+// the recipient scans with
+fn stealth_address_for(
+    recipient: Address<CurrentNetwork>,
+    ephemeral_scalar: Scalar<CurrentNetwork>,
+) -> Result<(Address<CurrentNetwork>, Group<CurrentNetwork>)> {
+    ensure!(!ephemeral_scalar.is_zero(), "the ephemeral scalar is zero");
+    let ephemeral_key = CurrentNetwork::g_scalar_multiply(&ephemeral_scalar);
+    let tweak = stealth_tweak(*recipient * ephemeral_scalar)?;
+
+    let stealth_point = *recipient + CurrentNetwork::g_scalar_multiply(&tweak);
+    ensure!(
+        !stealth_point.is_zero(),
+        "the stealth address is the identity"
+    );
+
+    Ok((Address::new(stealth_point), ephemeral_key))
+}
+
+/// Derives a one-time stealth address for a recipient, so payments to the recipient can't be
+/// linked to its public address. With the recipient address A = G * view_key and the ephemeral
+/// scalar e, the ephemeral public key is R = G * e and the stealth address is P = A + G * s, where
+/// the tweak s is Poseidon2 hashed to a scalar over the `AleoUtilsStealth0` domain separator and
+/// the x-coordinate of the ECDH shared point A * e. The recipient recomputes the shared point as
+/// R * view_key, so publishing R next to P lets it detect P with `stealth_scan`.
+///
+/// P is the address of the view key view_key + s, which can decrypt the records sent to P. Aleo
+/// derives view keys from private keys by hashing, so no private key of P exists: P can receive
+/// and be viewed, not spend. `ephemeral_scalar` has to be a non-zero scalar string sampled
+/// uniformly at random for every stealth address and kept secret, anyone knowing it can link P to
+/// A. Returns a framed list of the stealth address and R as a group string, or 0 on failure.
+#[no_mangle]
+pub extern "C" fn stealth_address(
+    recipient_address: *const u8,
+    recipient_address_len: usize,
+    ephemeral_scalar: *const u8,
+    ephemeral_scalar_len: usize,
+) -> u64 {
+    let Some(recipient) = parse_from_raw::<Address<CurrentNetwork>>(
+        recipient_address,
+        recipient_address_len,
+        "recipient address",
+    ) else {
+        return 0;
+    };
+    let Some(ephemeral_scalar) = parse_from_raw::<Scalar<CurrentNetwork>>(
+        ephemeral_scalar,
+        ephemeral_scalar_len,
+        "ephemeral scalar",
+    ) else {
+        return 0;
+    };
+
+    let Some((stealth_address, ephemeral_key)) = ok_or_log(
+        stealth_address_for(recipient, ephemeral_scalar),
+        "failed to derive stealth address",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(encode_frames(&[
+        stealth_address.to_string(),
+        ephemeral_key.to_string(),
+    ]))
+}
+
+/// Checks whether a stealth address derived with `stealth_address` belongs to the account of a
+/// view key: recomputes the tweak s from the shared point R * view_key, where R is the ephemeral
+/// public key published with the stealth address, and compares G * view_key + G * s with it.
+///
+/// Returns 1 if the stealth address belongs to the view key and 0 otherwise. The last error is
+/// cleared on entry and is only set when an input fails to parse, so a 0 with an empty last error
+/// means the stealth address is someone else's.
+#[no_mangle]
+pub extern "C" fn stealth_scan(
+    view_key: *const u8,
+    view_key_len: usize,
+    ephemeral_pubkey: *const u8,
+    ephemeral_pubkey_len: usize,
+    stealth_address: *const u8,
+    stealth_address_len: usize,
+) -> u64 {
+    clear_last_error();
+
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+    let Some(ephemeral_key) = parse_from_raw::<Group<CurrentNetwork>>(
+        ephemeral_pubkey,
+        ephemeral_pubkey_len,
+        "ephemeral public key",
+    ) else {
+        return 0;
+    };
+    let Some(stealth_address) = parse_from_raw::<Address<CurrentNetwork>>(
+        stealth_address,
+        stealth_address_len,
+        "stealth address",
+    ) else {
+        return 0;
+    };
+
+    let Some(tweak) = ok_or_log(
+        stealth_tweak(ephemeral_key * *view_key),
+        "failed to derive stealth tweak",
+    ) else {
+        return 0;
+    };
+    let expected =
+        CurrentNetwork::g_scalar_multiply(&*view_key) + CurrentNetwork::g_scalar_multiply(&tweak);
+
+    (expected == *stealth_address) as u64
+}
@@ -0,0 +1,80 @@
+use alloc::string::ToString;
+
+use indexmap::IndexMap;
+use snarkvm_console::{
+    account::{Address, PrivateKey},
+    prelude::{FromStr, Result},
+    program::{Entry, Identifier, Literal, Owner, Plaintext, ProgramID, U64},
+    types::{Field, Group},
+};
+
+use crate::{
+    log::ok_or_log,
+    memory::{forget_buf_ptr_len, parse_from_raw},
+    network::CurrentNetwork,
+    record::PlaintextRecord,
+};
+
+// Builds the credits record `{ owner: address.private, microcredits: amount.private, _nonce }`
+// and computes its commitment as a `credits.aleo/credits` record
+fn fee_record_commitment(
+    address: Address<CurrentNetwork>,
+    amount: u64,
+    nonce: Group<CurrentNetwork>,
+) -> Result<Field<CurrentNetwork>> {
+    let owner = Owner::Private(Plaintext::from(Literal::Address(address)));
+    let data = IndexMap::from([(
+        Identifier::from_str("microcredits")?,
+        Entry::Private(Plaintext::from(Literal::U64(U64::new(amount)))),
+    )]);
+    let record = PlaintextRecord::from_plaintext(owner, data, nonce)?;
+
+    record.to_commitment(
+        &ProgramID::from_str("credits.aleo")?,
+        &Identifier::from_str("credits")?,
+    )
+}
+
+/// Computes the commitment of a `credits.aleo` credits record holding `amount` microcredits, such
+/// as the record a private fee is paid from or the change record of a fee transition, so the host
+/// can pre-construct and look up fee records off-chain.
+///
+/// - `amount` - the microcredits the record holds
+/// - `private_key` - the private key of the record owner, the record is owned privately by its
+///   address
+/// - `nonce` - the record nonce as a group string, G * r for the randomizer r of the transition
+///   output
+///
+/// Returns the commitment as a field string, or 0 if an input fails to parse.
+#[no_mangle]
+pub extern "C" fn compute_fee_commitment(
+    amount: u64,
+    private_key: *const u8,
+    private_key_len: usize,
+    nonce: *const u8,
+    nonce_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(address) = ok_or_log(
+        Address::try_from(&private_key),
+        "failed to convert a private key to address",
+    ) else {
+        return 0;
+    };
+    let Some(nonce) = parse_from_raw::<Group<CurrentNetwork>>(nonce, nonce_len, "nonce") else {
+        return 0;
+    };
+
+    let Some(commitment) = ok_or_log(
+        fee_record_commitment(address, amount, nonce),
+        "failed to compute fee record commitment",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(commitment.to_string().into_bytes())
+}
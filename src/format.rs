@@ -6,15 +6,181 @@ use core::{iter, slice, str};
 
 use indexmap::IndexMap;
 use snarkvm_console::{
-    prelude::{FromBytes, FromStr, Result, ToBytes},
-    program::{Identifier, Literal, Plaintext, Value, U128},
+    account::{Address, PrivateKey, Signature, ViewKey},
+    prelude::{
+        bail, ensure, AffineCurve, FromBits, FromBytes, FromStr, Network, One, Result, ToBits,
+        ToBytes, Zero,
+    },
+    program::{Entry, Identifier, Literal, Plaintext, PlaintextType, ProgramID, Value, U128},
+    types::{Boolean, Field, Group, Scalar, I128, I16, I32, I64, I8, U16, U32, U64, U8},
 };
 
-use crate::{log::log, memory::forget_buf_ptr_len, network::CurrentNetwork};
+use crate::{
+    log::{clear_last_error, log, ok_or_log},
+    memory::{encode_frames, forget_buf_ptr_len, parse_frames, parse_from_raw, str_from_raw},
+    network::CurrentNetwork,
+    record::PlaintextRecord,
+};
 
 const CHUNK_SIZE: usize = 16 * 32;
 const MAX_CHUNKS: usize = 32;
 
+// Number of whole bytes that always fit into a field element (252 data bits)
+pub(crate) const FIELD_DATA_BYTES: usize = 31;
+
+// Type codes of the literals literal_to_bytes and bytes_to_literal convert, the variant indices of
+// snarkVM's LiteralType
+pub const LITERAL_BOOLEAN: u32 = 1;
+pub const LITERAL_I8: u32 = 4;
+pub const LITERAL_I16: u32 = 5;
+pub const LITERAL_I32: u32 = 6;
+pub const LITERAL_I64: u32 = 7;
+pub const LITERAL_I128: u32 = 8;
+pub const LITERAL_U8: u32 = 9;
+pub const LITERAL_U16: u32 = 10;
+pub const LITERAL_U32: u32 = 11;
+pub const LITERAL_U64: u32 = 12;
+pub const LITERAL_U128: u32 = 13;
+
+// Kinds of strings classify_bech32 reports, STRING_UNKNOWN for anything it doesn't recognize
+pub const STRING_UNKNOWN: u64 = 0;
+pub const STRING_ADDRESS: u64 = 1;
+pub const STRING_PRIVATE_KEY: u64 = 2;
+pub const STRING_VIEW_KEY: u64 = 3;
+pub const STRING_SIGNATURE: u64 = 4;
+pub const STRING_TRANSACTION_ID: u64 = 5;
+pub const STRING_TRANSITION_ID: u64 = 6;
+pub const STRING_BLOCK_HASH: u64 = 7;
+
+pub(crate) fn bytes_to_bits_le(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+        .collect()
+}
+
+// Packs up to FIELD_DATA_BYTES little-endian bytes into a field element. Shorter inputs behave as
+// if they were padded with zero bytes at the most significant end.
+pub(crate) fn field_from_le_bytes(bytes: &[u8]) -> Result<Field<CurrentNetwork>> {
+    ensure!(
+        bytes.len() <= FIELD_DATA_BYTES,
+        "at most {FIELD_DATA_BYTES} bytes fit into a field element"
+    );
+    Field::from_bits_le(&bytes_to_bits_le(bytes))
+}
+
+// Reverses field_from_le_bytes, fails if the field doesn't fit into FIELD_DATA_BYTES bytes.
+pub(crate) fn field_to_le_bytes(field: &Field<CurrentNetwork>) -> Result<Vec<u8>> {
+    let mut bytes = field.to_bytes_le()?;
+    ensure!(
+        bytes[FIELD_DATA_BYTES..].iter().all(|byte| *byte == 0),
+        "field element doesn't fit into {FIELD_DATA_BYTES} bytes"
+    );
+    bytes.truncate(FIELD_DATA_BYTES);
+
+    Ok(bytes)
+}
+
+// Number of fields pack_bytes produces for `byte_len` bytes: the length field followed by one
+// field per started FIELD_DATA_BYTES chunk
+fn packed_field_count(byte_len: usize) -> usize {
+    1 + byte_len.div_ceil(FIELD_DATA_BYTES)
+}
+
+// Checks that a plaintext conforms to a plaintext type. Struct values don't carry the name of their
+// struct, so a struct type only checks that the value is a struct.
+pub(crate) fn check_plaintext_type(
+    plaintext: &Plaintext<CurrentNetwork>,
+    expected: &PlaintextType<CurrentNetwork>,
+) -> Result<()> {
+    match (plaintext, expected) {
+        (Plaintext::Literal(literal, _), PlaintextType::Literal(literal_type)) => {
+            ensure!(
+                literal.to_type() == *literal_type,
+                "expected a {} literal, found a {} literal",
+                literal_type,
+                literal.to_type()
+            );
+        }
+        (Plaintext::Struct(..), PlaintextType::Struct(_)) => {}
+        (Plaintext::Array(elements, _), PlaintextType::Array(array_type)) => {
+            let length = **array_type.length() as usize;
+            ensure!(
+                elements.len() == length,
+                "expected an array of {} elements, found {} elements",
+                length,
+                elements.len()
+            );
+            for element in elements {
+                check_plaintext_type(element, array_type.next_element_type())?;
+            }
+        }
+        (Plaintext::Literal(..), _) => {
+            bail!("expected a value of type {}, found a literal", expected)
+        }
+        (Plaintext::Struct(..), _) => {
+            bail!("expected a value of type {}, found a struct", expected)
+        }
+        (Plaintext::Array(..), _) => bail!("expected a value of type {}, found an array", expected),
+    }
+
+    Ok(())
+}
+
+// Checks that a group element is a non-identity point of the prime-order subgroup. Parsing
+// recovers the point from its x-coordinate and should already reject points outside the subgroup,
+// the check is repeated so the guarantee doesn't rest on the parser.
+pub(crate) fn check_in_subgroup(group: &Group<CurrentNetwork>) -> Result<()> {
+    let point = group.to_affine();
+    ensure!(
+        point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve(),
+        "group element isn't in the prime-order subgroup"
+    );
+    ensure!(!group.is_zero(), "group element is the identity");
+
+    Ok(())
+}
+
+// Packs bytes into fields: the first field holds the number of bytes, followed by the bytes
+// split into FIELD_DATA_BYTES chunks, each read as a little-endian integer.
+pub(crate) fn pack_bytes(bytes: &[u8]) -> Result<Vec<Field<CurrentNetwork>>> {
+    let mut fields = Vec::with_capacity(packed_field_count(bytes.len()));
+    fields.push(field_from_le_bytes(&(bytes.len() as u64).to_le_bytes())?);
+    for chunk in bytes.chunks(FIELD_DATA_BYTES) {
+        fields.push(field_from_le_bytes(chunk)?);
+    }
+
+    Ok(fields)
+}
+
+// Reverses pack_bytes.
+pub(crate) fn unpack_bytes(fields: &[Field<CurrentNetwork>]) -> Result<Vec<u8>> {
+    ensure!(!fields.is_empty(), "missing length field");
+
+    let len_bytes = field_to_le_bytes(&fields[0])?;
+    ensure!(
+        len_bytes[8..].iter().all(|byte| *byte == 0),
+        "length field is too large"
+    );
+    let len = u64::from_le_bytes(len_bytes[..8].try_into()?) as usize;
+    ensure!(
+        fields.len() == packed_field_count(len),
+        "length field doesn't match the number of fields"
+    );
+
+    let mut bytes = Vec::with_capacity(len + FIELD_DATA_BYTES);
+    for field in &fields[1..] {
+        bytes.extend_from_slice(&field_to_le_bytes(field)?);
+    }
+    ensure!(
+        bytes[len..].iter().all(|byte| *byte == 0),
+        "last field has data past the length"
+    );
+    bytes.truncate(len);
+
+    Ok(bytes)
+}
+
 fn create_struct_key(prefix: &str, idx: usize) -> String {
     let mut key = String::from(prefix);
     key.push_str(idx.to_string().as_str());
@@ -226,3 +392,569 @@ pub extern "C" fn formatted_message_to_bytes(
     buf.shrink_to_fit();
     forget_buf_ptr_len(buf)
 }
+
+/// Encodes a UTF-8 string as field elements, for program inputs embedding short strings. The first
+/// field holds the length of the string in bytes, followed by the bytes split into 31-byte chunks,
+/// each read as a little-endian integer (the last chunk is zero-padded at the most significant
+/// end). Returns the fields as a framed list of field strings.
+#[no_mangle]
+pub extern "C" fn string_to_fields(string: *const u8, string_len: usize) -> u64 {
+    let Some(string) = str_from_raw(string, string_len, "string") else {
+        return 0;
+    };
+
+    let Some(fields) = ok_or_log(
+        pack_bytes(string.as_bytes()),
+        "failed to pack string into fields",
+    ) else {
+        return 0;
+    };
+
+    let field_strings = fields.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+    forget_buf_ptr_len(encode_frames(&field_strings))
+}
+
+/// Returns the number of field elements a payload of `byte_len` bytes occupies when packed the way
+/// `string_to_fields` packs strings: one length field plus one field per started 31-byte chunk.
+#[no_mangle]
+pub extern "C" fn field_count_for_bytes(byte_len: usize) -> u64 {
+    packed_field_count(byte_len) as u64
+}
+
+/// Decodes a framed list of field strings created by `string_to_fields` back into the string.
+#[no_mangle]
+pub extern "C" fn fields_to_string(fields: *const u8, fields_len: usize) -> u64 {
+    let fields_bytes = unsafe { slice::from_raw_parts(fields, fields_len) };
+    let Some(parsed) = parse_frames::<Field<CurrentNetwork>>(fields_bytes, "fields") else {
+        return 0;
+    };
+
+    let Some(bytes) = ok_or_log(unpack_bytes(&parsed), "failed to unpack fields") else {
+        return 0;
+    };
+    let Some(string) = ok_or_log(
+        String::from_utf8(bytes),
+        "fields don't encode a UTF-8 string",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(string.into_bytes())
+}
+
+/// Splits bytes into `chunk_bytes`-sized chunks and converts each into a field element, read as a
+/// little-endian integer, for program inputs of type `[field; N]` where the program expects a given
+/// chunking. Unlike `string_to_fields` no length field is prepended. The last chunk may be shorter,
+/// then it's zero-padded at the most significant end: the trailing bytes `01 02` become `513field`
+/// whatever the chunk size, the same as the full chunk `01 02 00 .. 00`, so the original length has
+/// to be known to tell trailing zero bytes from padding. `chunk_bytes` has to
+/// be between 1 and 31, the number of whole bytes always fitting into a field element.
+///
+/// Returns a framed list of field strings, or 0 on an invalid chunk size.
+#[no_mangle]
+pub extern "C" fn bytes_to_field_array(
+    bytes: *const u8,
+    bytes_len: usize,
+    chunk_bytes: u32,
+) -> u64 {
+    let chunk_bytes = chunk_bytes as usize;
+    if chunk_bytes == 0 || chunk_bytes > FIELD_DATA_BYTES {
+        log("chunk size has to be between 1 and 31 bytes");
+        return 0;
+    }
+
+    let bytes = unsafe { slice::from_raw_parts(bytes, bytes_len) };
+    let Some(fields) = ok_or_log(
+        bytes
+            .chunks(chunk_bytes)
+            .map(field_from_le_bytes)
+            .collect::<Result<Vec<_>>>(),
+        "failed to convert bytes to fields",
+    ) else {
+        return 0;
+    };
+
+    let field_strings = fields.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+    forget_buf_ptr_len(encode_frames(&field_strings))
+}
+
+// Adds one to a non-negative decimal integer string
+fn decimal_increment(decimal: &str) -> String {
+    let mut digits = decimal.as_bytes().to_vec();
+    for digit in digits.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            return String::from_utf8(digits).unwrap();
+        }
+    }
+    digits.insert(0, b'1');
+
+    String::from_utf8(digits).unwrap()
+}
+
+// Number of decimal digits that always fit into a u64
+const U64_DECIMAL_DIGITS: usize = 19;
+
+// Parses a non-negative decimal integer string into a field element, reducing it modulo the base
+// field. Digits are accumulated in u64 chunks, so no big integer type is needed.
+fn field_from_decimal(decimal: &str) -> Result<Field<CurrentNetwork>> {
+    ensure!(
+        !decimal.is_empty() && decimal.bytes().all(|b| b.is_ascii_digit()),
+        "expected a non-negative decimal integer"
+    );
+
+    let mut field = Field::<CurrentNetwork>::zero();
+    for chunk in decimal.as_bytes().chunks(U64_DECIMAL_DIGITS) {
+        let value = chunk
+            .iter()
+            .fold(0u64, |acc, digit| acc * 10 + (digit - b'0') as u64);
+        let scale = 10u64.pow(chunk.len() as u32);
+        field = field * Field::from_u64(scale) + Field::from_u64(value);
+    }
+
+    Ok(field)
+}
+
+// Computes a modulus as a decimal string from the string representation of the largest element of
+// its field, which is modulus - 1 followed by the type suffix.
+fn modulus_from_max_element(max_element: String, suffix: &str) -> u64 {
+    let Some(decimal) = max_element.strip_suffix(suffix) else {
+        log("unexpected field element representation");
+        return 0;
+    };
+
+    forget_buf_ptr_len(decimal_increment(decimal).into_bytes())
+}
+
+/// Returns the modulus of the base field (the field of `field` values) as a decimal string. It's
+/// computed from snarkVM field arithmetic (-1 + 1 as an integer), so it can't drift from the
+/// field the crate operates on.
+#[no_mangle]
+pub extern "C" fn base_field_modulus() -> u64 {
+    modulus_from_max_element((-Field::<CurrentNetwork>::one()).to_string(), "field")
+}
+
+/// Returns the modulus of the scalar field (the field of `scalar` values) as a decimal string,
+/// computed the same way as `base_field_modulus`.
+#[no_mangle]
+pub extern "C" fn scalar_field_modulus() -> u64 {
+    modulus_from_max_element((-Scalar::<CurrentNetwork>::one()).to_string(), "scalar")
+}
+
+/// Parses a base-10 integer string of any size, reduces it modulo the base field and returns the
+/// resulting field string, e.g. `42` becomes `42field` and the modulus becomes `0field`. Only
+/// ASCII digits are accepted, no sign or separators.
+#[no_mangle]
+pub extern "C" fn decimal_to_field(decimal: *const u8, decimal_len: usize) -> u64 {
+    let Some(decimal) = str_from_raw(decimal, decimal_len, "decimal") else {
+        return 0;
+    };
+
+    let Some(field) = ok_or_log(
+        field_from_decimal(decimal),
+        "failed to parse decimal integer",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(field.to_string().into_bytes())
+}
+
+/// Returns the value of a field string as a base-10 integer string, the reverse of
+/// `decimal_to_field` for values below the modulus.
+#[no_mangle]
+pub extern "C" fn field_to_decimal(field: *const u8, field_len: usize) -> u64 {
+    let Some(field) = parse_from_raw::<Field<CurrentNetwork>>(field, field_len, "field") else {
+        return 0;
+    };
+
+    let field_string = field.to_string();
+    let Some(decimal) = field_string.strip_suffix("field") else {
+        log("unexpected field element representation");
+        return 0;
+    };
+
+    forget_buf_ptr_len(decimal.as_bytes().to_vec())
+}
+
+/// Returns the bit decomposition snarkVM computes for an Aleo value (a plaintext literal, struct
+/// or array, or a record) as one byte per bit, each 0 or 1. `little_endian` selects `to_bits_le`
+/// when non-zero and `to_bits_be` otherwise. Plaintext bits include snarkVM's type header (variant
+/// and size) ahead of the value's own bits, exactly as they're fed into on-chain hashes.
+#[no_mangle]
+pub extern "C" fn value_to_bits(value: *const u8, value_len: usize, little_endian: u32) -> u64 {
+    let Some(value) = parse_from_raw::<Value<CurrentNetwork>>(value, value_len, "value") else {
+        return 0;
+    };
+
+    let bits = if little_endian != 0 {
+        value.to_bits_le()
+    } else {
+        value.to_bits_be()
+    };
+
+    forget_buf_ptr_len(bits.into_iter().map(u8::from).collect())
+}
+
+/// Validates a program ID such as `token.aleo` and returns its canonical string form. The name must
+/// be a valid snarkVM identifier (starting with a letter, then letters, digits and underscores) and
+/// the network suffix must be `aleo`. Returns 0 for invalid IDs.
+#[no_mangle]
+pub extern "C" fn normalize_program_id(program_id: *const u8, program_id_len: usize) -> u64 {
+    let Some(program_id) =
+        parse_from_raw::<ProgramID<CurrentNetwork>>(program_id, program_id_len, "program ID")
+    else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(program_id.to_string().into_bytes())
+}
+
+/// Checks that a plaintext value conforms to a type, e.g. `5u64` to `u64` or `[1u8, 2u8]` to
+/// `[u8; 2u32]`, before it's submitted as a program input. Literal types must match exactly and
+/// arrays are checked element by element. Struct values don't carry the name of their struct, so a
+/// struct type such as `MyStruct` only checks that the value is a struct. Returns 1 if the value
+/// conforms, and 0 with a last error describing the mismatch otherwise.
+#[no_mangle]
+pub extern "C" fn validate_value_type(
+    value: *const u8,
+    value_len: usize,
+    expected_type: *const u8,
+    expected_type_len: usize,
+) -> u64 {
+    let Some(value) = parse_from_raw::<Plaintext<CurrentNetwork>>(value, value_len, "value") else {
+        return 0;
+    };
+    let Some(expected_type) = parse_from_raw::<PlaintextType<CurrentNetwork>>(
+        expected_type,
+        expected_type_len,
+        "expected type",
+    ) else {
+        return 0;
+    };
+
+    ok_or_log(
+        check_plaintext_type(&value, &expected_type),
+        "value doesn't match the expected type",
+    )
+    .is_some() as u64
+}
+
+/// Checks that a group string received from an external party is a point of the prime-order
+/// subgroup other than the identity, before the host uses it in an ECDH step such as computing a
+/// record view key (point * view_key_secret).x. A point of small order, or one outside the
+/// subgroup, multiplied by a secret scalar only takes a few values, so a peer supplying it learns
+/// the secret modulo that order from the result, and the identity yields a shared secret that
+/// doesn't depend on the secret at all. Returns 1 if the point is safe to use and 0 otherwise,
+/// including for strings that aren't the x-coordinate of a curve point.
+#[no_mangle]
+pub extern "C" fn group_is_in_subgroup(group: *const u8, group_len: usize) -> u64 {
+    let Some(group) = parse_from_raw::<Group<CurrentNetwork>>(group, group_len, "group") else {
+        return 0;
+    };
+
+    if let Err(e) = check_in_subgroup(&group) {
+        log(e.to_string());
+        return 0;
+    }
+
+    1
+}
+
+// Quotes a string as a JSON string. It doesn't escape anything: it's only used for keys, addresses
+// and literals, which are alphanumeric.
+pub(crate) fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    json.push_str(value);
+    json.push('"');
+    json
+}
+
+// Builds a JSON object from members in the given order, the values have to be JSON already (see
+// json_string)
+pub(crate) fn json_object(members: &[(&str, String)]) -> String {
+    let mut json = String::from("{");
+    for (i, (key, value)) in members.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        json.push_str(&json_string(key));
+        json.push(':');
+        json.push_str(value);
+    }
+    json.push('}');
+    json
+}
+
+/// Returns a signature as a canonical JSON object for structured logs, with the keys in this order:
+/// `address`, the signer address derived from the signature's compute key, and the `challenge` and
+/// `response` scalars, all as strings, e.g.
+/// `{"address":"aleo1...","challenge":"1scalar","response":"2scalar"}`. Returns 0 if the signature
+/// doesn't parse.
+#[no_mangle]
+pub extern "C" fn signature_to_json(signature: *const u8, signature_len: usize) -> u64 {
+    let Some(signature) =
+        parse_from_raw::<Signature<CurrentNetwork>>(signature, signature_len, "signature")
+    else {
+        return 0;
+    };
+
+    let json = json_object(&[
+        ("address", json_string(&signature.to_address().to_string())),
+        ("challenge", json_string(&signature.challenge().to_string())),
+        ("response", json_string(&signature.response().to_string())),
+    ]);
+
+    forget_buf_ptr_len(json.into_bytes())
+}
+
+/// Parses a list of Aleo literal program inputs separated by whitespace and/or commas, e.g.
+/// `5u64 aleo1... true` or `1field, 2field`. Every input is validated against the type its suffix
+/// or form implies and canonicalized (e.g. `1_000u64` becomes `1000u64`). String literals
+/// containing whitespace or commas aren't supported.
+///
+/// Returns the canonical inputs as a framed list of strings. On a malformed input returns 0 and
+/// the last error names the offending token.
+#[no_mangle]
+pub extern "C" fn parse_program_inputs(inputs: *const u8, inputs_len: usize) -> u64 {
+    let Some(inputs) = str_from_raw(inputs, inputs_len, "program inputs") else {
+        return 0;
+    };
+
+    let mut values = Vec::new();
+    for token in inputs
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+    {
+        let mut context = String::from("invalid program input `");
+        context.push_str(token);
+        context.push('`');
+
+        let Some(literal) = ok_or_log(Literal::<CurrentNetwork>::from_str(token), &context) else {
+            return 0;
+        };
+        values.push(literal.to_string());
+    }
+
+    if values.is_empty() {
+        log("no program inputs");
+        return 0;
+    }
+
+    forget_buf_ptr_len(encode_frames(&values))
+}
+
+// Returns the little-endian byte form of a boolean or integer literal, a single 0 or 1 byte for
+// booleans
+fn literal_le_bytes(literal: &Literal<CurrentNetwork>) -> Result<Vec<u8>> {
+    Ok(match literal {
+        Literal::Boolean(value) => vec![**value as u8],
+        Literal::I8(value) => value.to_le_bytes().to_vec(),
+        Literal::I16(value) => value.to_le_bytes().to_vec(),
+        Literal::I32(value) => value.to_le_bytes().to_vec(),
+        Literal::I64(value) => value.to_le_bytes().to_vec(),
+        Literal::I128(value) => value.to_le_bytes().to_vec(),
+        Literal::U8(value) => value.to_le_bytes().to_vec(),
+        Literal::U16(value) => value.to_le_bytes().to_vec(),
+        Literal::U32(value) => value.to_le_bytes().to_vec(),
+        Literal::U64(value) => value.to_le_bytes().to_vec(),
+        Literal::U128(value) => value.to_le_bytes().to_vec(),
+        _ => bail!("a {} literal isn't a boolean or integer", literal.to_type()),
+    })
+}
+
+// Reverses literal_le_bytes for a literal type code, the input has to be exactly as wide as the
+// type
+fn literal_from_le_bytes(bytes: &[u8], type_code: u32) -> Result<Literal<CurrentNetwork>> {
+    let width = match type_code {
+        LITERAL_BOOLEAN | LITERAL_I8 | LITERAL_U8 => 1,
+        LITERAL_I16 | LITERAL_U16 => 2,
+        LITERAL_I32 | LITERAL_U32 => 4,
+        LITERAL_I64 | LITERAL_U64 => 8,
+        LITERAL_I128 | LITERAL_U128 => 16,
+        _ => bail!("unknown literal type code {type_code}"),
+    };
+    ensure!(
+        bytes.len() == width,
+        "expected {width} bytes for the literal type, got {}",
+        bytes.len()
+    );
+
+    Ok(match type_code {
+        LITERAL_BOOLEAN => match bytes[0] {
+            0 => Literal::Boolean(Boolean::new(false)),
+            1 => Literal::Boolean(Boolean::new(true)),
+            byte => bail!("boolean byte has to be 0 or 1, got {byte}"),
+        },
+        LITERAL_I8 => Literal::I8(I8::new(i8::from_le_bytes(bytes.try_into()?))),
+        LITERAL_I16 => Literal::I16(I16::new(i16::from_le_bytes(bytes.try_into()?))),
+        LITERAL_I32 => Literal::I32(I32::new(i32::from_le_bytes(bytes.try_into()?))),
+        LITERAL_I64 => Literal::I64(I64::new(i64::from_le_bytes(bytes.try_into()?))),
+        LITERAL_I128 => Literal::I128(I128::new(i128::from_le_bytes(bytes.try_into()?))),
+        LITERAL_U8 => Literal::U8(U8::new(u8::from_le_bytes(bytes.try_into()?))),
+        LITERAL_U16 => Literal::U16(U16::new(u16::from_le_bytes(bytes.try_into()?))),
+        LITERAL_U32 => Literal::U32(U32::new(u32::from_le_bytes(bytes.try_into()?))),
+        LITERAL_U64 => Literal::U64(U64::new(u64::from_le_bytes(bytes.try_into()?))),
+        _ => Literal::U128(U128::new(u128::from_le_bytes(bytes.try_into()?))),
+    })
+}
+
+/// Parses a boolean or integer literal, e.g. `true` or `-5i16`, and returns its native
+/// little-endian byte form: one byte, 0 or 1, for booleans and the two's complement bytes of the
+/// integer's width for integers. Returns 0 if the literal fails to parse or has another type.
+#[no_mangle]
+pub extern "C" fn literal_to_bytes(literal: *const u8, literal_len: usize) -> u64 {
+    let Some(literal) = parse_from_raw::<Literal<CurrentNetwork>>(literal, literal_len, "literal")
+    else {
+        return 0;
+    };
+
+    let Some(bytes) = ok_or_log(
+        literal_le_bytes(&literal),
+        "failed to convert literal to bytes",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(bytes)
+}
+
+/// Converts the little-endian byte form of a boolean or integer back into its literal string, the
+/// reverse of `literal_to_bytes`. `type_code` is one of the `LITERAL_` codes, the input has to be
+/// exactly as wide as the type and a boolean byte has to be 0 or 1. Returns 0 on an unknown type
+/// code or a malformed input.
+#[no_mangle]
+pub extern "C" fn bytes_to_literal(bytes: *const u8, bytes_len: usize, type_code: u32) -> u64 {
+    let bytes = unsafe { slice::from_raw_parts(bytes, bytes_len) };
+
+    let Some(literal) = ok_or_log(
+        literal_from_le_bytes(bytes, type_code),
+        "failed to convert bytes to literal",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(literal.to_string().into_bytes())
+}
+
+// Compares plaintexts structurally: literals by value, arrays element by element and structs
+// member by member regardless of the order their members are listed in
+fn plaintexts_equal(a: &Plaintext<CurrentNetwork>, b: &Plaintext<CurrentNetwork>) -> bool {
+    match (a, b) {
+        (Plaintext::Literal(a, _), Plaintext::Literal(b, _)) => a == b,
+        (Plaintext::Struct(a, _), Plaintext::Struct(b, _)) => {
+            a.len() == b.len()
+                && a.iter().all(|(name, member)| {
+                    b.get(name)
+                        .is_some_and(|other| plaintexts_equal(member, other))
+                })
+        }
+        (Plaintext::Array(a, _), Plaintext::Array(b, _)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| plaintexts_equal(a, b))
+        }
+        _ => false,
+    }
+}
+
+// Compares plaintext records: the owner, the nonce and the entries by name, each with the same
+// visibility and a structurally equal value
+fn records_equal(a: &PlaintextRecord, b: &PlaintextRecord) -> bool {
+    a.owner() == b.owner()
+        && a.nonce() == b.nonce()
+        && a.data().len() == b.data().len()
+        && a.data()
+            .iter()
+            .all(|(name, entry)| match (entry, b.data().get(name)) {
+                (Entry::Constant(a), Some(Entry::Constant(b)))
+                | (Entry::Public(a), Some(Entry::Public(b)))
+                | (Entry::Private(a), Some(Entry::Private(b))) => plaintexts_equal(a, b),
+                _ => false,
+            })
+}
+
+/// Checks whether two Aleo values (plaintext literals, structs or arrays, or plaintext records) are
+/// semantically equal, whatever their formatting: whitespace and the order struct members or
+/// record entries are written in don't matter, e.g. `{ a: 1u8, b: 2u8 }` equals `{b:2u8,a:1u8}`.
+/// Array elements are compared in order, and records also compare their owner and nonce. Other
+/// values, i.e. futures, are compared by their canonical string.
+///
+/// Returns 1 if the values are equal and 0 otherwise. The last error is cleared on entry and is
+/// only set when a value fails to parse, so a 0 with an empty last error means the values differ.
+#[no_mangle]
+pub extern "C" fn values_equal(a: *const u8, a_len: usize, b: *const u8, b_len: usize) -> u64 {
+    clear_last_error();
+
+    let Some(a) = parse_from_raw::<Value<CurrentNetwork>>(a, a_len, "first value") else {
+        return 0;
+    };
+    let Some(b) = parse_from_raw::<Value<CurrentNetwork>>(b, b_len, "second value") else {
+        return 0;
+    };
+
+    let equal = match (&a, &b) {
+        (Value::Plaintext(a), Value::Plaintext(b)) => plaintexts_equal(a, b),
+        (Value::Record(a), Value::Record(b)) => records_equal(a, b),
+        (Value::Plaintext(_) | Value::Record(_), _)
+        | (_, Value::Plaintext(_) | Value::Record(_)) => false,
+        _ => a.to_string() == b.to_string(),
+    };
+
+    equal as u64
+}
+
+// Parses a string as the type its kind implies, only to validate it
+fn check_string_kind(input: &str, kind: u64) -> Result<()> {
+    match kind {
+        STRING_ADDRESS => drop(Address::<CurrentNetwork>::from_str(input)?),
+        STRING_PRIVATE_KEY => drop(PrivateKey::<CurrentNetwork>::from_str(input)?),
+        STRING_VIEW_KEY => drop(ViewKey::<CurrentNetwork>::from_str(input)?),
+        STRING_SIGNATURE => drop(Signature::<CurrentNetwork>::from_str(input)?),
+        STRING_TRANSACTION_ID => drop(<CurrentNetwork as Network>::TransactionID::from_str(input)?),
+        STRING_TRANSITION_ID => drop(<CurrentNetwork as Network>::TransitionID::from_str(input)?),
+        STRING_BLOCK_HASH => drop(<CurrentNetwork as Network>::BlockHash::from_str(input)?),
+        _ => bail!("unknown string kind"),
+    }
+
+    Ok(())
+}
+
+/// Reports what kind of Aleo string the input is, so the host can route a pasted string to the
+/// right handler: one of the `STRING_` codes for an address (`aleo1`), private key
+/// (`APrivateKey1`), view key (`AViewKey1`), signature (`sign1`), transaction ID (`at1`),
+/// transition ID (`au1`) or block hash (`ab1`). Addresses, signatures and IDs are bech32m strings
+/// whose human-readable part names their kind, keys are base58 strings with a fixed prefix. The
+/// kind is only reported if the whole string parses as it, checksum included.
+///
+/// Returns `STRING_UNKNOWN` for unrecognized prefixes and for strings with a known prefix that
+/// fail to parse, with the last error set in the latter case. The last error is cleared on entry.
+#[no_mangle]
+pub extern "C" fn classify_bech32(input: *const u8, input_len: usize) -> u64 {
+    clear_last_error();
+
+    let Some(input) = str_from_raw(input, input_len, "input") else {
+        return STRING_UNKNOWN;
+    };
+
+    let kind = [
+        ("aleo1", STRING_ADDRESS),
+        ("APrivateKey1", STRING_PRIVATE_KEY),
+        ("AViewKey1", STRING_VIEW_KEY),
+        ("sign1", STRING_SIGNATURE),
+        ("at1", STRING_TRANSACTION_ID),
+        ("au1", STRING_TRANSITION_ID),
+        ("ab1", STRING_BLOCK_HASH),
+    ]
+    .into_iter()
+    .find_map(|(prefix, kind)| input.starts_with(prefix).then_some(kind));
+    let Some(kind) = kind else {
+        return STRING_UNKNOWN;
+    };
+
+    match ok_or_log(check_string_kind(input, kind), "malformed string") {
+        Some(()) => kind,
+        None => STRING_UNKNOWN,
+    }
+}
@@ -1,17 +1,304 @@
 use core::{str, slice};
-use alloc::string::ToString;
+use std::time::Instant;
+use alloc::{boxed::Box, string::ToString, vec::Vec};
 
 use snarkvm_console::{
+  account::Address,
   program::{Value, Network, CastLossy, U128},
   prelude::*,
+  types::{Field, Scalar},
 };
+use tiny_keccak::{Hasher, Keccak, Sha3};
 
 use crate::{
-  log::log,
-  memory::forget_buf_ptr_len,
+  format::{bytes_to_bits_le, pack_bytes},
+  log::{clear_last_error, log, ok_or_log},
+  memory::{decode_frames, encode_frames, forget_buf_ptr_len, max_alloc, parse_from_raw},
   network::CurrentNetwork,
 };
 
+// Operation codes of the hash and commitment functions, shared by hashing exports
+pub const BHP256: u32 = 0;
+pub const BHP512: u32 = 1;
+pub const BHP768: u32 = 2;
+pub const BHP1024: u32 = 3;
+pub const PED64: u32 = 4;
+pub const PED128: u32 = 5;
+pub const PSD2: u32 = 6;
+pub const PSD4: u32 = 7;
+pub const PSD8: u32 = 8;
+pub const KECCAK256: u32 = 9;
+pub const KECCAK384: u32 = 10;
+pub const KECCAK512: u32 = 11;
+pub const SHA3_256: u32 = 12;
+pub const SHA3_384: u32 = 13;
+pub const SHA3_512: u32 = 14;
+
+// Returned by `max_input_bytes` for operations that don't limit their input size
+pub const UNBOUNDED_INPUT: u64 = u64::MAX;
+
+/// Returns the maximum number of input bytes the operation accepts, so callers can validate
+/// inputs before crossing the FFI boundary. Pedersen hashes and commitments are limited to 64
+/// and 128 input bits. BHP, Poseidon, Keccak and SHA-3 absorb their input iteratively and accept
+/// any length, for those `UNBOUNDED_INPUT` is returned. Unknown operation codes return 0.
+#[no_mangle]
+pub extern "C" fn max_input_bytes(operation: u32) -> u64 {
+  match operation {
+    PED64 => 64 / 8,
+    PED128 => 128 / 8,
+    BHP256 | BHP512 | BHP768 | BHP1024 | PSD2 | PSD4 | PSD8 => UNBOUNDED_INPUT,
+    KECCAK256 | KECCAK384 | KECCAK512 | SHA3_256 | SHA3_384 | SHA3_512 => UNBOUNDED_INPUT,
+    _ => {
+      log("unknown operation code");
+      0
+    }
+  }
+}
+
+/// Pre-flight check of an input size against the operation's capacity (see `max_input_bytes`),
+/// so the host can reject oversized inputs before allocating and copying them into the module.
+/// Returns 1 if an input of `input_len` bytes is within the capacity and 0 otherwise. The last
+/// error is cleared on entry and is only set for unknown operation codes, so a 0 with an empty
+/// last error means the input is too large.
+#[no_mangle]
+pub extern "C" fn check_hash_input(operation: u32, input_len: usize) -> u64 {
+  clear_last_error();
+
+  let limit = max_input_bytes(operation);
+  if limit == 0 {
+    return 0;
+  }
+
+  (input_len as u64 <= limit) as u64
+}
+
+// Runs a Keccak or SHA-3 hasher over the input and returns the digest
+fn keccak_digest<H: Hasher>(mut hasher: H, input: &[u8], digest_size: usize) -> Vec<u8> {
+  let mut digest = vec![0u8; digest_size];
+  hasher.update(input);
+  hasher.finalize(&mut digest);
+
+  digest
+}
+
+// Hashes raw bytes with the operation and returns the digest bytes. Keccak and SHA-3 are the
+// standard byte-oriented functions. BHP and Pedersen hash the little-endian bits of the input,
+// Poseidon hashes the input packed into fields like `string_to_fields` does (the byte length
+// followed by 31-byte chunks). Field digests are returned as their 32 little-endian bytes.
+pub(crate) fn digest_bytes(operation: u32, input: &[u8]) -> Result<Vec<u8>> {
+  let field = match operation {
+    KECCAK256 => return Ok(keccak_digest(Keccak::v256(), input, 32)),
+    KECCAK384 => return Ok(keccak_digest(Keccak::v384(), input, 48)),
+    KECCAK512 => return Ok(keccak_digest(Keccak::v512(), input, 64)),
+    SHA3_256 => return Ok(keccak_digest(Sha3::v256(), input, 32)),
+    SHA3_384 => return Ok(keccak_digest(Sha3::v384(), input, 48)),
+    SHA3_512 => return Ok(keccak_digest(Sha3::v512(), input, 64)),
+    BHP256 => CurrentNetwork::hash_bhp256(&bytes_to_bits_le(input))?,
+    BHP512 => CurrentNetwork::hash_bhp512(&bytes_to_bits_le(input))?,
+    BHP768 => CurrentNetwork::hash_bhp768(&bytes_to_bits_le(input))?,
+    BHP1024 => CurrentNetwork::hash_bhp1024(&bytes_to_bits_le(input))?,
+    PED64 => CurrentNetwork::hash_ped64(&bytes_to_bits_le(input))?,
+    PED128 => CurrentNetwork::hash_ped128(&bytes_to_bits_le(input))?,
+    PSD2 => CurrentNetwork::hash_psd2(&pack_bytes(input)?)?,
+    PSD4 => CurrentNetwork::hash_psd4(&pack_bytes(input)?)?,
+    PSD8 => CurrentNetwork::hash_psd8(&pack_bytes(input)?)?,
+    _ => bail!("unknown operation code {operation}"),
+  };
+
+  field.to_bytes_le()
+}
+
+/// Hashes raw bytes with the operation and returns the digest bytes (see `digest_bytes` for how
+/// every operation treats the input), or 0 on an unknown operation or oversized input.
+#[no_mangle]
+pub extern "C" fn hash_bytes(operation: u32, input: *const u8, input_len: usize) -> u64 {
+  let input_bytes = unsafe { slice::from_raw_parts(input, input_len) };
+
+  let Some(digest) = ok_or_log(digest_bytes(operation, input_bytes), "failed to hash input") else {
+    return 0;
+  };
+
+  forget_buf_ptr_len(digest)
+}
+
+/// Hashes the same input with several operations in one call. `spec` is a framed list of
+/// operation codes, each a 4-byte little-endian integer, and the digests (see `hash_bytes`) are
+/// returned as a framed list in the same order. Returns 0 if the spec is malformed, empty or
+/// contains an unknown operation, or if any of the hashes fails.
+#[no_mangle]
+pub extern "C" fn hash_multi(
+  spec: *const u8,
+  spec_len: usize,
+  input: *const u8,
+  input_len: usize,
+) -> u64 {
+  let spec_bytes = unsafe { slice::from_raw_parts(spec, spec_len) };
+  let Some(codes) = decode_frames(spec_bytes) else {
+    log("malformed hash spec");
+    return 0;
+  };
+  if codes.is_empty() {
+    log("empty hash spec");
+    return 0;
+  }
+
+  let input_bytes = unsafe { slice::from_raw_parts(input, input_len) };
+
+  let mut digests = Vec::with_capacity(codes.len());
+  for code in codes {
+    let Ok(code) = <[u8; 4]>::try_from(code) else {
+      log("hash spec operation codes must be 4 bytes long");
+      return 0;
+    };
+    let digest = digest_bytes(u32::from_le_bytes(code), input_bytes);
+    let Some(digest) = ok_or_log(digest, "failed to hash input") else {
+      return 0;
+    };
+    digests.push(digest);
+  }
+
+  forget_buf_ptr_len(encode_frames(&digests))
+}
+
+/// Hashes the concatenation of a framed list of byte chunks with the operation and returns the
+/// digest (see `hash_bytes`), the same digest hashing the joined chunks gives. Lets the host hash
+/// structured data without joining it first. Returns 0 if the list is malformed or hashing fails.
+#[no_mangle]
+pub extern "C" fn hash_concat(operation: u32, parts: *const u8, parts_len: usize) -> u64 {
+  let parts_bytes = unsafe { slice::from_raw_parts(parts, parts_len) };
+  let Some(parts) = decode_frames(parts_bytes) else {
+    log("malformed list of parts to hash");
+    return 0;
+  };
+
+  let digest = digest_bytes(operation, &parts.concat());
+  let Some(digest) = ok_or_log(digest, "failed to hash input") else {
+    return 0;
+  };
+
+  forget_buf_ptr_len(digest)
+}
+
+/// Hashes an input under a domain tag with the operation and returns the digest, so digests
+/// computed for different purposes can't collide. The hashed bytes are the domain length as a
+/// 4-byte little-endian integer, followed by the domain and then the input, i.e. the domain framed
+/// like every item of a framed list and the input appended as is. The digest is the one
+/// `hash_bytes` returns for these bytes. Returns 0 on a domain longer than u32::MAX bytes or if
+/// hashing fails.
+#[no_mangle]
+pub extern "C" fn hash_with_domain(
+  operation: u32,
+  domain: *const u8,
+  domain_len: usize,
+  input: *const u8,
+  input_len: usize,
+) -> u64 {
+  let Ok(domain_len_prefix) = u32::try_from(domain_len) else {
+    log("hash domain is too long");
+    return 0;
+  };
+  let domain_bytes = unsafe { slice::from_raw_parts(domain, domain_len) };
+  let input_bytes = unsafe { slice::from_raw_parts(input, input_len) };
+
+  let mut tagged = Vec::with_capacity(4 + domain_len + input_len);
+  tagged.extend_from_slice(&domain_len_prefix.to_le_bytes());
+  tagged.extend_from_slice(domain_bytes);
+  tagged.extend_from_slice(input_bytes);
+
+  let Some(digest) = ok_or_log(digest_bytes(operation, &tagged), "failed to hash input") else {
+    return 0;
+  };
+
+  forget_buf_ptr_len(digest)
+}
+
+// Maximum number of bits a Pedersen opening commits to, the input size of PED128
+const PEDERSEN_MAX_BITS: usize = 128;
+
+/// Commits to an input of at most 16 bytes with a Pedersen commitment (snarkVM's `commit.ped128`
+/// over the little-endian bits of the input) under a randomizer scalar string, and returns the
+/// commitment together with its opening, so a range proof layer can later show what it commits to.
+///
+/// The result is a framed list of three items:
+/// - the commitment as a field string
+/// - the committed value bits as one byte per bit, each 0 or 1, least significant bit first
+/// - the randomizer as a scalar string
+///
+/// The commitment only hides the value if the randomizer is uniformly random and kept secret
+/// until the commitment is opened. Returns 0 on an oversized input or an invalid randomizer.
+#[no_mangle]
+pub extern "C" fn pedersen_open(
+  input: *const u8,
+  input_len: usize,
+  randomizer: *const u8,
+  randomizer_len: usize,
+) -> u64 {
+  let input_bytes = unsafe { slice::from_raw_parts(input, input_len) };
+  let Some(randomizer) =
+    parse_from_raw::<Scalar<CurrentNetwork>>(randomizer, randomizer_len, "randomizer") else {
+    return 0;
+  };
+
+  let bits = bytes_to_bits_le(input_bytes);
+  if bits.len() > PEDERSEN_MAX_BITS {
+    log("pedersen commitment input exceeds 16 bytes");
+    return 0;
+  }
+
+  let commitment = CurrentNetwork::commit_ped128(&bits, &randomizer);
+  let Some(commitment) = ok_or_log(commitment, "failed to compute pedersen commitment") else {
+    return 0;
+  };
+
+  let opening_bits = bits.into_iter().map(u8::from).collect::<Vec<_>>();
+  forget_buf_ptr_len(encode_frames(&[
+    commitment.to_string().into_bytes(),
+    opening_bits,
+    randomizer.to_string().into_bytes(),
+  ]))
+}
+
+/// Checks a Pedersen commitment opening returned by `pedersen_open`: recommits to the value bits,
+/// one byte per bit each 0 or 1, under the randomizer scalar string and compares the result to the
+/// commitment field string. Returns 1 if the opening is valid and 0 otherwise, including for
+/// malformed inputs.
+#[no_mangle]
+pub extern "C" fn pedersen_verify(
+  commitment: *const u8,
+  commitment_len: usize,
+  bits: *const u8,
+  bits_len: usize,
+  randomizer: *const u8,
+  randomizer_len: usize,
+) -> u64 {
+  let Some(commitment) =
+    parse_from_raw::<Field<CurrentNetwork>>(commitment, commitment_len, "commitment") else {
+    return 0;
+  };
+  let Some(randomizer) =
+    parse_from_raw::<Scalar<CurrentNetwork>>(randomizer, randomizer_len, "randomizer") else {
+    return 0;
+  };
+
+  let bit_bytes = unsafe { slice::from_raw_parts(bits, bits_len) };
+  if bit_bytes.len() > PEDERSEN_MAX_BITS {
+    log("pedersen opening has more than 128 bits");
+    return 0;
+  }
+  if bit_bytes.iter().any(|&bit| bit > 1) {
+    log("pedersen opening bits must be 0 or 1");
+    return 0;
+  }
+  let bits = bit_bytes.iter().map(|&bit| bit == 1).collect::<Vec<_>>();
+
+  let recomputed = CurrentNetwork::commit_ped128(&bits, &randomizer);
+  let Some(recomputed) = ok_or_log(recomputed, "failed to compute pedersen commitment") else {
+    return 0;
+  };
+
+  (recomputed == commitment) as u64
+}
+
 #[no_mangle]
 pub extern "C" fn hash_message(message: *const u8, message_len: usize) -> u64 {
   // Convert a pointer to a string into a string
@@ -122,3 +409,159 @@ pub extern "C" fn hash_message_bytes(message: *const u8, message_len: usize) ->
 
   forget_buf_ptr_len(hash_bytes)
 }
+
+/// Hashes a Leo value to an address the same way Leo's `Poseidon2::hash_to_address` does
+/// (`hash.psd2 ... into ... as address`): the value's fields are hashed to a group element with
+/// Poseidon2 and the element is used as the address. Returns the bech32 address string.
+#[no_mangle]
+pub extern "C" fn hash_to_address(input: *const u8, input_len: usize) -> u64 {
+  // Convert a pointer to a string into a string
+  let input_str = unsafe {
+    match str::from_utf8(slice::from_raw_parts(input, input_len)) {
+      Ok(val) => val,
+      Err(e) => {
+        let mut err_str = String::from("failed to rebuild input from pointer: ");
+        err_str.push_str(e.to_string().as_str());
+
+        log(err_str);
+
+        return 0;
+      },
+    }
+  };
+
+  // convert the string value into an array of fields
+  let fields = match Value::<CurrentNetwork>::from_str(input_str)
+    .and_then(|value| value.to_fields()) {
+      Ok(val) => val,
+      Err(e) => {
+        let mut err_str = String::from("failed to parse value from string: ");
+        err_str.push_str(e.to_string().as_str());
+
+        log(err_str);
+
+        return 0;
+      }
+  };
+
+  // hash the fields to a group element
+  let group = match CurrentNetwork::hash_to_group_psd2(fields.as_slice()) {
+    Ok(val) => val,
+    Err(e) => {
+      let mut err_str = String::from("failed to compute Poseidon2 hash to group: ");
+      err_str.push_str(e.to_string().as_str());
+
+      log(err_str);
+
+      return 0;
+    }
+  };
+
+  let address_bytes = Address::<CurrentNetwork>::new(group).to_string().into_bytes();
+
+  forget_buf_ptr_len(address_bytes)
+}
+
+// Largest input hash_benchmark allocates, whatever the limit set with set_max_alloc, 1 MiB
+const MAX_BENCHMARK_INPUT_BYTES: usize = 1 << 20;
+
+/// Diagnostics only: runs the operation `iterations` times over `input_size` zero bytes (see
+/// `hash_bytes`) and returns the elapsed wall-clock nanoseconds, at least 1, for sizing
+/// deployments on the target hardware. The time is read from the WASI clock, so it's only
+/// meaningful if the host provides a real one. Returns 0 on an unknown operation, zero iterations
+/// or an input size above `MAX_BENCHMARK_INPUT_BYTES`, `max_input_bytes` of the operation or the
+/// limit set with `set_max_alloc`, all checked before the input is allocated.
+#[no_mangle]
+pub extern "C" fn hash_benchmark(operation: u32, input_size: usize, iterations: u32) -> u64 {
+  if iterations == 0 {
+    log("hash benchmark needs at least one iteration");
+    return 0;
+  }
+
+  if input_size > MAX_BENCHMARK_INPUT_BYTES {
+    log("input size exceeds the maximum hash benchmark input size");
+    return 0;
+  }
+  let limit = max_input_bytes(operation);
+  if limit == 0 {
+    return 0;
+  }
+  if input_size as u64 > limit {
+    log("input size exceeds the maximum input size of the operation");
+    return 0;
+  }
+  let max_alloc = max_alloc();
+  if max_alloc != 0 && input_size as u64 > max_alloc {
+    log("input size exceeds the limit set with set_max_alloc");
+    return 0;
+  }
+
+  let input = vec![0u8; input_size];
+
+  let start = Instant::now();
+  for _ in 0..iterations {
+    if ok_or_log(digest_bytes(operation, &input), "failed to hash input").is_none() {
+      return 0;
+    }
+  }
+  let elapsed = start.elapsed().as_nanos();
+
+  u64::try_from(elapsed).unwrap_or(u64::MAX).max(1)
+}
+
+// State of a field chain, the handle returned to the host is a pointer to it
+struct FieldChain {
+  head: Field<CurrentNetwork>,
+}
+
+/// Starts a field chain, a hash-linked log of field elements, with the `initial` field string as
+/// its head. Every absorbed field replaces the head with Poseidon2(head, field), so the head
+/// commits to the initial value and every field absorbed since, in order.
+///
+/// Returns an opaque chain handle, or 0 if the initial value fails to parse. The handle must be
+/// released with field_chain_free exactly once and must not be used after that.
+#[no_mangle]
+pub extern "C" fn field_chain_new(initial: *const u8, initial_len: usize) -> u64 {
+  let Some(head) = parse_from_raw::<Field<CurrentNetwork>>(initial, initial_len, "initial field")
+  else {
+    return 0;
+  };
+
+  Box::into_raw(Box::new(FieldChain { head })) as usize as u64
+}
+
+/// Absorbs a field string into a field chain, the new head being Poseidon2 over the previous head
+/// followed by the field (snarkVM's `hash.psd2` of the two fields). Returns the new head as a field
+/// string, or 0 if the field fails to parse, in which case the head is unchanged.
+#[no_mangle]
+pub extern "C" fn field_chain_absorb(handle: u64, field: *const u8, field_len: usize) -> u64 {
+  if handle == 0 {
+    log("invalid field chain handle");
+    return 0;
+  }
+
+  let chain = unsafe { &mut *(handle as usize as *mut FieldChain) };
+
+  let Some(field) = parse_from_raw::<Field<CurrentNetwork>>(field, field_len, "field") else {
+    return 0;
+  };
+  let Some(head) = ok_or_log(
+    CurrentNetwork::hash_psd2(&[chain.head, field]),
+    "failed to absorb field",
+  ) else {
+    return 0;
+  };
+  chain.head = head;
+
+  forget_buf_ptr_len(head.to_string().into_bytes())
+}
+
+/// Releases a field chain. Freeing a 0 handle is a no-op.
+#[no_mangle]
+pub extern "C" fn field_chain_free(handle: u64) {
+  if handle == 0 {
+    return;
+  }
+
+  drop(unsafe { Box::from_raw(handle as usize as *mut FieldChain) });
+}
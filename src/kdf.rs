@@ -0,0 +1,167 @@
+use core::slice;
+
+use snarkvm_console::{
+    account::{Address, PrivateKey, ViewKey},
+    prelude::{ensure, Result, ToBytes, Zero},
+};
+use tiny_keccak::{Hasher, Sha3};
+
+use crate::{
+    log::{log, ok_or_log},
+    memory::{forget_buf_ptr_len, parse_from_raw},
+    network::CurrentNetwork,
+};
+
+// Size of a SHA3-256 digest, the HMAC output length
+pub(crate) const DIGEST_SIZE: usize = 32;
+// HMAC block size of SHA3-256, its sponge rate in bytes
+const HMAC_BLOCK_SIZE: usize = 136;
+// HKDF can expand into at most 255 blocks of the digest size
+const MAX_OUTPUT_LEN: usize = 255 * DIGEST_SIZE;
+// Size of the keys message_key derives
+const MESSAGE_KEY_SIZE: usize = 32;
+// HKDF salt of message_key, separates its keys from other uses of the shared secret
+const MESSAGE_KEY_SALT: &[u8] = b"AleoUtilsMessageKey0";
+
+fn sha3_256(parts: &[&[u8]]) -> [u8; DIGEST_SIZE] {
+    let mut hasher = Sha3::v256();
+    for part in parts {
+        hasher.update(part);
+    }
+
+    let mut digest = [0u8; DIGEST_SIZE];
+    hasher.finalize(&mut digest);
+    digest
+}
+
+// HMAC (RFC 2104) with SHA3-256 over the concatenation of `parts`
+pub(crate) fn hmac_sha3_256(key: &[u8], parts: &[&[u8]]) -> [u8; DIGEST_SIZE] {
+    let mut block = [0u8; HMAC_BLOCK_SIZE];
+    if key.len() > HMAC_BLOCK_SIZE {
+        block[..DIGEST_SIZE].copy_from_slice(&sha3_256(&[key]));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let inner_pad = block.map(|byte| byte ^ 0x36);
+    let mut inner = Vec::with_capacity(parts.len() + 1);
+    inner.push(&inner_pad[..]);
+    inner.extend_from_slice(parts);
+    let inner_digest = sha3_256(&inner);
+
+    let outer_pad = block.map(|byte| byte ^ 0x5c);
+    sha3_256(&[&outer_pad, &inner_digest])
+}
+
+// HKDF (RFC 5869) extract and expand steps with HMAC-SHA3-256. An empty salt is replaced by a
+// digest-sized block of zeros, as the RFC specifies.
+pub(crate) fn hkdf_sha3_256(ikm: &[u8], salt: &[u8], info: &[u8], out_len: usize) -> Vec<u8> {
+    let zero_salt = [0u8; DIGEST_SIZE];
+    let salt = if salt.is_empty() {
+        &zero_salt[..]
+    } else {
+        salt
+    };
+    let prk = hmac_sha3_256(salt, &[ikm]);
+
+    let mut okm = Vec::with_capacity(out_len + DIGEST_SIZE);
+    let mut block = [0u8; DIGEST_SIZE];
+    for counter in 1..=out_len.div_ceil(DIGEST_SIZE) as u8 {
+        let previous = if counter == 1 { &[][..] } else { &block[..] };
+        block = hmac_sha3_256(&prk, &[previous, info, &[counter]]);
+        okm.extend_from_slice(&block);
+    }
+    okm.truncate(out_len);
+
+    okm
+}
+
+/// Derives `out_len` bytes of key material from the input key material `ikm` with HKDF
+/// (RFC 5869), using HMAC-SHA3-256 as the PRF. `salt` may be empty, `info` binds the output to a
+/// context so that distinct `info` values yield independent keys from the same secret. `out_len`
+/// must be between 1 and 8160 (255 SHA3-256 blocks) bytes. Returns 0 on an invalid length.
+#[no_mangle]
+pub extern "C" fn hkdf_derive(
+    ikm: *const u8,
+    ikm_len: usize,
+    salt: *const u8,
+    salt_len: usize,
+    info: *const u8,
+    info_len: usize,
+    out_len: usize,
+) -> u64 {
+    if out_len == 0 || out_len > MAX_OUTPUT_LEN {
+        log("hkdf output length must be between 1 and 8160 bytes");
+        return 0;
+    }
+
+    let ikm = unsafe { slice::from_raw_parts(ikm, ikm_len) };
+    let salt = unsafe { slice::from_raw_parts(salt, salt_len) };
+    let info = unsafe { slice::from_raw_parts(info, info_len) };
+
+    forget_buf_ptr_len(hkdf_sha3_256(ikm, salt, info, out_len))
+}
+
+// Derives the message key of an account and a peer: the ECDH shared secret is the x-coordinate of
+// peer_address * view_key, which both sides compute as G * view_key_a * view_key_b, and the key is
+// HKDF-SHA3-256 over its 32 little-endian bytes with the MESSAGE_KEY_SALT salt and the context as
+// info.
+fn derive_message_key(
+    private_key: PrivateKey<CurrentNetwork>,
+    peer_address: Address<CurrentNetwork>,
+    context: &[u8],
+) -> Result<Vec<u8>> {
+    let view_key = ViewKey::try_from(private_key)?;
+    let shared_point = *peer_address * *view_key;
+    ensure!(!shared_point.is_zero(), "the shared secret is the identity");
+    let shared_secret = shared_point.to_x_coordinate().to_bytes_le()?;
+
+    Ok(hkdf_sha3_256(
+        &shared_secret,
+        MESSAGE_KEY_SALT,
+        context,
+        MESSAGE_KEY_SIZE,
+    ))
+}
+
+/// Derives a 32-byte symmetric key for off-chain messages between an account and a peer address.
+/// Both parties derive the same key: the ECDH shared secret is the x-coordinate of
+/// `peer_address * view_key`, where `view_key` is the view key of `private_key`, which equals
+/// `G * view_key_a * view_key_b` for both sides. The key is HKDF-SHA3-256 (see `hkdf_derive`) over
+/// the 32 little-endian bytes of the shared secret, with the salt `AleoUtilsMessageKey0` and
+/// `context` as info.
+///
+/// The key only depends on the two accounts and the context, so a one-time key needs a context
+/// that is never reused, e.g. one including a message ID or counter. Anyone holding either view key
+/// can derive the key. The returned buffer should be released with `free_secret`. Returns 0 if an
+/// input fails to parse.
+#[no_mangle]
+pub extern "C" fn message_key(
+    private_key: *const u8,
+    private_key_len: usize,
+    peer_address: *const u8,
+    peer_address_len: usize,
+    context: *const u8,
+    context_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(peer_address) =
+        parse_from_raw::<Address<CurrentNetwork>>(peer_address, peer_address_len, "peer address")
+    else {
+        return 0;
+    };
+    let context = unsafe { slice::from_raw_parts(context, context_len) };
+
+    let Some(key) = ok_or_log(
+        derive_message_key(private_key, peer_address, context),
+        "failed to derive message key",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(key)
+}
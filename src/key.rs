@@ -1,13 +1,147 @@
-use alloc::string::ToString;
-use core::{slice, str};
+use alloc::string::{String, ToString};
+use core::{iter, slice, str};
 
-use rand::{rngs::StdRng, SeedableRng};
+use rand::{rngs::StdRng, CryptoRng, Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use snarkvm_console::{
-    account::{Address, PrivateKey},
-    prelude::FromStr,
+    account::{Address, ComputeKey, GraphKey, PrivateKey, ViewKey},
+    network::{MainnetV0, TestnetV0},
+    prelude::{ensure, FromStr, Network, Result, ToBytes},
+    types::{Field, Group, Scalar},
 };
+use tiny_keccak::{Hasher, Sha3};
 
-use crate::{log::log, memory::forget_buf_ptr_len, network::CurrentNetwork};
+use crate::{
+    format::{check_in_subgroup, field_from_le_bytes, json_object, json_string, pack_bytes},
+    log::{clear_last_error, log, ok_or_log},
+    memory::{encode_frames, forget_buf_ptr_len, parse_frames, parse_from_raw, str_from_raw},
+    network::CurrentNetwork,
+};
+
+// Size of the seed child keys are derived from
+const SEED_SIZE: usize = 32;
+// Maximum number of addresses derived by a single derive_address_range call
+const MAX_DERIVE_RANGE: u32 = 1000;
+// Maximum number of private keys generated by a single new_private_keys call
+const MAX_NEW_KEYS: u32 = 1000;
+// Output formats of get_address_ext
+const ADDRESS_FORMAT_BECH32: u32 = 0;
+const ADDRESS_FORMAT_RAW: u32 = 1;
+
+// Bech32 alphabet, the data characters in the order of the 5-bit values they encode
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+// Constant the polymod of a valid bech32m string yields (BIP 350)
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+// Number of data characters holding the checksum
+const BECH32_CHECKSUM_LEN: usize = 6;
+// Human-readable part of Aleo addresses
+const ADDRESS_HRP: &str = "aleo";
+// Messages logged by verify_address_checksum, the host matches on them
+const ADDRESS_CHECKSUM_ERROR: &str = "invalid address checksum";
+const ADDRESS_HRP_ERROR: &str = "address has a valid checksum but isn't an aleo address";
+
+// BCH checksum of bech32 over 5-bit values (BIP 173)
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ff_ffff) << 5) ^ u32::from(value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+
+    checksum
+}
+
+// Checks that a string is well-formed bech32m with a valid checksum, without decoding its data, and
+// returns its lowercase human-readable part.
+fn bech32m_hrp(string: &str) -> Option<String> {
+    if !string.bytes().all(|c| (33..=126).contains(&c)) {
+        return None;
+    }
+    if string.bytes().any(|c| c.is_ascii_lowercase())
+        && string.bytes().any(|c| c.is_ascii_uppercase())
+    {
+        return None;
+    }
+
+    let string = string.to_ascii_lowercase();
+    let (hrp, data) = string.rsplit_once('1')?;
+    if hrp.is_empty() || data.len() < BECH32_CHECKSUM_LEN {
+        return None;
+    }
+    let data = data
+        .bytes()
+        .map(|c| {
+            BECH32_CHARSET
+                .iter()
+                .position(|&d| d == c)
+                .map(|value| value as u8)
+        })
+        .collect::<Option<Vec<u8>>>()?;
+
+    let values = hrp
+        .bytes()
+        .map(|c| c >> 5)
+        .chain(iter::once(0))
+        .chain(hrp.bytes().map(|c| c & 0x1f))
+        .chain(data);
+
+    (bech32_polymod(values) == BECH32M_CONST).then(|| hrp.to_string())
+}
+
+// Derives the child private key at `index` from a 32-byte seed. The child key seed is computed as
+// Poseidon2(domain, seed[0..16], seed[16..32], index), where both seed halves and the index are read
+// as little-endian integers and the domain is the "AleoUtilsChildKey0" separator. The private key
+// is then created from that seed the same way snarkVM creates it from any private key seed.
+fn child_private_key(seed: &[u8], index: u32) -> Result<PrivateKey<CurrentNetwork>> {
+    ensure!(
+        seed.len() == SEED_SIZE,
+        "seed must be {SEED_SIZE} bytes long"
+    );
+
+    let (low, high) = seed.split_at(SEED_SIZE / 2);
+    let input = [
+        Field::<CurrentNetwork>::new_domain_separator("AleoUtilsChildKey0"),
+        field_from_le_bytes(low)?,
+        field_from_le_bytes(high)?,
+        field_from_le_bytes(&index.to_le_bytes())?,
+    ];
+    let child_seed = CurrentNetwork::hash_psd2(&input)?;
+
+    PrivateKey::try_from(child_seed)
+}
+
+// Parses a private key of network N and returns the address string it has on that network
+fn network_address<N: Network>(private_key: &str) -> Result<String> {
+    let private_key = PrivateKey::<N>::from_str(private_key)?;
+
+    Ok(Address::<N>::try_from(private_key)?.to_string())
+}
+
+// Derives the private key of a label. The key seed is Poseidon2 over the "AleoUtilsLabelKey0"
+// domain separator followed by the label packed into fields (see `pack_bytes`), and the private
+// key is created from that seed the same way snarkVM creates it from any private key seed.
+fn label_private_key(label: &[u8]) -> Result<PrivateKey<CurrentNetwork>> {
+    let mut input = vec![Field::<CurrentNetwork>::new_domain_separator(
+        "AleoUtilsLabelKey0",
+    )];
+    input.extend(pack_bytes(label)?);
+    let seed = CurrentNetwork::hash_psd2(&input)?;
+
+    PrivateKey::try_from(seed)
+}
 
 #[no_mangle]
 pub extern "C" fn new_private_key() -> u64 {
@@ -27,6 +161,44 @@ pub extern "C" fn new_private_key() -> u64 {
     forget_buf_ptr_len(output_bytes)
 }
 
+// Generates `count` private keys from one RNG and returns their strings
+fn private_key_batch<R: Rng + CryptoRng>(count: u32, rng: &mut R) -> Result<Vec<String>> {
+    (0..count)
+        .map(|_| PrivateKey::<CurrentNetwork>::new(rng).map(|key| key.to_string()))
+        .collect()
+}
+
+/// Generates `count` private keys in one call and returns them as a framed list of private key
+/// strings. With an empty `seed` the keys are sampled from entropy, with a 32-byte seed they're
+/// sampled from a ChaCha20 RNG seeded with it, so the same seed always yields the same batch. A
+/// seeded batch is only as secret as its seed, use it for load testing and reproducible
+/// provisioning. `count` must be between 1 and 1000.
+#[no_mangle]
+pub extern "C" fn new_private_keys(count: u32, seed: *const u8, seed_len: usize) -> u64 {
+    if !(1..=MAX_NEW_KEYS).contains(&count) {
+        log("number of private keys must be between 1 and 1000");
+        return 0;
+    }
+
+    let keys = match seed_len {
+        0 => private_key_batch(count, &mut StdRng::from_entropy()),
+        SEED_SIZE => {
+            let mut rng_seed = [0u8; SEED_SIZE];
+            rng_seed.copy_from_slice(unsafe { slice::from_raw_parts(seed, seed_len) });
+            private_key_batch(count, &mut ChaCha20Rng::from_seed(rng_seed))
+        }
+        _ => {
+            log("seed must be empty or 32 bytes long");
+            return 0;
+        }
+    };
+    let Some(keys) = ok_or_log(keys, "failed to generate new private keys") else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(encode_frames(&keys))
+}
+
 #[no_mangle]
 pub extern "C" fn get_address(private_key: *const u8, private_key_len: usize) -> u64 {
     // Convert the input string to a Rust string
@@ -74,3 +246,453 @@ pub extern "C" fn get_address(private_key: *const u8, private_key_len: usize) ->
     let output_bytes = address.into_bytes();
     forget_buf_ptr_len(output_bytes)
 }
+
+/// Returns the address of a private key in the requested format:
+///
+/// - `ADDRESS_FORMAT_BECH32` (0) - the `aleo1...` string, same as `get_address`
+/// - `ADDRESS_FORMAT_RAW` (1) - the 32-byte little-endian x-coordinate of the address group
+///   element, the field its bech32 form encodes
+///
+/// Returns 0 for unknown formats.
+#[no_mangle]
+pub extern "C" fn get_address_ext(
+    private_key: *const u8,
+    private_key_len: usize,
+    format: u32,
+) -> u64 {
+    if format != ADDRESS_FORMAT_BECH32 && format != ADDRESS_FORMAT_RAW {
+        log("unknown address format");
+        return 0;
+    }
+
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(address) = ok_or_log(
+        Address::<CurrentNetwork>::try_from(private_key),
+        "failed to convert a private key to address",
+    ) else {
+        return 0;
+    };
+
+    let output = if format == ADDRESS_FORMAT_RAW {
+        let Some(bytes) = ok_or_log(address.to_bytes_le(), "failed to serialize address") else {
+            return 0;
+        };
+        bytes
+    } else {
+        address.to_string().into_bytes()
+    };
+
+    forget_buf_ptr_len(output)
+}
+
+/// Returns the compute key of a private key as a framed list of its components: the `pk_sig` and
+/// `pr_sig` group strings and the `sk_prf` scalar string.
+#[no_mangle]
+pub extern "C" fn get_compute_key(private_key: *const u8, private_key_len: usize) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+
+    let Some(compute_key) = ok_or_log(
+        ComputeKey::try_from(private_key),
+        "failed to convert a private key to compute key",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(encode_frames(&[
+        compute_key.pk_sig().to_string(),
+        compute_key.pr_sig().to_string(),
+        compute_key.sk_prf().to_string(),
+    ]))
+}
+
+/// Returns the secret scalars of a private key as a framed list of scalar strings: `sk_sig`, the
+/// signature secret key, and `r_sig`, the randomizer the compute key's `pr_sig` commits to. Both
+/// are derived from the private key seed.
+///
+/// The components are as sensitive as the private key itself: anyone holding them can sign as the
+/// account, since signing only needs `sk_sig` and `r_sig`. The returned buffer should be released
+/// with `free_secret`, and the components must never be logged or stored unencrypted. The seed
+/// can't be recovered from them.
+#[no_mangle]
+pub extern "C" fn private_key_components(private_key: *const u8, private_key_len: usize) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(encode_frames(&[
+        private_key.sk_sig().to_string(),
+        private_key.r_sig().to_string(),
+    ]))
+}
+
+// Rebuilds a compute key from its components. snarkVM derives sk_prf from pk_sig and pr_sig, so the
+// given sk_prf is only checked against the derived one.
+fn compute_key_from_components(
+    pk_sig: Group<CurrentNetwork>,
+    pr_sig: Group<CurrentNetwork>,
+    sk_prf: Scalar<CurrentNetwork>,
+) -> Result<ComputeKey<CurrentNetwork>> {
+    let compute_key = ComputeKey::try_from((pk_sig, pr_sig))?;
+    ensure!(
+        compute_key.sk_prf() == sk_prf,
+        "sk_prf doesn't match pk_sig and pr_sig"
+    );
+
+    Ok(compute_key)
+}
+
+/// Derives an address from the components of a compute key, e.g. ones stored separately by an
+/// external key manager: the `pk_sig` and `pr_sig` group strings and the `sk_prf` scalar string.
+/// Returns the address string, or 0 if a component fails to parse or they don't form a compute key.
+#[no_mangle]
+pub extern "C" fn address_from_components(
+    pk_sig: *const u8,
+    pk_sig_len: usize,
+    pr_sig: *const u8,
+    pr_sig_len: usize,
+    sk_prf: *const u8,
+    sk_prf_len: usize,
+) -> u64 {
+    let Some(pk_sig) = parse_from_raw::<Group<CurrentNetwork>>(pk_sig, pk_sig_len, "pk_sig") else {
+        return 0;
+    };
+    let Some(pr_sig) = parse_from_raw::<Group<CurrentNetwork>>(pr_sig, pr_sig_len, "pr_sig") else {
+        return 0;
+    };
+    let Some(sk_prf) = parse_from_raw::<Scalar<CurrentNetwork>>(sk_prf, sk_prf_len, "sk_prf")
+    else {
+        return 0;
+    };
+
+    let Some(address) = ok_or_log(
+        compute_key_from_components(pk_sig, pr_sig, sk_prf)
+            .and_then(Address::<CurrentNetwork>::try_from),
+        "failed to derive address from compute key components",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(address.to_string().into_bytes())
+}
+
+/// Checks the internal consistency of a compute key given as a framed list of its components, as
+/// `get_compute_key` returns them: the `pk_sig` and `pr_sig` group strings and the `sk_prf` scalar
+/// string. The key is valid if both group elements are non-identity points of the prime-order
+/// subgroup and `sk_prf` is the one snarkVM derives from them, so the address derived from the
+/// components is the address of the key's account.
+///
+/// Returns 1 for a valid compute key and 0 otherwise. The last error is cleared on entry and is
+/// only set when the components fail to parse, so a 0 with an empty last error means the
+/// components parse but don't form a valid compute key.
+#[no_mangle]
+pub extern "C" fn compute_key_is_valid(compute_key: *const u8, compute_key_len: usize) -> u64 {
+    clear_last_error();
+
+    let compute_key = unsafe { slice::from_raw_parts(compute_key, compute_key_len) };
+    let Some(components) = parse_frames::<String>(compute_key, "compute key") else {
+        return 0;
+    };
+    let [pk_sig, pr_sig, sk_prf] = components.as_slice() else {
+        log("a compute key has 3 components: pk_sig, pr_sig and sk_prf");
+        return 0;
+    };
+    let Some(pk_sig) = ok_or_log(Group::<CurrentNetwork>::from_str(pk_sig), "invalid pk_sig")
+    else {
+        return 0;
+    };
+    let Some(pr_sig) = ok_or_log(Group::<CurrentNetwork>::from_str(pr_sig), "invalid pr_sig")
+    else {
+        return 0;
+    };
+    let Some(sk_prf) = ok_or_log(Scalar::<CurrentNetwork>::from_str(sk_prf), "invalid sk_prf")
+    else {
+        return 0;
+    };
+
+    let valid = check_in_subgroup(&pk_sig).is_ok()
+        && check_in_subgroup(&pr_sig).is_ok()
+        && compute_key_from_components(pk_sig, pr_sig, sk_prf).is_ok();
+
+    valid as u64
+}
+
+/// Returns the view key of a private key.
+#[no_mangle]
+pub extern "C" fn get_view_key(private_key: *const u8, private_key_len: usize) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+
+    let Some(view_key) = ok_or_log(
+        ViewKey::try_from(private_key),
+        "failed to convert a private key to view key",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(view_key.to_string().into_bytes())
+}
+
+/// Returns the secret scalar of a view key as a scalar string. A view key is this scalar: records
+/// are decrypted with the record view key (nonce * secret).x and the address is G * secret, so the
+/// host can perform the ECDH steps of a custom decryption pipeline itself.
+///
+/// The secret is as sensitive as the view key: it decrypts every record owned by the account. The
+/// returned buffer should be released with `free_secret`.
+#[no_mangle]
+pub extern "C" fn view_key_secret(view_key: *const u8, view_key_len: usize) -> u64 {
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+
+    forget_buf_ptr_len((*view_key).to_string().into_bytes())
+}
+
+/// Derives the child private key at `index` from a 32-byte master `seed`, so wallets can recreate
+/// a whole family of accounts from one backed-up secret. The child key seed is
+/// Poseidon2("AleoUtilsChildKey0", seed[0..16], seed[16..32], index), with the domain separator
+/// as the first field, both 16-byte seed halves read as little-endian integers and the index as a
+/// little-endian u32, and the private key is created from it the way snarkVM creates a private key
+/// from any seed (see `child_private_key`). Every index gives an independent key, and keys can't be
+/// linked to each other or to the seed without the seed.
+///
+/// The child key is as sensitive as the seed for its own account, the returned buffer should be
+/// released with `free_secret`. Returns the private key string, or 0 if the seed isn't
+/// SEED_SIZE bytes long.
+#[no_mangle]
+pub extern "C" fn derive_child_key(seed: *const u8, seed_len: usize, index: u32) -> u64 {
+    let seed_bytes = unsafe { slice::from_raw_parts(seed, seed_len) };
+
+    let pk = match child_private_key(seed_bytes, index) {
+        Ok(val) => val.to_string(),
+        Err(e) => {
+            let mut err_str = String::from("failed to derive child private key: ");
+            err_str.push_str(e.to_string().as_str());
+
+            log(err_str);
+
+            return 0;
+        }
+    };
+
+    let output_bytes = pk.into_bytes();
+    forget_buf_ptr_len(output_bytes)
+}
+
+/// Derives the addresses of the `count` child keys at `start`, `start + 1`, ... from a 32-byte
+/// master `seed`, for wallets scanning a derivation range. Every address is the address of the
+/// key `derive_child_key` returns for the same seed and index, but the private keys never leave
+/// the module.
+///
+/// Returns a framed list of address strings in index order, or 0 if the seed isn't SEED_SIZE bytes
+/// long, `count` isn't between 1 and MAX_DERIVE_RANGE (1000), or the range would go past the
+/// largest u32 index.
+#[no_mangle]
+pub extern "C" fn derive_address_range(
+    seed: *const u8,
+    seed_len: usize,
+    start: u32,
+    count: u32,
+) -> u64 {
+    if !(1..=MAX_DERIVE_RANGE).contains(&count) {
+        log("number of addresses must be between 1 and 1000");
+        return 0;
+    }
+
+    // index of the last derived key, the range must not wrap around
+    let last = match start.checked_add(count - 1) {
+        Some(val) => val,
+        None => {
+            log("derivation range exceeds the maximum key index");
+            return 0;
+        }
+    };
+
+    let seed_bytes = unsafe { slice::from_raw_parts(seed, seed_len) };
+
+    let mut addresses = Vec::with_capacity(count as usize);
+    for index in start..=last {
+        let address = match child_private_key(seed_bytes, index)
+            .and_then(Address::<CurrentNetwork>::try_from)
+        {
+            Ok(addr) => addr.to_string(),
+            Err(e) => {
+                let mut err_str = String::from("failed to derive child address: ");
+                err_str.push_str(e.to_string().as_str());
+
+                log(err_str);
+
+                return 0;
+            }
+        };
+
+        addresses.push(address);
+    }
+
+    forget_buf_ptr_len(encode_frames(&addresses))
+}
+
+/// Checks only the bech32m encoding of an address: its checksum and that its human-readable part
+/// is `aleo`, without decoding the address into a curve point. This is cheaper than parsing the
+/// address, and the logged error tells the two failures apart: "invalid address checksum" for
+/// malformed strings and bad checksums, and "address has a valid checksum but isn't an aleo
+/// address" for valid bech32m with another human-readable part, e.g. a record ciphertext or a
+/// bech32m string of another chain. Every Aleo network encodes addresses with the `aleo` prefix,
+/// so this can't tell an address of one network from another's. Returns 1 if both checks pass and
+/// 0 otherwise.
+#[no_mangle]
+pub extern "C" fn verify_address_checksum(address: *const u8, address_len: usize) -> u64 {
+    let Some(address) = str_from_raw(address, address_len, "address") else {
+        return 0;
+    };
+
+    let Some(hrp) = bech32m_hrp(address) else {
+        log(ADDRESS_CHECKSUM_ERROR);
+        return 0;
+    };
+    if hrp != ADDRESS_HRP {
+        log(ADDRESS_HRP_ERROR);
+        return 0;
+    }
+
+    1
+}
+
+/// Derives a well-known address from a public label, e.g. the faucet account of a service, so
+/// every service can reproduce the same address from the same label. The private key of the
+/// address is derived from the label alone (see `label_private_key`), so anyone who knows the
+/// label can spend from the address: these addresses are public, never send private funds to
+/// them. Returns the address string, or 0 on failure.
+#[no_mangle]
+pub extern "C" fn address_from_label(label: *const u8, label_len: usize) -> u64 {
+    let label_bytes = unsafe { slice::from_raw_parts(label, label_len) };
+
+    let address = label_private_key(label_bytes).and_then(Address::<CurrentNetwork>::try_from);
+    let Some(address) = ok_or_log(address, "failed to derive label address") else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(address.to_string().into_bytes())
+}
+
+/// Derives the address of a private key on an explicitly given network instead of the network the
+/// module is built for, so the host can show a key's address on several networks without
+/// switching any global state. `network_id` is the snarkVM network ID: 0 for mainnet (`MainnetV0`)
+/// and 1 for testnet (`TestnetV0`). Returns the address string, or 0 for unknown network IDs and
+/// invalid private keys.
+#[no_mangle]
+pub extern "C" fn address_for_network(
+    private_key: *const u8,
+    private_key_len: usize,
+    network_id: u32,
+) -> u64 {
+    let Some(private_key) = str_from_raw(private_key, private_key_len, "private key") else {
+        return 0;
+    };
+
+    let address = match u16::try_from(network_id) {
+        Ok(MainnetV0::ID) => network_address::<MainnetV0>(private_key),
+        Ok(TestnetV0::ID) => network_address::<TestnetV0>(private_key),
+        _ => {
+            log("unknown network ID");
+            return 0;
+        }
+    };
+    let Some(address) = ok_or_log(address, "failed to derive address") else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(address.into_bytes())
+}
+
+/// Assigns an address to one of `num_shards` shards, for services partitioning accounts. The shard
+/// is the first 8 bytes of the SHA3-256 digest of the canonical (lowercase bech32m) address string,
+/// read as a little-endian u64, modulo `num_shards`. The mapping only depends on the address and
+/// the shard count and is fixed, so it stays stable across versions and can be reproduced outside
+/// this module. The modulo bias is below `num_shards / 2^64` and negligible in practice.
+///
+/// Returns the shard index in `[0, num_shards)`. The last error is cleared on entry, and 0 is also
+/// returned with the last error set for an invalid address or a zero shard count.
+#[no_mangle]
+pub extern "C" fn address_shard(address: *const u8, address_len: usize, num_shards: u32) -> u64 {
+    clear_last_error();
+
+    if num_shards == 0 {
+        log("the number of shards has to be positive");
+        return 0;
+    }
+    let Some(address) = parse_from_raw::<Address<CurrentNetwork>>(address, address_len, "address")
+    else {
+        return 0;
+    };
+
+    let mut digest = [0u8; 32];
+    let mut hasher = Sha3::v256();
+    hasher.update(address.to_string().as_bytes());
+    hasher.finalize(&mut digest);
+
+    let value = u64::from_le_bytes(digest[..8].try_into().unwrap());
+    value % u64::from(num_shards)
+}
+
+// Derives every key of an account and renders them as the JSON object account_to_json returns
+fn account_json(private_key: &PrivateKey<CurrentNetwork>) -> Result<String> {
+    let view_key = ViewKey::try_from(private_key)?;
+    let compute_key = ComputeKey::try_from(private_key)?;
+    let graph_key = GraphKey::try_from(&view_key)?;
+    let address = Address::try_from(&compute_key)?;
+
+    Ok(json_object(&[
+        ("private_key", json_string(&private_key.to_string())),
+        ("view_key", json_string(&view_key.to_string())),
+        (
+            "compute_key",
+            json_object(&[
+                ("pk_sig", json_string(&compute_key.pk_sig().to_string())),
+                ("pr_sig", json_string(&compute_key.pr_sig().to_string())),
+                ("sk_prf", json_string(&compute_key.sk_prf().to_string())),
+            ]),
+        ),
+        (
+            "graph_key",
+            json_object(&[("sk_tag", json_string(&graph_key.sk_tag().to_string()))]),
+        ),
+        ("address", json_string(&address.to_string())),
+    ]))
+}
+
+/// Derives all keys of an account from its private key and returns them as one JSON object for
+/// wallet export, with the members in this order: `private_key`, `view_key`, `compute_key` (an
+/// object of the `pk_sig` and `pr_sig` groups and the `sk_prf` scalar, see `get_compute_key`),
+/// `graph_key` (an object of the `sk_tag` field) and `address`, all values as strings.
+///
+/// The JSON holds the private key, so it's as sensitive as the key itself: the returned buffer
+/// should be released with `free_secret`. Returns 0 if the private key fails to parse.
+#[no_mangle]
+pub extern "C" fn account_to_json(private_key: *const u8, private_key_len: usize) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+
+    let Some(json) = ok_or_log(account_json(&private_key), "failed to derive account keys") else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(json.into_bytes())
+}
@@ -1,11 +1,23 @@
 extern crate alloc;
 extern crate core;
 
+pub mod crypto;
+pub mod fee;
 pub mod format;
 pub mod hash;
+pub mod kdf;
 pub mod key;
 pub mod log;
 pub mod memory;
+pub mod merkle;
+pub mod program;
+pub mod record;
 pub mod sign;
+pub mod transaction;
+pub mod transition;
+
+// Exports the Go tests need to set up inputs, e.g. record ciphertexts, that aren't part of the API
+#[cfg(feature = "test-exports")]
+mod testing;
 
 mod network;
@@ -1,6 +1,14 @@
 // import logging function from host
 
-use alloc::string::String;
+use alloc::string::{String, ToString};
+use core::{cell::RefCell, fmt::Display};
+
+use crate::memory::forget_buf_ptr_len;
+
+std::thread_local! {
+    // Message of the last logged error, kept until the next error or clear_last_error
+    static LAST_ERROR: RefCell<String> = RefCell::new(String::new());
+}
 
 #[cfg(not(test))]
 extern "C" {
@@ -20,4 +28,40 @@ pub fn log<T: Into<String>>(val: T) {
     unsafe {
         host_log_string(ptr, len);
     }
+
+    LAST_ERROR.with(|last| *last.borrow_mut() = string);
+}
+
+// Returns the value of a result, or logs the error prefixed with `context` and returns None.
+pub(crate) fn ok_or_log<T, E: Display>(result: Result<T, E>, context: &str) -> Option<T> {
+    match result {
+        Ok(val) => Some(val),
+        Err(e) => {
+            let mut err_str = String::from(context);
+            err_str.push_str(": ");
+            err_str.push_str(e.to_string().as_str());
+
+            log(err_str);
+
+            None
+        }
+    }
+}
+
+/// Returns the message of the last error logged by any export, or 0 if there was none since the
+/// module was instantiated or the last error was cleared.
+#[no_mangle]
+pub extern "C" fn last_error() -> u64 {
+    let message = LAST_ERROR.with(|last| last.borrow().clone());
+    if message.is_empty() {
+        return 0;
+    }
+
+    forget_buf_ptr_len(message.into_bytes())
+}
+
+/// Clears the last error, so that a following last_error call only reports new failures.
+#[no_mangle]
+pub extern "C" fn clear_last_error() {
+    LAST_ERROR.with(|last| last.borrow_mut().clear());
 }
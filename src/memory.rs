@@ -1,41 +1,237 @@
-use alloc::vec::Vec;
-use core::{mem, ptr};
+use alloc::{string::ToString, vec::Vec};
+use core::{
+    cell::RefCell,
+    fmt::Display,
+    mem, ptr, slice, str,
+    str::FromStr,
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+};
+
+use crate::log::{log, ok_or_log};
+
+// Size of the little-endian length prefix of every item in a framed buffer
+const FRAME_HEADER_SIZE: usize = 4;
+// Size of the capacity header preceding every buffer handed to or returned to the host
+const ALLOC_HEADER_SIZE: usize = 8;
+// Bit offsets of the pointer and length in a packed (length << 32) | pointer return value
+const PACKED_PTR_SHIFT: u32 = 0;
+const PACKED_LEN_SHIFT: u32 = 32;
+
+// Number of buffers allocated for or returned to the host that haven't been deallocated yet
+static LIVE_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+// Number of regions requested from the allocator for host buffers since instantiation
+static HEAP_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+// Largest size in bytes `alloc` accepts, 0 for no limit, see set_max_alloc
+static MAX_ALLOC: AtomicU64 = AtomicU64::new(0);
+
+// Whether deallocated host buffers are kept for reuse, see enable_buffer_pool
+static POOL_ENABLED: AtomicBool = AtomicBool::new(false);
+// Number of size buckets, pooled regions have a power of two capacity below 1 << POOL_BUCKETS
+const POOL_BUCKETS: usize = 24;
+// Maximum number of free regions kept per bucket
+const POOL_BUCKET_LIMIT: usize = 16;
+
+std::thread_local! {
+    // Free regions by size bucket, the regions of bucket i have a capacity of exactly 1 << i
+    static POOL: RefCell<Vec<Vec<Vec<u8>>>> = RefCell::new(Vec::new());
+}
+
+// Returns the pool bucket of a capacity, if regions of that size are pooled
+fn pool_bucket(capacity: usize) -> Option<usize> {
+    let bucket = capacity.checked_next_power_of_two()?.trailing_zeros() as usize;
+    (bucket < POOL_BUCKETS).then_some(bucket)
+}
+
+// Returns an empty region with room for at least `capacity` bytes. With the pool enabled the
+// capacity is rounded up to a power of two, and a free region of that size is reused if there is
+// one.
+fn take_region(capacity: usize) -> Vec<u8> {
+    if POOL_ENABLED.load(Ordering::Relaxed) {
+        if let Some(bucket) = pool_bucket(capacity) {
+            let pooled = POOL.with(|pool| pool.borrow_mut().get_mut(bucket).and_then(Vec::pop));
+            if let Some(region) = pooled {
+                return region;
+            }
+
+            HEAP_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            return Vec::with_capacity(1 << bucket);
+        }
+    }
+
+    HEAP_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    Vec::with_capacity(capacity)
+}
+
+// Releases a region, keeping it for reuse if the pool is enabled and its bucket isn't full
+fn release_region(region: Vec<u8>) {
+    let capacity = region.capacity();
+    if !POOL_ENABLED.load(Ordering::Relaxed) || !capacity.is_power_of_two() {
+        return;
+    }
+    let Some(bucket) = pool_bucket(capacity) else {
+        return;
+    };
+
+    POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        if pool.is_empty() {
+            pool.resize_with(POOL_BUCKETS, Vec::new);
+        }
+        if pool[bucket].len() < POOL_BUCKET_LIMIT {
+            pool[bucket].push(region);
+        }
+    });
+}
 
 pub fn forget_buf_ptr_len(mut buf: Vec<u8>) -> u64 {
     buf.shrink_to_fit();
     debug_assert_eq!(buf.capacity(), buf.len());
     let len = buf.len();
-    let total_capacity = len.checked_add(8).expect("buffer too large");
-    let mut v: Vec<u8> = Vec::with_capacity(total_capacity);
+    let total_capacity = len
+        .checked_add(ALLOC_HEADER_SIZE)
+        .expect("buffer too large");
+    let mut v: Vec<u8> = take_region(total_capacity);
     let cap = v.capacity();
     let base = v.as_mut_ptr();
     unsafe {
         // Write capacity header
         ptr::write_unaligned(base.cast::<u64>(), cap as u64);
         // Copy data bytes after header
-        ptr::copy_nonoverlapping(buf.as_ptr(), base.add(8), len);
-        mem::forget(buf);
-        let data_ptr = base.add(8) as usize as u64;
+        ptr::copy_nonoverlapping(buf.as_ptr(), base.add(ALLOC_HEADER_SIZE), len);
+        // The data now lives in the returned region, release the source buffer
+        drop(buf);
+        let data_ptr = base.add(ALLOC_HEADER_SIZE) as usize as u64;
         mem::forget(v);
-        ((len as u64) << 32) | data_ptr
+        LIVE_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ((len as u64) << PACKED_LEN_SHIFT) | data_ptr
+    }
+}
+
+/// Encodes a list of items as a framed buffer: every item is prefixed with its length as a 4-byte
+/// little-endian integer. Lists passed to or returned from exported functions use this layout.
+pub fn encode_frames<T: AsRef<[u8]>>(items: &[T]) -> Vec<u8> {
+    let total_len = items
+        .iter()
+        .map(|item| FRAME_HEADER_SIZE + item.as_ref().len())
+        .sum();
+    let mut buf = Vec::with_capacity(total_len);
+    for item in items {
+        let item = item.as_ref();
+        buf.extend_from_slice(&(item.len() as u32).to_le_bytes());
+        buf.extend_from_slice(item);
+    }
+
+    buf
+}
+
+/// Splits a framed buffer (see `encode_frames`) into its items. Returns None if a length prefix
+/// is truncated or points past the end of the buffer.
+pub fn decode_frames(mut buf: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut items = Vec::new();
+    while !buf.is_empty() {
+        if buf.len() < FRAME_HEADER_SIZE {
+            return None;
+        }
+        let (header, rest) = buf.split_at(FRAME_HEADER_SIZE);
+        let item_len = u32::from_le_bytes(header.try_into().ok()?) as usize;
+        if rest.len() < item_len {
+            return None;
+        }
+        let (item, rest) = rest.split_at(item_len);
+        items.push(item);
+        buf = rest;
+    }
+
+    Some(items)
+}
+
+// Parses every item of a framed list of strings (see `decode_frames`) with `T::from_str`. Logs the
+// failure with the name of the list and returns None if the list or any of its items is invalid.
+pub(crate) fn parse_frames<T>(buf: &[u8], name: &str) -> Option<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let mut context = String::from("failed to parse ");
+    context.push_str(name);
+
+    let Some(items) = decode_frames(buf) else {
+        context.push_str(": malformed framed list");
+        log(context);
+        return None;
+    };
+
+    items
+        .into_iter()
+        .map(|item| {
+            let val = ok_or_log(str::from_utf8(item), &context)?;
+            ok_or_log(T::from_str(val), &context)
+        })
+        .collect()
+}
+
+// Rebuilds a UTF-8 string argument from a pointer, logging the failure with the argument name
+pub(crate) fn str_from_raw<'a>(ptr: *const u8, len: usize, name: &str) -> Option<&'a str> {
+    match str::from_utf8(unsafe { slice::from_raw_parts(ptr, len) }) {
+        Ok(val) => Some(val),
+        Err(e) => {
+            let mut err_str = String::from("failed to rebuild ");
+            err_str.push_str(name);
+            err_str.push_str(" from pointer: ");
+            err_str.push_str(e.to_string().as_str());
+
+            log(err_str);
+
+            None
+        }
     }
 }
 
+// Rebuilds a string argument from a pointer and parses it with `T::from_str`, logging the failure
+// with the argument name
+pub(crate) fn parse_from_raw<T>(ptr: *const u8, len: usize, name: &str) -> Option<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let val = str_from_raw(ptr, len, name)?;
+
+    let mut context = String::from("failed to parse ");
+    context.push_str(name);
+    context.push_str(" from string");
+
+    ok_or_log(T::from_str(val), &context)
+}
+
+// Returns the limit set with `set_max_alloc`, 0 for no limit
+pub(crate) fn max_alloc() -> u64 {
+    MAX_ALLOC.load(Ordering::Relaxed)
+}
+
 // Header-based allocation (8-byte little-endian capacity header preceding data region)
 // Returns a pointer to usable data (after the header). The second parameter passed from Go
 // to `dealloc` is ignored for safety; capacity is always read from the header.
+// Returns null if the size exceeds the limit set with `set_max_alloc`.
 #[no_mangle]
 pub extern "C" fn alloc(size: usize) -> *const u8 {
+    let max_alloc = max_alloc();
+    if max_alloc != 0 && size as u64 > max_alloc {
+        log("allocation size exceeds the limit set with set_max_alloc");
+        return ptr::null();
+    }
+
     // Allocate vector with space for header + requested size (length left 0; caller writes bytes).
-    let mut v: Vec<u8> = Vec::with_capacity(size + 8);
+    let mut v: Vec<u8> = take_region(size + ALLOC_HEADER_SIZE);
     let full_cap = v.capacity();
     let base = v.as_mut_ptr(); // pointer to header start
     unsafe {
         // Store full vector capacity (not just requested size) so we can reconstruct exactly.
         ptr::write_unaligned(base.cast::<u64>(), full_cap as u64);
         // We purposely leave length at 0; caller will write directly into linear memory.
-        let data_ptr = base.add(8);
+        let data_ptr = base.add(ALLOC_HEADER_SIZE);
         mem::forget(v);
+        LIVE_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
         data_ptr as *const u8
     }
 }
@@ -46,9 +242,88 @@ pub extern "C" fn dealloc(data_ptr: *const u8, _ignored: usize) {
         return;
     }
     unsafe {
-        let header_ptr = data_ptr.sub(8);
+        let header_ptr = data_ptr.sub(ALLOC_HEADER_SIZE);
         let full_cap = ptr::read_unaligned(header_ptr.cast::<u64>()) as usize;
         // Rebuild Vec using the original full capacity (length 0 since caller relinquishes ownership)
-        let _ = Vec::from_raw_parts(header_ptr.cast_mut(), 0, full_cap);
+        release_region(Vec::from_raw_parts(header_ptr.cast_mut(), 0, full_cap));
+    }
+    LIVE_ALLOCATIONS.fetch_sub(1, Ordering::Relaxed);
+}
+
+/// Zeroizes and deallocates a buffer returned by an export, given the exact packed
+/// (length << 32) | pointer value the export returned. Meant for buffers holding secrets such as
+/// private keys, so the host doesn't have to unpack the value and wipe the memory itself.
+#[no_mangle]
+pub extern "C" fn free_secret(packed: u64) {
+    let data_ptr = (packed as u32) as usize as *mut u8;
+    let len = (packed >> PACKED_LEN_SHIFT) as usize;
+    if data_ptr.is_null() {
+        return;
+    }
+
+    unsafe {
+        for i in 0..len {
+            // volatile writes can't be optimized away even though the memory is freed right after
+            ptr::write_volatile(data_ptr.add(i), 0);
+        }
     }
+
+    dealloc(data_ptr, len);
+}
+
+/// Returns the number of buffers allocated with `alloc` or returned by exports that haven't been
+/// deallocated yet, so the host can check it doesn't leak module memory.
+#[no_mangle]
+pub extern "C" fn allocation_count() -> u64 {
+    LIVE_ALLOCATIONS.load(Ordering::Relaxed) as u64
+}
+
+/// Describes the FFI contract of the module, so the host can check it matches its expectations
+/// when the module is loaded. Returns a framed list of little-endian u32 values:
+///
+/// - pointer width in bits
+/// - 1 if the target is little-endian, 0 otherwise
+/// - size of the capacity header preceding allocated buffers, in bytes
+/// - bit offset of the pointer in a packed return value
+/// - bit offset of the length in a packed return value
+#[no_mangle]
+pub extern "C" fn abi_info() -> u64 {
+    let values: [u32; 5] = [
+        usize::BITS,
+        cfg!(target_endian = "little") as u32,
+        ALLOC_HEADER_SIZE as u32,
+        PACKED_PTR_SHIFT,
+        PACKED_LEN_SHIFT,
+    ];
+    let items = values.map(u32::to_le_bytes);
+
+    forget_buf_ptr_len(encode_frames(&items))
+}
+
+/// Turns the buffer pool on (`on` != 0) or off. With the pool on, deallocated buffers are kept in
+/// a freelist by power of two size bucket and reused by `alloc` and for export results, instead of
+/// going through the allocator every time. Turning it off releases all pooled buffers. The pool is
+/// off by default.
+#[no_mangle]
+pub extern "C" fn enable_buffer_pool(on: u32) {
+    POOL_ENABLED.store(on != 0, Ordering::Relaxed);
+    if on == 0 {
+        POOL.with(|pool| pool.borrow_mut().clear());
+    }
+}
+
+/// Limits the size of a single buffer the host can request with `alloc` to `bytes`, so lengths
+/// taken from untrusted input can't exhaust the module's memory. Larger requests return null and
+/// log an error. 0, the default, removes the limit. Buffers returned by exports aren't limited.
+#[no_mangle]
+pub extern "C" fn set_max_alloc(bytes: u64) {
+    MAX_ALLOC.store(bytes, Ordering::Relaxed);
+}
+
+/// Returns the number of regions requested from the allocator for buffers allocated with `alloc`
+/// or returned by exports since the module was instantiated. Buffers reused from the pool aren't
+/// counted.
+#[no_mangle]
+pub extern "C" fn heap_allocation_count() -> u64 {
+    HEAP_ALLOCATIONS.load(Ordering::Relaxed) as u64
 }
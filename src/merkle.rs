@@ -0,0 +1,567 @@
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+use core::{slice, str};
+
+use snarkvm_console::{
+    prelude::{bail, ensure, FromStr, Network, Result, ToBits, Zero},
+    program::TransitionLeaf,
+    types::Field,
+};
+
+use crate::{
+    log::{clear_last_error, log, ok_or_log},
+    memory::{
+        decode_frames, encode_frames, forget_buf_ptr_len, parse_frames, parse_from_raw,
+        str_from_raw,
+    },
+    network::CurrentNetwork,
+};
+
+// Maximum depth of a tree, leaf indices are u32
+const MAX_TREE_DEPTH: u32 = 32;
+
+// Depth of snarkVM's block tree (`BLOCK_TREE_DEPTH`), whose root is the state root
+const BLOCK_TREE_DEPTH: u8 = 32;
+
+/// Returned by `merkle_find_leaf` if the target isn't among the leaves
+pub const LEAF_NOT_FOUND: u64 = u64::MAX;
+
+// Hashes a leaf the way snarkVM BHP Merkle trees (`Network::merkle_tree_bhp`) do: BHP1024 of the
+// leaf bits prefixed with a `false` bit, which separates leaf hashes from inner node hashes.
+pub(crate) fn hash_leaf(leaf: &[bool]) -> Result<Field<CurrentNetwork>> {
+    let mut input = Vec::with_capacity(1 + leaf.len());
+    input.push(false);
+    input.extend_from_slice(leaf);
+
+    CurrentNetwork::hash_bhp1024(&input)
+}
+
+// Hashes two child nodes the way snarkVM BHP Merkle trees do: BHP512 of the bits of both children
+// prefixed with a `true` bit
+fn hash_children(
+    left: &Field<CurrentNetwork>,
+    right: &Field<CurrentNetwork>,
+) -> Result<Field<CurrentNetwork>> {
+    let mut input = vec![true];
+    input.extend(left.to_bits_le());
+    input.extend(right.to_bits_le());
+
+    CurrentNetwork::hash_bhp512(&input)
+}
+
+// Depth of the smallest tree with room for `leaf_count` leaves
+fn tree_depth(leaf_count: usize) -> u32 {
+    leaf_count.next_power_of_two().trailing_zeros()
+}
+
+// Builds every level of the tree over the leaves, from the leaf hashes up to the root. The leaf
+// level is padded to a power of two with the empty hash, the hash of two zero children.
+fn tree_levels(leaves: &[Field<CurrentNetwork>]) -> Result<Vec<Vec<Field<CurrentNetwork>>>> {
+    ensure!(!leaves.is_empty(), "a tree needs at least one leaf");
+    ensure!(
+        tree_depth(leaves.len()) <= MAX_TREE_DEPTH,
+        "too many leaves"
+    );
+
+    let mut level = leaves
+        .iter()
+        .map(|leaf| hash_leaf(&leaf.to_bits_le()))
+        .collect::<Result<Vec<_>>>()?;
+    let empty_hash = hash_children(&Field::zero(), &Field::zero())?;
+    level.resize(leaves.len().next_power_of_two(), empty_hash);
+
+    let mut levels = vec![level];
+    while levels[levels.len() - 1].len() > 1 {
+        let parents = levels[levels.len() - 1]
+            .chunks(2)
+            .map(|pair| hash_children(&pair[0], &pair[1]))
+            .collect::<Result<Vec<_>>>()?;
+        levels.push(parents);
+    }
+
+    Ok(levels)
+}
+
+// Walks from known nodes of the leaf level, (index, hash) pairs sorted by index, up to the root and
+// returns it. At every level each node is paired with its sibling, taken from the known nodes if
+// it's one of them and from `sibling(level, index)` otherwise. The siblings are requested level by
+// level from the leaves up, and within a level from left to right, which is the order of the
+// hashes in a multiproof.
+fn walk_to_root(
+    mut nodes: Vec<(u64, Field<CurrentNetwork>)>,
+    depth: u32,
+    mut sibling: impl FnMut(u32, u64) -> Result<Field<CurrentNetwork>>,
+) -> Result<Field<CurrentNetwork>> {
+    for level in 0..depth {
+        let mut parents = Vec::with_capacity(nodes.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            let (index, hash) = nodes[i];
+            let sibling_hash = match nodes.get(i + 1) {
+                Some(&(next_index, next_hash)) if next_index == index ^ 1 => {
+                    i += 1;
+                    next_hash
+                }
+                _ => sibling(level, index ^ 1)?,
+            };
+
+            let (left, right) = if index & 1 == 0 {
+                (hash, sibling_hash)
+            } else {
+                (sibling_hash, hash)
+            };
+            parents.push((index >> 1, hash_children(&left, &right)?));
+            i += 1;
+        }
+        nodes = parents;
+    }
+
+    ensure!(nodes.len() == 1, "leaf nodes don't lead to a single root");
+    Ok(nodes[0].1)
+}
+
+// Pairs leaf hashes with their indices, sorted by index. Rejects empty, mismatched and duplicate
+// indices, and indices outside a tree of `leaf_count` leaves.
+fn indexed_leaves(
+    leaf_hashes: Vec<Field<CurrentNetwork>>,
+    indices: &[u32],
+    leaf_count: u64,
+) -> Result<Vec<(u64, Field<CurrentNetwork>)>> {
+    ensure!(
+        !indices.is_empty() && leaf_hashes.len() == indices.len(),
+        "expected the same non-zero number of leaves and indices"
+    );
+
+    let mut nodes = indices
+        .iter()
+        .map(|&index| u64::from(index))
+        .zip(leaf_hashes)
+        .collect::<Vec<_>>();
+    nodes.sort_by_key(|(index, _)| *index);
+    ensure!(
+        nodes.windows(2).all(|pair| pair[0].0 != pair[1].0),
+        "duplicate leaf index"
+    );
+    ensure!(
+        nodes[nodes.len() - 1].0 < leaf_count,
+        "leaf index out of range"
+    );
+
+    Ok(nodes)
+}
+
+// State of an incremental Merkle tree, the handle returned to the host is a pointer to it
+struct IncrementalTree {
+    depth: u32,
+    // Number of leaves appended so far, the index of the next leaf
+    leaf_count: u64,
+    // Per level, the last left node whose right sibling isn't complete yet
+    frontier: Vec<Field<CurrentNetwork>>,
+    // Per level, the root of an empty subtree: the padding leaf, then the hash of two of them
+    empty: Vec<Field<CurrentNetwork>>,
+}
+
+impl IncrementalTree {
+    fn new(depth: u32) -> Result<Self> {
+        ensure!(
+            (1..=MAX_TREE_DEPTH).contains(&depth),
+            "tree depth must be between 1 and {}",
+            MAX_TREE_DEPTH
+        );
+
+        let mut empty = vec![hash_children(&Field::zero(), &Field::zero())?];
+        for level in 0..depth as usize {
+            empty.push(hash_children(&empty[level], &empty[level])?);
+        }
+
+        Ok(Self {
+            depth,
+            leaf_count: 0,
+            frontier: vec![Field::zero(); depth as usize],
+            empty,
+        })
+    }
+
+    // Appends a leaf and returns the new root, hashing only the path from the leaf to the root
+    fn append(&mut self, leaf: &Field<CurrentNetwork>) -> Result<Field<CurrentNetwork>> {
+        ensure!(self.leaf_count < 1 << self.depth, "the tree is full");
+
+        let index = self.leaf_count;
+        let mut hash = hash_leaf(&leaf.to_bits_le())?;
+        for level in 0..self.depth as usize {
+            hash = if (index >> level) & 1 == 0 {
+                self.frontier[level] = hash;
+                hash_children(&hash, &self.empty[level])?
+            } else {
+                hash_children(&self.frontier[level], &hash)?
+            };
+        }
+        self.leaf_count += 1;
+
+        Ok(hash)
+    }
+}
+
+// Parses a framed list of leaf indices, each a 4-byte little-endian integer
+fn parse_indices(buf: &[u8]) -> Option<Vec<u32>> {
+    let Some(items) = decode_frames(buf) else {
+        log("malformed list of leaf indices");
+        return None;
+    };
+
+    items
+        .into_iter()
+        .map(|item| match <[u8; 4]>::try_from(item) {
+            Ok(index) => Some(u32::from_le_bytes(index)),
+            Err(_) => {
+                log("leaf indices must be 4 bytes long");
+                None
+            }
+        })
+        .collect()
+}
+
+// Creates the multiproof of the leaves at `indices`, see merkle_multi_proof for the format
+fn multi_proof(leaves: &[Field<CurrentNetwork>], indices: &[u32]) -> Result<Vec<Vec<u8>>> {
+    let levels = tree_levels(leaves)?;
+    let depth = (levels.len() - 1) as u32;
+    ensure!(
+        indices.iter().all(|&index| (index as usize) < leaves.len()),
+        "leaf index out of range"
+    );
+    let leaf_hashes = indices
+        .iter()
+        .map(|&index| levels[0][index as usize])
+        .collect();
+    let nodes = indexed_leaves(leaf_hashes, indices, leaves.len() as u64)?;
+
+    let mut proof = vec![depth.to_le_bytes().to_vec()];
+    walk_to_root(nodes, depth, |level, index| {
+        let hash = levels[level as usize][index as usize];
+        proof.push(hash.to_string().into_bytes());
+        Ok(hash)
+    })?;
+
+    Ok(proof)
+}
+
+// Verifies a multiproof, see merkle_verify_multi
+fn verify_multi_proof(
+    root: Field<CurrentNetwork>,
+    leaves: &[Field<CurrentNetwork>],
+    indices: &[u32],
+    proof: &[&[u8]],
+) -> Result<bool> {
+    let Some((depth, siblings)) = proof.split_first() else {
+        return Ok(false);
+    };
+    let depth = u32::from_le_bytes((*depth).try_into()?);
+    ensure!(
+        depth <= MAX_TREE_DEPTH,
+        "tree depth exceeds {}",
+        MAX_TREE_DEPTH
+    );
+
+    let leaf_hashes = leaves
+        .iter()
+        .map(|leaf| hash_leaf(&leaf.to_bits_le()))
+        .collect::<Result<Vec<_>>>()?;
+    let nodes = indexed_leaves(leaf_hashes, indices, 1 << depth)?;
+
+    let mut siblings = siblings.iter();
+    let computed_root = walk_to_root(nodes, depth, |_, _| {
+        let Some(sibling) = siblings.next() else {
+            bail!("proof has too few hashes");
+        };
+        Field::from_str(str::from_utf8(sibling)?)
+    })?;
+
+    Ok(siblings.next().is_none() && computed_root == root)
+}
+
+// Variant of record outputs in snarkVM's transition leaves (`Output::to_transition_leaf`)
+const RECORD_LEAF_VARIANT: u8 = 3;
+
+/// Computes the leaf hash a record output has in the transition tree of the transition that
+/// created it, the first step of the `StatePath` proving the record's inclusion in a block's state
+/// root. snarkVM inserts outputs into the transition tree as `TransitionLeaf`s, the bits of the
+/// leaf version (1), the index of the output among the transition's inputs and outputs, the
+/// variant (3 for records) and the record commitment, and hashes leaves with BHP1024 (see
+/// `hash_leaf`). The rest of the path (transition, transaction and block trees) comes from a node.
+///
+/// - `commitment` - the record commitment field string
+/// - `index` - the position of the output in the transition: the number of inputs plus the index
+///   of the output, at most 255
+///
+/// Returns the leaf hash as a field string, or 0 if the commitment fails to parse or the index is
+/// out of range.
+#[no_mangle]
+pub extern "C" fn record_leaf_hash(
+    commitment: *const u8,
+    commitment_len: usize,
+    index: u32,
+) -> u64 {
+    let Some(commitment) =
+        parse_from_raw::<Field<CurrentNetwork>>(commitment, commitment_len, "commitment")
+    else {
+        return 0;
+    };
+    let Ok(index) = u8::try_from(index) else {
+        log("transition leaf index must be at most 255");
+        return 0;
+    };
+
+    let leaf =
+        TransitionLeaf::<CurrentNetwork>::new_with_version(index, RECORD_LEAF_VARIANT, commitment);
+    let Some(leaf_hash) = ok_or_log(hash_leaf(&leaf.to_bits_le()), "failed to compute leaf hash")
+    else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(leaf_hash.to_string().into_bytes())
+}
+
+/// Computes the root of a BHP Merkle tree over a framed list of leaf field strings, e.g. record
+/// commitments. Leaves are hashed with `hash_leaf` over their little-endian bits and inner nodes
+/// with BHP512 of a `true` bit followed by the bits of both children, as in snarkVM's BHP Merkle
+/// trees. The tree is as deep as the number of leaves requires, with the leaf level padded to a
+/// power of two with the hash of two zero children, so its root isn't comparable to the roots of
+/// snarkVM's fixed-depth trees. Returns the root as a field string.
+#[no_mangle]
+pub extern "C" fn merkle_root(leaves: *const u8, leaves_len: usize) -> u64 {
+    let leaves_buf = unsafe { slice::from_raw_parts(leaves, leaves_len) };
+    let Some(leaves) = parse_frames::<Field<CurrentNetwork>>(leaves_buf, "leaves") else {
+        return 0;
+    };
+
+    let Some(levels) = ok_or_log(tree_levels(&leaves), "failed to build merkle tree") else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(levels[levels.len() - 1][0].to_string().into_bytes())
+}
+
+/// Creates a multiproof for the leaves at `indices` of the tree over `leaves` (see `merkle_root`),
+/// proving all of them with one set of sibling hashes. `leaves` is a framed list of all leaf field
+/// strings of the tree and `indices` a framed list of 4-byte little-endian leaf indices.
+///
+/// The proof is a framed list whose first item is the depth of the tree as a 4-byte little-endian
+/// integer, followed by the field strings of the sibling hashes that can't be computed from the
+/// proven leaves, level by level from the leaves up and within a level from left to right.
+/// Siblings shared by several of the proven leaves are included once, and siblings that are proven
+/// leaves or computed from them are left out.
+#[no_mangle]
+pub extern "C" fn merkle_multi_proof(
+    leaves: *const u8,
+    leaves_len: usize,
+    indices: *const u8,
+    indices_len: usize,
+) -> u64 {
+    let leaves_buf = unsafe { slice::from_raw_parts(leaves, leaves_len) };
+    let Some(leaves) = parse_frames::<Field<CurrentNetwork>>(leaves_buf, "leaves") else {
+        return 0;
+    };
+    let indices_buf = unsafe { slice::from_raw_parts(indices, indices_len) };
+    let Some(indices) = parse_indices(indices_buf) else {
+        return 0;
+    };
+
+    let Some(proof) = ok_or_log(
+        multi_proof(&leaves, &indices),
+        "failed to create merkle multiproof",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(encode_frames(&proof))
+}
+
+/// Verifies a multiproof created with `merkle_multi_proof` against a root field string. `leaves`
+/// is a framed list of the proven leaf field strings and `indices` a framed list of their 4-byte
+/// little-endian indices, in the same order. Returns 1 if the proof uses every sibling hash and
+/// leads to the root, and 0 otherwise.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn merkle_verify_multi(
+    root: *const u8,
+    root_len: usize,
+    leaves: *const u8,
+    leaves_len: usize,
+    indices: *const u8,
+    indices_len: usize,
+    proof: *const u8,
+    proof_len: usize,
+) -> u64 {
+    let Some(root) = parse_from_raw::<Field<CurrentNetwork>>(root, root_len, "root") else {
+        return 0;
+    };
+    let leaves_buf = unsafe { slice::from_raw_parts(leaves, leaves_len) };
+    let Some(leaves) = parse_frames::<Field<CurrentNetwork>>(leaves_buf, "leaves") else {
+        return 0;
+    };
+    let indices_buf = unsafe { slice::from_raw_parts(indices, indices_len) };
+    let Some(indices) = parse_indices(indices_buf) else {
+        return 0;
+    };
+    let proof_buf = unsafe { slice::from_raw_parts(proof, proof_len) };
+    let Some(proof) = decode_frames(proof_buf) else {
+        log("malformed merkle multiproof");
+        return 0;
+    };
+
+    ok_or_log(
+        verify_multi_proof(root, &leaves, &indices, &proof),
+        "failed to verify merkle multiproof",
+    )
+    .unwrap_or(false) as u64
+}
+
+/// Returns the index of the first leaf equal to `target` in a framed list of leaf field strings,
+/// e.g. to find the index of a record commitment before calling `merkle_multi_proof`. Leaves are
+/// compared as fields, so differently formatted strings of the same field match. Returns
+/// `LEAF_NOT_FOUND` if the target isn't among the leaves, and also if the leaves or the target
+/// don't parse, which is told apart by the last error being set, as it's cleared at the start.
+#[no_mangle]
+pub extern "C" fn merkle_find_leaf(
+    leaves: *const u8,
+    leaves_len: usize,
+    target: *const u8,
+    target_len: usize,
+) -> u64 {
+    clear_last_error();
+
+    let leaves_buf = unsafe { slice::from_raw_parts(leaves, leaves_len) };
+    let Some(leaves) = parse_frames::<Field<CurrentNetwork>>(leaves_buf, "leaves") else {
+        return LEAF_NOT_FOUND;
+    };
+    let Some(target) = parse_from_raw::<Field<CurrentNetwork>>(target, target_len, "target") else {
+        return LEAF_NOT_FOUND;
+    };
+
+    leaves
+        .iter()
+        .position(|leaf| *leaf == target)
+        .map_or(LEAF_NOT_FOUND, |index| index as u64)
+}
+
+/// Computes the state root of a chain from the hashes of its blocks, the way nodes do: the root
+/// of snarkVM's block tree, a depth 32 BHP Merkle tree (`Network::merkle_tree_bhp`) whose leaves
+/// are the little-endian bits of the block hashes in height order, starting with the genesis
+/// block. Leaves are hashed with BHP1024 (see `hash_leaf`), inner nodes with BHP512, and missing
+/// leaves and subtrees take snarkVM's empty hash.
+///
+/// - `block_hashes` - a framed list of `ab1...` block hash strings, from genesis to the block the
+///   root is wanted for
+///
+/// Returns the root as the `sr1...` string nodes report, or 0 if a block hash fails to parse or
+/// there are more than 2^32 blocks.
+#[no_mangle]
+pub extern "C" fn compute_state_root(block_hashes: *const u8, block_hashes_len: usize) -> u64 {
+    let block_hashes_buf = unsafe { slice::from_raw_parts(block_hashes, block_hashes_len) };
+    let Some(block_hashes) =
+        parse_frames::<<CurrentNetwork as Network>::BlockHash>(block_hashes_buf, "block hashes")
+    else {
+        return 0;
+    };
+
+    let leaves = block_hashes
+        .iter()
+        .map(|block_hash| (**block_hash).to_bits_le())
+        .collect::<Vec<_>>();
+    let Some(tree) = ok_or_log(
+        CurrentNetwork::merkle_tree_bhp::<BLOCK_TREE_DEPTH>(&leaves),
+        "failed to build block tree",
+    ) else {
+        return 0;
+    };
+
+    let state_root = <CurrentNetwork as Network>::StateRoot::from(*tree.root());
+    forget_buf_ptr_len(state_root.to_string().into_bytes())
+}
+
+// Parses a state root either as a field string or as the bech32m `sr1` string nodes report
+// (`Network::StateRoot`), returning the field it encodes
+fn parse_state_root(root: &str) -> Result<Field<CurrentNetwork>> {
+    if root.starts_with("sr1") {
+        return Ok(*<CurrentNetwork as Network>::StateRoot::from_str(root)?);
+    }
+
+    Field::from_str(root)
+}
+
+/// Compares two state roots by the field they encode rather than by their string, so roots that
+/// are formatted differently (leading zeros, `_` digit separators, a field string against the
+/// `sr1` string a node reports) still match. Light clients can use this to detect a reorg when the
+/// root they stored, e.g. from `compute_state_root`, differs from the node's.
+///
+/// Returns 1 if the roots are equal and 0 otherwise. The last error is cleared on entry and is
+/// only set when a root fails to parse, so a 0 with an empty last error means the roots differ.
+#[no_mangle]
+pub extern "C" fn state_roots_equal(a: *const u8, a_len: usize, b: *const u8, b_len: usize) -> u64 {
+    clear_last_error();
+
+    let Some(a) = str_from_raw(a, a_len, "first state root") else {
+        return 0;
+    };
+    let Some(a) = ok_or_log(parse_state_root(a), "failed to parse first state root") else {
+        return 0;
+    };
+    let Some(b) = str_from_raw(b, b_len, "second state root") else {
+        return 0;
+    };
+    let Some(b) = ok_or_log(parse_state_root(b), "failed to parse second state root") else {
+        return 0;
+    };
+
+    (a == b) as u64
+}
+
+/// Starts an incremental Merkle tree of a fixed `depth` between 1 and 32, for append-only logs
+/// such as a growing set of record commitments. Leaves and inner nodes are hashed like in
+/// `merkle_root`, and leaves that haven't been appended yet are padding leaves, so once more than
+/// half of the 2^depth leaves are appended the root equals `merkle_root` over the same leaves.
+///
+/// Returns an opaque handle, or 0 on an invalid depth. The handle must be released with
+/// `incr_merkle_free` exactly once and must not be used after that.
+#[no_mangle]
+pub extern "C" fn incr_merkle_new(depth: u32) -> u64 {
+    let Some(tree) = ok_or_log(
+        IncrementalTree::new(depth),
+        "failed to create incremental merkle tree",
+    ) else {
+        return 0;
+    };
+
+    Box::into_raw(Box::new(tree)) as usize as u64
+}
+
+/// Appends a leaf field string to an incremental Merkle tree, hashing only the path from the new
+/// leaf to the root instead of rebuilding the tree. Returns the new root as a field string, or 0 if
+/// the leaf fails to parse or the tree is full.
+#[no_mangle]
+pub extern "C" fn incr_merkle_append(handle: u64, leaf: *const u8, leaf_len: usize) -> u64 {
+    if handle == 0 {
+        log("invalid incremental merkle tree handle");
+        return 0;
+    }
+
+    let tree = unsafe { &mut *(handle as usize as *mut IncrementalTree) };
+
+    let Some(leaf) = parse_from_raw::<Field<CurrentNetwork>>(leaf, leaf_len, "leaf") else {
+        return 0;
+    };
+    let Some(root) = ok_or_log(tree.append(&leaf), "failed to append leaf") else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(root.to_string().into_bytes())
+}
+
+/// Releases an incremental Merkle tree. Freeing a 0 handle is a no-op.
+#[no_mangle]
+pub extern "C" fn incr_merkle_free(handle: u64) {
+    if handle == 0 {
+        return;
+    }
+
+    drop(unsafe { Box::from_raw(handle as usize as *mut IncrementalTree) });
+}
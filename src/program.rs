@@ -0,0 +1,113 @@
+use alloc::{string::ToString, vec::Vec};
+
+use snarkvm_console::{
+    prelude::{Network, ToBits, ToBytes},
+    program::Identifier,
+};
+use snarkvm_synthesizer_program::Program;
+
+use crate::{
+    log::{log, ok_or_log},
+    memory::{encode_frames, forget_buf_ptr_len, parse_from_raw},
+    network::CurrentNetwork,
+};
+
+// Message logged by program_function_signature if the program has no function of the requested
+// name, the host matches on it
+const FUNCTION_NOT_FOUND_ERROR: &str = "program has no function with that name";
+
+/// Parses an Aleo program from its source and returns a local fingerprint of it: BHP1024 over the
+/// little-endian bits of the program's canonical byte encoding. The fingerprint is this library's
+/// own scheme with no on-chain equivalent, it isn't a program ID, a deployment ID or any hash a
+/// node reports, so it's only comparable with other fingerprints from this function. Whitespace
+/// and comments of the source don't change it, while any change to the program's ID, imports or
+/// definitions does, so the host can tell two sources apart by their program rather than their
+/// text. Returns the fingerprint as a field string, or 0 with the parse error in the last error.
+#[no_mangle]
+pub extern "C" fn program_fingerprint(program_source: *const u8, program_source_len: usize) -> u64 {
+    let Some(program) =
+        parse_from_raw::<Program<CurrentNetwork>>(program_source, program_source_len, "program")
+    else {
+        return 0;
+    };
+
+    let Some(bytes) = ok_or_log(program.to_bytes_le(), "failed to serialize program") else {
+        return 0;
+    };
+    let Some(hash) = ok_or_log(
+        CurrentNetwork::hash_bhp1024(&bytes.to_bits_le()),
+        "failed to hash program",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(hash.to_string().into_bytes())
+}
+
+/// Parses an Aleo program from its source and returns the names of the functions it exports, the
+/// ones callable in a transition, framed, in the order the program declares them. Closures aren't
+/// callable from outside the program and are left out. Returns 0 with the parse error in the last
+/// error.
+#[no_mangle]
+pub extern "C" fn program_functions(program_source: *const u8, program_source_len: usize) -> u64 {
+    let Some(program) =
+        parse_from_raw::<Program<CurrentNetwork>>(program_source, program_source_len, "program")
+    else {
+        return 0;
+    };
+
+    let names: Vec<_> = program
+        .functions()
+        .keys()
+        .map(|name| name.to_string())
+        .collect();
+
+    forget_buf_ptr_len(encode_frames(&names))
+}
+
+/// Parses an Aleo program from its source and returns the signature of its function
+/// `function_name`: two frames, the first holding the framed input types and the second the framed
+/// output types, in declaration order and with their visibility, e.g. `u64.public` or
+/// `token.record`. Returns 0 with the last error set to `FUNCTION_NOT_FOUND_ERROR` if the program
+/// has no such function, or to the parse error for a malformed program or name.
+#[no_mangle]
+pub extern "C" fn program_function_signature(
+    program_source: *const u8,
+    program_source_len: usize,
+    function_name: *const u8,
+    function_name_len: usize,
+) -> u64 {
+    let Some(program) =
+        parse_from_raw::<Program<CurrentNetwork>>(program_source, program_source_len, "program")
+    else {
+        return 0;
+    };
+    let Some(function_name) = parse_from_raw::<Identifier<CurrentNetwork>>(
+        function_name,
+        function_name_len,
+        "function name",
+    ) else {
+        return 0;
+    };
+
+    let Some(function) = program.functions().get(&function_name) else {
+        log(FUNCTION_NOT_FOUND_ERROR);
+        return 0;
+    };
+
+    let inputs: Vec<_> = function
+        .inputs()
+        .iter()
+        .map(|input| input.value_type().to_string())
+        .collect();
+    let outputs: Vec<_> = function
+        .outputs()
+        .iter()
+        .map(|output| output.value_type().to_string())
+        .collect();
+
+    forget_buf_ptr_len(encode_frames(&[
+        encode_frames(&inputs),
+        encode_frames(&outputs),
+    ]))
+}
@@ -0,0 +1,802 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use core::slice;
+
+use snarkvm_console::{
+    account::{Address, GraphKey, PrivateKey, ViewKey},
+    prelude::{anyhow, bail, ensure, FromStr, Network, Parser, Result, ToBits, Zero},
+    program::{
+        Ciphertext, Entry, EntryType, Identifier, Literal, Plaintext, ProgramID, PublicOrPrivate,
+        Record, RecordType,
+    },
+    types::{Field, Scalar},
+};
+
+use crate::{
+    format::check_plaintext_type,
+    log::{clear_last_error, log, ok_or_log},
+    memory::{encode_frames, forget_buf_ptr_len, parse_frames, parse_from_raw, str_from_raw},
+    network::CurrentNetwork,
+};
+
+pub(crate) type PlaintextRecord = Record<CurrentNetwork, Plaintext<CurrentNetwork>>;
+pub(crate) type CiphertextRecord = Record<CurrentNetwork, Ciphertext<CurrentNetwork>>;
+
+// Cached state of a scan session, the handle returned to the host is a pointer to it
+struct ScanSession {
+    sk_tag: Field<CurrentNetwork>,
+}
+
+// Derives the tag secret of a view key
+fn tag_secret(view_key: ViewKey<CurrentNetwork>) -> Result<Field<CurrentNetwork>> {
+    Ok(GraphKey::try_from(view_key)?.sk_tag())
+}
+
+// Computes the tag of a record commitment, Poseidon2(sk_tag, commitment)
+fn record_tag(
+    sk_tag: Field<CurrentNetwork>,
+    commitment: Field<CurrentNetwork>,
+) -> Result<Field<CurrentNetwork>> {
+    CurrentNetwork::hash_psd2(&[sk_tag, commitment])
+}
+
+// Parses a commitment string and returns its tag as a field string, or 0 on failure
+fn tag_output(sk_tag: Field<CurrentNetwork>, commitment: *const u8, commitment_len: usize) -> u64 {
+    let Some(commitment) =
+        parse_from_raw::<Field<CurrentNetwork>>(commitment, commitment_len, "commitment")
+    else {
+        return 0;
+    };
+
+    let Some(tag) = ok_or_log(
+        record_tag(sk_tag, commitment),
+        "failed to compute record tag",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(tag.to_string().into_bytes())
+}
+
+// Returns the amount of a credits record, the u64 `microcredits` entry of any visibility, or None
+// if the record has no `microcredits` entry
+pub(crate) fn record_microcredits(record: &PlaintextRecord) -> Result<Option<u64>> {
+    let Some(entry) = record.data().get(&Identifier::from_str("microcredits")?) else {
+        return Ok(None);
+    };
+    let (Entry::Constant(plaintext) | Entry::Public(plaintext) | Entry::Private(plaintext)) = entry;
+
+    match plaintext {
+        Plaintext::Literal(Literal::U64(amount), _) => Ok(Some(**amount)),
+        _ => bail!("microcredits entry isn't a u64"),
+    }
+}
+
+// Checks that a plaintext record conforms to a record type: the owner has the declared visibility,
+// and the record has exactly the declared entries, each with the declared visibility and type.
+// Records don't carry the name of their type, so the name isn't checked.
+fn check_record_type(
+    record: &PlaintextRecord,
+    record_type: &RecordType<CurrentNetwork>,
+) -> Result<()> {
+    let owner_is_public = matches!(record_type.owner(), PublicOrPrivate::Public);
+    ensure!(
+        record.owner().is_public() == owner_is_public,
+        "the record owner should be {}",
+        if owner_is_public { "public" } else { "private" }
+    );
+
+    for (name, entry_type) in record_type.entries() {
+        let Some(entry) = record.data().get(name) else {
+            bail!("the record is missing the entry `{name}`");
+        };
+        match (entry, entry_type) {
+            (Entry::Constant(plaintext), EntryType::Constant(plaintext_type))
+            | (Entry::Public(plaintext), EntryType::Public(plaintext_type))
+            | (Entry::Private(plaintext), EntryType::Private(plaintext_type)) => {
+                check_plaintext_type(plaintext, plaintext_type)
+                    .map_err(|e| anyhow!("entry `{name}`: {e}"))?
+            }
+            _ => bail!("entry `{name}` should be {entry_type}"),
+        }
+    }
+    if let Some(name) = record
+        .data()
+        .keys()
+        .find(|name| !record_type.entries().contains_key(*name))
+    {
+        bail!("the record has the unexpected entry `{name}`");
+    }
+
+    Ok(())
+}
+
+// Message logged by record_owner for records without an owner entry, the host matches on it
+const NO_OWNER_ERROR: &str = "record has no owner field";
+
+// Parses the top-level entries of a record string with snarkVM's identifier and entry parsers and
+// returns their names, without requiring the owner and `_nonce` entries a record starts and ends
+// with. Entries of nested structs aren't top-level, so a struct member named `owner` isn't listed.
+fn top_level_entry_names(record: &str) -> Result<Vec<String>> {
+    let Some(mut rest) = record.trim_start().strip_prefix('{') else {
+        bail!("a record starts with `{{`");
+    };
+
+    let mut names = Vec::new();
+    loop {
+        // Reserved entries like `_nonce` are an underscore followed by an identifier
+        let (unprefixed, underscore) = match rest.trim_start().strip_prefix('_') {
+            Some(unprefixed) => (unprefixed, "_"),
+            None => (rest.trim_start(), ""),
+        };
+        let (after_name, name) = Identifier::<CurrentNetwork>::parse(unprefixed)
+            .map_err(|e| anyhow!("failed to parse entry name: {e}"))?;
+        let Some(after_colon) = after_name.trim_start().strip_prefix(':') else {
+            bail!("entry `{underscore}{name}` should be followed by `:`");
+        };
+        let (after_entry, _) =
+            Entry::<CurrentNetwork, Plaintext<CurrentNetwork>>::parse(after_colon.trim_start())
+                .map_err(|e| anyhow!("failed to parse entry `{underscore}{name}`: {e}"))?;
+
+        let mut name_str = String::from(underscore);
+        name_str.push_str(&name.to_string());
+        names.push(name_str);
+
+        rest = after_entry.trim_start();
+        if let Some(after_comma) = rest.strip_prefix(',') {
+            rest = after_comma;
+        } else if rest
+            .strip_prefix('}')
+            .is_some_and(|end| end.trim().is_empty())
+        {
+            return Ok(names);
+        } else {
+            bail!("entries should be separated by `,` and closed by `}}`");
+        }
+    }
+}
+
+// Computes the checksum snarkVM stores with every output record ciphertext, BHP1024 over the bits
+// of the ciphertext
+pub(crate) fn ciphertext_checksum(ciphertext: &CiphertextRecord) -> Result<Field<CurrentNetwork>> {
+    CurrentNetwork::hash_bhp1024(&ciphertext.to_bits_le())
+}
+
+// Parses a plaintext record with the program ID and record name it was defined with and computes
+// its commitment, logging any failure
+fn commitment_from_raw(
+    record: *const u8,
+    record_len: usize,
+    program_id: *const u8,
+    program_id_len: usize,
+    record_name: *const u8,
+    record_name_len: usize,
+) -> Option<Field<CurrentNetwork>> {
+    let record = parse_from_raw::<PlaintextRecord>(record, record_len, "record")?;
+    let program_id =
+        parse_from_raw::<ProgramID<CurrentNetwork>>(program_id, program_id_len, "program ID")?;
+    let record_name =
+        parse_from_raw::<Identifier<CurrentNetwork>>(record_name, record_name_len, "record name")?;
+
+    ok_or_log(
+        record.to_commitment(&program_id, &record_name),
+        "failed to compute record commitment",
+    )
+}
+
+/// Computes the commitment of a plaintext record the same way snarkVM does for transition outputs.
+///
+/// - `record` - the plaintext record, including its `_nonce`, e.g.
+///   `{ owner: aleo1....private, microcredits: 100u64.private, _nonce: 0group.public }`
+/// - `program_id` - the program that defines the record, e.g. `credits.aleo`
+/// - `record_name` - the name of the record type in that program, e.g. `credits`
+///
+/// Returns the commitment as a field string, or 0 if any input fails to parse.
+#[no_mangle]
+pub extern "C" fn compute_record_commitment(
+    record: *const u8,
+    record_len: usize,
+    program_id: *const u8,
+    program_id_len: usize,
+    record_name: *const u8,
+    record_name_len: usize,
+) -> u64 {
+    let Some(commitment) = commitment_from_raw(
+        record,
+        record_len,
+        program_id,
+        program_id_len,
+        record_name,
+        record_name_len,
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(commitment.to_string().into_bytes())
+}
+
+/// Checks that a decrypted record matches a commitment, by recomputing the commitment of the
+/// record (see compute_record_commitment) and comparing it to the given one. A mismatch means the
+/// record was decrypted with the wrong view key, or that the record or commitment were tampered
+/// with.
+///
+/// Returns 1 if the commitments match and 0 otherwise. The last error is cleared on entry and is
+/// only set when an input fails to parse, so a 0 with an empty last error is a mismatch.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn record_matches_commitment(
+    record: *const u8,
+    record_len: usize,
+    commitment: *const u8,
+    commitment_len: usize,
+    program_id: *const u8,
+    program_id_len: usize,
+    record_name: *const u8,
+    record_name_len: usize,
+) -> u64 {
+    clear_last_error();
+
+    let Some(expected) =
+        parse_from_raw::<Field<CurrentNetwork>>(commitment, commitment_len, "commitment")
+    else {
+        return 0;
+    };
+    let Some(computed) = commitment_from_raw(
+        record,
+        record_len,
+        program_id,
+        program_id_len,
+        record_name,
+        record_name_len,
+    ) else {
+        return 0;
+    };
+
+    (computed == expected) as u64
+}
+
+/// Computes the tag of a record commitment for a view key, Poseidon2(sk_tag, commitment), where
+/// sk_tag is the tag secret of the view key's graph key.
+///
+/// Every call parses the view key and derives its tag secret again, use a scan session when
+/// computing tags for many commitments.
+#[no_mangle]
+pub extern "C" fn compute_record_tag(
+    view_key: *const u8,
+    view_key_len: usize,
+    commitment: *const u8,
+    commitment_len: usize,
+) -> u64 {
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+    let Some(sk_tag) = ok_or_log(tag_secret(view_key), "failed to derive tag secret") else {
+        return 0;
+    };
+
+    tag_output(sk_tag, commitment, commitment_len)
+}
+
+/// Computes the serial number of a record commitment for the private key of the record owner, the
+/// value a transition spending the record reveals. See `Record::serial_number` in snarkVM for the
+/// derivation.
+#[no_mangle]
+pub extern "C" fn compute_serial_number(
+    private_key: *const u8,
+    private_key_len: usize,
+    commitment: *const u8,
+    commitment_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(commitment) =
+        parse_from_raw::<Field<CurrentNetwork>>(commitment, commitment_len, "commitment")
+    else {
+        return 0;
+    };
+
+    let Some(serial_number) = ok_or_log(
+        PlaintextRecord::serial_number(private_key, commitment),
+        "failed to compute serial number",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(serial_number.to_string().into_bytes())
+}
+
+/// Computes both the serial number and the tag of a record commitment for the private key of the
+/// record owner, parsing the key and deriving its view key and tag secret once instead of once per
+/// value as compute_serial_number and compute_record_tag do. Returns a framed list of the serial
+/// number and the tag as field strings, or 0 on failure.
+#[no_mangle]
+pub extern "C" fn compute_serial_and_tag(
+    private_key: *const u8,
+    private_key_len: usize,
+    commitment: *const u8,
+    commitment_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(commitment) =
+        parse_from_raw::<Field<CurrentNetwork>>(commitment, commitment_len, "commitment")
+    else {
+        return 0;
+    };
+
+    let Some(view_key) = ok_or_log(
+        ViewKey::try_from(private_key),
+        "failed to convert a private key to view key",
+    ) else {
+        return 0;
+    };
+    let Some(sk_tag) = ok_or_log(tag_secret(view_key), "failed to derive tag secret") else {
+        return 0;
+    };
+
+    let Some(serial_number) = ok_or_log(
+        PlaintextRecord::serial_number(private_key, commitment),
+        "failed to compute serial number",
+    ) else {
+        return 0;
+    };
+    let Some(tag) = ok_or_log(
+        record_tag(sk_tag, commitment),
+        "failed to compute record tag",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(encode_frames(&[serial_number.to_string(), tag.to_string()]))
+}
+
+/// Starts a scan session for a view key. The view key is parsed and its tag secret derived once,
+/// so scan_session_tag only has to hash the commitment.
+///
+/// Returns an opaque session handle, or 0 on failure. The handle must be released with
+/// scan_session_close exactly once and must not be used after that.
+#[no_mangle]
+pub extern "C" fn scan_session_new(view_key: *const u8, view_key_len: usize) -> u64 {
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+    let Some(sk_tag) = ok_or_log(tag_secret(view_key), "failed to derive tag secret") else {
+        return 0;
+    };
+
+    Box::into_raw(Box::new(ScanSession { sk_tag })) as usize as u64
+}
+
+/// Computes the tag of a record commitment with the tag secret cached by a scan session. Returns
+/// the tag as a field string, or 0 on failure.
+#[no_mangle]
+pub extern "C" fn scan_session_tag(
+    handle: u64,
+    commitment: *const u8,
+    commitment_len: usize,
+) -> u64 {
+    if handle == 0 {
+        log(String::from("invalid scan session handle"));
+        return 0;
+    }
+
+    let session = unsafe { &*(handle as usize as *const ScanSession) };
+
+    tag_output(session.sk_tag, commitment, commitment_len)
+}
+
+/// Releases a scan session. Closing a 0 handle is a no-op.
+#[no_mangle]
+pub extern "C" fn scan_session_close(handle: u64) {
+    if handle == 0 {
+        return;
+    }
+
+    drop(unsafe { Box::from_raw(handle as usize as *mut ScanSession) });
+}
+
+/// Decrypts a record ciphertext with the view key of its owner and returns the plaintext record
+/// string, or 0 if an input fails to parse or the record isn't owned by the view key.
+#[no_mangle]
+pub extern "C" fn decrypt_record(
+    ciphertext: *const u8,
+    ciphertext_len: usize,
+    view_key: *const u8,
+    view_key_len: usize,
+) -> u64 {
+    let Some(ciphertext) =
+        parse_from_raw::<CiphertextRecord>(ciphertext, ciphertext_len, "ciphertext")
+    else {
+        return 0;
+    };
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+
+    let Some(record) = ok_or_log(ciphertext.decrypt(&view_key), "failed to decrypt record") else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(record.to_string().into_bytes())
+}
+
+// Message logged by ciphertexts_equal_plaintext and decrypt_and_verify_record if a ciphertext
+// doesn't decrypt with the view key, the host matches on it
+const DECRYPT_ERROR: &str = "record ciphertext doesn't decrypt with the view key";
+// Message logged by decrypt_and_verify_record if the decrypted record doesn't match the
+// commitment, the host matches on it
+const COMMITMENT_MISMATCH_ERROR: &str = "decrypted record doesn't match the commitment";
+
+/// Decrypts two record ciphertexts with one view key and compares the plaintext records, ignoring
+/// their nonces, e.g. to detect the same record presented twice under different nonces. Records
+/// are equal if they have the same owner and the same entries with the same visibilities.
+///
+/// Returns 1 if the plaintexts are equal and 0 otherwise. As the last error is cleared at the
+/// start, a 0 with the last error set means an input failed to parse, or, with the message
+/// `DECRYPT_ERROR`, that a ciphertext isn't owned by the view key.
+#[no_mangle]
+pub extern "C" fn ciphertexts_equal_plaintext(
+    view_key: *const u8,
+    view_key_len: usize,
+    ciphertext_a: *const u8,
+    ciphertext_a_len: usize,
+    ciphertext_b: *const u8,
+    ciphertext_b_len: usize,
+) -> u64 {
+    clear_last_error();
+
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+
+    let mut records = Vec::with_capacity(2);
+    for (ciphertext, ciphertext_len) in [
+        (ciphertext_a, ciphertext_a_len),
+        (ciphertext_b, ciphertext_b_len),
+    ] {
+        let Some(ciphertext) =
+            parse_from_raw::<CiphertextRecord>(ciphertext, ciphertext_len, "ciphertext")
+        else {
+            return 0;
+        };
+        let Ok(record) = ciphertext.decrypt(&view_key) else {
+            log(DECRYPT_ERROR);
+            return 0;
+        };
+        records.push(record);
+    }
+
+    (records[0].owner() == records[1].owner() && records[0].data() == records[1].data()) as u64
+}
+
+/// Checks whether a record ciphertext is owned by a view key without decrypting its entries: only
+/// the owner entry is compared against the view key's address, the way snarkVM's
+/// `Record::is_owner` does.
+///
+/// Returns 1 if the view key owns the record and 0 otherwise. The last error is cleared on entry
+/// and is only set when an input fails to parse, so a 0 with an empty last error means the record
+/// belongs to someone else.
+#[no_mangle]
+pub extern "C" fn record_is_owner(
+    view_key: *const u8,
+    view_key_len: usize,
+    ciphertext: *const u8,
+    ciphertext_len: usize,
+) -> u64 {
+    clear_last_error();
+
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+    let Some(ciphertext) =
+        parse_from_raw::<CiphertextRecord>(ciphertext, ciphertext_len, "ciphertext")
+    else {
+        return 0;
+    };
+
+    ciphertext.is_owner(&view_key) as u64
+}
+
+/// Checks ownership of a framed list of record ciphertexts like `record_is_owner`, the inner loop
+/// of a wallet sync. The view key is parsed and its address derived once for the whole batch, and
+/// non-owned records are never decrypted.
+///
+/// Returns a buffer with one byte per ciphertext, 1 if the view key owns it and 0 otherwise, in
+/// input order, or 0 if the view key or any ciphertext fails to parse.
+#[no_mangle]
+pub extern "C" fn records_ownership_batch(
+    view_key: *const u8,
+    view_key_len: usize,
+    ciphertexts: *const u8,
+    ciphertexts_len: usize,
+) -> u64 {
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+    let ciphertexts_buf = unsafe { slice::from_raw_parts(ciphertexts, ciphertexts_len) };
+    let Some(ciphertexts) = parse_frames::<CiphertextRecord>(ciphertexts_buf, "ciphertexts") else {
+        return 0;
+    };
+
+    let address_x = view_key.to_address().to_x_coordinate();
+    let owned = ciphertexts
+        .iter()
+        .map(|ciphertext| {
+            ciphertext.is_owner_with_address_x_coordinate(&view_key, &address_x) as u8
+        })
+        .collect();
+
+    forget_buf_ptr_len(owned)
+}
+
+/// Returns the checksum the chain stores next to a record ciphertext in a transition output, which
+/// binds the output commitment to the ciphertext: BHP1024 over the little-endian bits of the
+/// ciphertext. The commitment itself is computed over the plaintext, so it can't be derived from
+/// the ciphertext without decrypting it, but the checksum lets indexers match observed ciphertexts
+/// to stored outputs.
+///
+/// - `ciphertext` - the record ciphertext, `record1...`
+///
+/// Returns the checksum as a field string, or 0 if the ciphertext fails to parse.
+#[no_mangle]
+pub extern "C" fn ciphertext_commitment(ciphertext: *const u8, ciphertext_len: usize) -> u64 {
+    let Some(ciphertext) =
+        parse_from_raw::<CiphertextRecord>(ciphertext, ciphertext_len, "record ciphertext")
+    else {
+        return 0;
+    };
+
+    let Some(checksum) = ok_or_log(
+        ciphertext_checksum(&ciphertext),
+        "failed to compute ciphertext checksum",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(checksum.to_string().into_bytes())
+}
+
+/// Returns the owner address of a plaintext record, whether the owner entry is public or private.
+///
+/// Returns 0 if the record doesn't parse. The last error is `record has no owner field` if the
+/// input's top-level entries parse but none of them is `owner`, and the parse error otherwise.
+#[no_mangle]
+pub extern "C" fn record_owner(record: *const u8, record_len: usize) -> u64 {
+    let Some(record_str) = str_from_raw(record, record_len, "record") else {
+        return 0;
+    };
+
+    let record = match PlaintextRecord::from_str(record_str) {
+        Ok(record) => record,
+        Err(e) => {
+            // A record that fails to parse lacks an owner if its entries parse and none of them
+            // is named `owner`
+            let no_owner = top_level_entry_names(record_str)
+                .is_ok_and(|names| !names.iter().any(|name| name == "owner"));
+            if no_owner {
+                log(NO_OWNER_ERROR);
+            } else {
+                ok_or_log(Err::<(), _>(e), "failed to parse record from string");
+            }
+
+            return 0;
+        }
+    };
+
+    let owner: Address<CurrentNetwork> = **record.owner();
+    forget_buf_ptr_len(owner.to_string().into_bytes())
+}
+
+/// Sums the balances of a framed list of decrypted plaintext credits records, e.g. the records a
+/// wallet owns, and returns the total microcredits as a decimal string. Records without a
+/// `microcredits` entry, like the records of other programs, are skipped and count as zero. Returns
+/// 0 if a record fails to parse, has a `microcredits` entry that isn't a u64, or the total
+/// overflows a u64.
+#[no_mangle]
+pub extern "C" fn sum_record_balances(records: *const u8, records_len: usize) -> u64 {
+    let records_buf = unsafe { slice::from_raw_parts(records, records_len) };
+    let Some(records) = parse_frames::<PlaintextRecord>(records_buf, "records") else {
+        return 0;
+    };
+
+    let mut total: u64 = 0;
+    for record in &records {
+        let Some(amount) = ok_or_log(
+            record_microcredits(record),
+            "failed to read record microcredits",
+        ) else {
+            return 0;
+        };
+        let Some(sum) = total.checked_add(amount.unwrap_or_default()) else {
+            log("record balances overflow a u64");
+            return 0;
+        };
+        total = sum;
+    }
+
+    forget_buf_ptr_len(total.to_string().into_bytes())
+}
+
+/// Checks that a decrypted plaintext record conforms to a record type definition of a program,
+/// e.g. `record token: owner as address.private; amount as u64.private;`, before it's used as a
+/// transition input. The owner must have the declared visibility and the record exactly the
+/// declared entries, each with the declared visibility and type (see `check_plaintext_type`).
+/// Records don't carry the name of their type, so the name of the definition isn't checked.
+/// Returns 1 if the record conforms, and 0 with a last error describing the mismatch otherwise.
+#[no_mangle]
+pub extern "C" fn validate_record(
+    record: *const u8,
+    record_len: usize,
+    record_type: *const u8,
+    record_type_len: usize,
+) -> u64 {
+    let Some(record) = parse_from_raw::<PlaintextRecord>(record, record_len, "record") else {
+        return 0;
+    };
+    let Some(record_type) =
+        parse_from_raw::<RecordType<CurrentNetwork>>(record_type, record_type_len, "record type")
+    else {
+        return 0;
+    };
+
+    ok_or_log(
+        check_record_type(&record, &record_type),
+        "record doesn't match the record type",
+    )
+    .is_some() as u64
+}
+
+/// Decrypts a record ciphertext with the view key of its owner and only returns the plaintext
+/// record if its recomputed commitment (see `compute_record_commitment`) matches the given one,
+/// so a ciphertext swapped or tampered with on the way from the node is never used.
+///
+/// - `commitment` - the commitment the chain stores for the record, a field string
+/// - `program_id` and `record_name` - the program and record type the record was created with,
+///   e.g. `credits.aleo` and `credits`, which the commitment is computed over
+///
+/// Returns the plaintext record string, or 0 with the last error set to `DECRYPT_ERROR` if the
+/// ciphertext doesn't decrypt with the view key, to `COMMITMENT_MISMATCH_ERROR` if the commitment
+/// doesn't match, or to a parse error.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn decrypt_and_verify_record(
+    view_key: *const u8,
+    view_key_len: usize,
+    ciphertext: *const u8,
+    ciphertext_len: usize,
+    commitment: *const u8,
+    commitment_len: usize,
+    program_id: *const u8,
+    program_id_len: usize,
+    record_name: *const u8,
+    record_name_len: usize,
+) -> u64 {
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+    let Some(ciphertext) =
+        parse_from_raw::<CiphertextRecord>(ciphertext, ciphertext_len, "ciphertext")
+    else {
+        return 0;
+    };
+    let Some(expected) =
+        parse_from_raw::<Field<CurrentNetwork>>(commitment, commitment_len, "commitment")
+    else {
+        return 0;
+    };
+    let Some(program_id) =
+        parse_from_raw::<ProgramID<CurrentNetwork>>(program_id, program_id_len, "program ID")
+    else {
+        return 0;
+    };
+    let Some(record_name) =
+        parse_from_raw::<Identifier<CurrentNetwork>>(record_name, record_name_len, "record name")
+    else {
+        return 0;
+    };
+
+    let Ok(record) = ciphertext.decrypt(&view_key) else {
+        log(DECRYPT_ERROR);
+        return 0;
+    };
+    let Some(computed) = ok_or_log(
+        record.to_commitment(&program_id, &record_name),
+        "failed to compute record commitment",
+    ) else {
+        return 0;
+    };
+    if computed != expected {
+        log(COMMITMENT_MISMATCH_ERROR);
+        return 0;
+    }
+
+    forget_buf_ptr_len(record.to_string().into_bytes())
+}
+
+// Moves the nonce of a record the view key owns from G * r to G * (r + delta) and encrypts its
+// entries again under the record view key of the new nonce
+fn rerandomize(
+    ciphertext: &CiphertextRecord,
+    view_key: &ViewKey<CurrentNetwork>,
+    delta: &Scalar<CurrentNetwork>,
+) -> Result<CiphertextRecord> {
+    ensure!(!delta.is_zero(), "the delta is zero");
+
+    let record = ciphertext.decrypt(view_key)?;
+    let nonce = *record.nonce() + CurrentNetwork::g_scalar_multiply(delta);
+    let record =
+        PlaintextRecord::from_plaintext(record.owner().clone(), record.data().clone(), nonce)?;
+
+    let record_view_key = (nonce * **view_key).to_x_coordinate();
+    record.encrypt_symmetric_unchecked(&record_view_key)
+}
+
+/// Re-randomizes the nonce of a record ciphertext, so the same record can be handed on without the
+/// ciphertext or nonce linking it to the original. The nonce G * r of the ciphertext becomes
+/// nonce' = nonce + G * delta = G * (r + delta), and every entry, the owner included, is encrypted
+/// again under the record view key (nonce' * view_key).x, the key snarkVM decrypts a record with.
+///
+/// Invariant: as view_key * G is the owner's address, (nonce' * view_key).x equals
+/// ((r + delta) * address).x, so the owner's view key decrypts the result to the same owner and
+/// entries as the input, only the `_nonce` differs. The view key is required because the entries
+/// are masked with randomizers derived from the record view key, which changes with the nonce, so
+/// they can only be re-encrypted after decrypting them with the old one.
+///
+/// - `delta` - a non-zero scalar string, e.g. sampled at random by the host
+///
+/// Returns the re-randomized ciphertext string, or 0 if an input fails to parse, the view key
+/// doesn't own the record or the delta is zero.
+#[no_mangle]
+pub extern "C" fn rerandomize_ciphertext(
+    ciphertext: *const u8,
+    ciphertext_len: usize,
+    view_key: *const u8,
+    view_key_len: usize,
+    delta: *const u8,
+    delta_len: usize,
+) -> u64 {
+    let Some(ciphertext) =
+        parse_from_raw::<CiphertextRecord>(ciphertext, ciphertext_len, "ciphertext")
+    else {
+        return 0;
+    };
+    let Some(view_key) =
+        parse_from_raw::<ViewKey<CurrentNetwork>>(view_key, view_key_len, "view key")
+    else {
+        return 0;
+    };
+    let Some(delta) = parse_from_raw::<Scalar<CurrentNetwork>>(delta, delta_len, "delta") else {
+        return 0;
+    };
+
+    let Some(rerandomized) = ok_or_log(
+        rerandomize(&ciphertext, &view_key, &delta),
+        "failed to re-randomize ciphertext",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(rerandomized.to_string().into_bytes())
+}
@@ -1,14 +1,84 @@
-use alloc::string::ToString;
+use alloc::{format, string::ToString};
 use core::{slice, str};
 
 use rand::{rngs::StdRng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use snarkvm_console::{
-    account::{Address, PrivateKey},
-    prelude::{FromBytes, FromStr, ToFields},
+    account::{Address, PrivateKey, Signature},
+    prelude::{FromBytes, FromStr, Network, Result, ToBytes, ToFields},
     program::{Literal, Plaintext, U128},
+    types::Field,
 };
 
-use crate::{log::log, memory::forget_buf_ptr_len, network::CurrentNetwork};
+use crate::{
+    format::{field_from_le_bytes, pack_bytes, FIELD_DATA_BYTES},
+    hash::{digest_bytes, KECCAK256, KECCAK384, KECCAK512, SHA3_256, SHA3_384, SHA3_512},
+    log::{clear_last_error, log, ok_or_log},
+    memory::{encode_frames, forget_buf_ptr_len, parse_frames, parse_from_raw, str_from_raw},
+    network::CurrentNetwork,
+};
+
+// Size of the binary signature encoding: the challenge and response scalars followed by the
+// compute key's pk_sig and pr_sig group elements, 32 bytes each
+const SIGNATURE_BYTES_SIZE: usize = 128;
+
+// Converts a signed message into the fields the signature is computed over.
+// When we're dealing with bytes, we only accept U128 number as LE bytes (should come from the hash):
+// first we create a u128 value, then turn it into a plaintext literal, then get fields of that literal
+fn message_fields(message_bytes: &[u8]) -> Result<Vec<Field<CurrentNetwork>>> {
+    U128::<CurrentNetwork>::from_bytes_le(message_bytes)
+        .map(|integer| Plaintext::Literal(Literal::U128(integer), Default::default()))
+        .and_then(|plaintext| plaintext.to_fields())
+}
+
+// Hashes a message with one of the hash module's operation codes and reduces the digest to the
+// single field a hashed-message signature is computed over. Keccak and SHA-3 digests are
+// truncated to their first FIELD_DATA_BYTES little-endian bytes so they always fit below the
+// field modulus, every other operation already hashes to a field that is used as is.
+fn hashed_message_field(message: &[u8], hash_algorithm: u32) -> Result<Field<CurrentNetwork>> {
+    let digest = digest_bytes(hash_algorithm, message)?;
+    match hash_algorithm {
+        KECCAK256 | KECCAK384 | KECCAK512 | SHA3_256 | SHA3_384 | SHA3_512 => {
+            field_from_le_bytes(&digest[..FIELD_DATA_BYTES])
+        }
+        _ => Field::from_bytes_le(&digest),
+    }
+}
+
+// Converts a signed message and a replay-protection nonce into the fields the signature is computed
+// over: the message fields (see message_fields) followed by the nonce packed like
+// string_to_fields packs bytes. The packed nonce starts with its length, so a message can't be
+// shifted into the nonce or vice versa.
+fn message_with_nonce_fields(
+    message_bytes: &[u8],
+    nonce: &[u8],
+) -> Result<Vec<Field<CurrentNetwork>>> {
+    let mut fields = message_fields(message_bytes)?;
+    fields.extend(pack_bytes(nonce)?);
+    Ok(fields)
+}
+
+// Creates the RNG the nonce of a deterministic signature is sampled from. Its seed is the
+// little-endian encoding of Poseidon2(domain, private key seed, message fields...), where the
+// domain is a separator naming the signing scheme (e.g. "AleoUtilsSignNonce0" for
+// deterministic_sign), so the nonce is unique per scheme, key and message and can't be computed
+// without the private key.
+fn deterministic_nonce_rng(
+    domain: &str,
+    private_key: &PrivateKey<CurrentNetwork>,
+    message: &[Field<CurrentNetwork>],
+) -> Result<ChaCha20Rng> {
+    let mut input = Vec::with_capacity(message.len() + 2);
+    input.push(Field::<CurrentNetwork>::new_domain_separator(domain));
+    input.push(private_key.seed());
+    input.extend_from_slice(message);
+
+    let nonce_seed = CurrentNetwork::hash_psd2(&input)?.to_bytes_le()?;
+    let mut rng_seed = [0u8; 32];
+    rng_seed.copy_from_slice(&nonce_seed[..32]);
+
+    Ok(ChaCha20Rng::from_seed(rng_seed))
+}
 
 #[no_mangle]
 pub extern "C" fn sign(
@@ -62,17 +132,7 @@ pub extern "C" fn sign(
     // restore the data for signing slice from the pointer
     let hash_field_bytes = unsafe { slice::from_raw_parts(hash_field_str, hash_field_len) };
 
-    // when we're dealing with bytes, we only accept U128 number as LE bytes (should come from the hash)
-    // first we create a u128 value, then turn it into a plaintext literal, then get fields of that literal
-    let fields_for_signing = match U128::<CurrentNetwork>::from_bytes_le(hash_field_bytes)
-        .and_then(|integer| {
-            Ok(Plaintext::Literal(
-                Literal::U128(integer),
-                Default::default(),
-            ))
-        })
-        .and_then(|plaintext| plaintext.to_fields())
-    {
+    let fields_for_signing = match message_fields(hash_field_bytes) {
         Ok(val) => val,
         Err(e) => {
             let mut err_str = String::from("failed to parse u128 plaintext value from bytes: ");
@@ -106,3 +166,515 @@ pub extern "C" fn sign(
     let output_bytes = signature.to_string().into_bytes();
     forget_buf_ptr_len(output_bytes)
 }
+
+/// Verifies a signature over a message signed with `sign` (little-endian bytes of a U128) against
+/// an address. Returns 1 if the signature is valid and 0 otherwise.
+///
+/// The result doesn't short-circuit: all three arguments are always parsed, verification runs
+/// whenever they parse, and the outcome is combined without branching, so the time taken doesn't
+/// reveal which of the inputs was rejected. snarkVM verifies by recomputing the challenge and
+/// comparing field elements rather than bytes, so there's no byte position to leak. This is best
+/// effort: parsing time still depends on the input lengths, and neither snarkVM's arithmetic nor
+/// the WASM runtime executing this module give constant-time guarantees.
+#[no_mangle]
+pub extern "C" fn verify_signature(
+    address_str: *const u8,
+    address_len: usize,
+    signature_str: *const u8,
+    signature_len: usize,
+    message: *const u8,
+    message_len: usize,
+) -> u64 {
+    let address = str_from_raw(address_str, address_len, "address").and_then(|val| {
+        ok_or_log(
+            Address::<CurrentNetwork>::from_str(val),
+            "failed to parse address from string",
+        )
+    });
+
+    let signature = str_from_raw(signature_str, signature_len, "signature").and_then(|val| {
+        ok_or_log(
+            Signature::<CurrentNetwork>::from_str(val),
+            "failed to parse signature from string",
+        )
+    });
+
+    let message_bytes = unsafe { slice::from_raw_parts(message, message_len) };
+    let fields = ok_or_log(
+        message_fields(message_bytes),
+        "failed to parse u128 plaintext value from bytes",
+    );
+
+    let parsed = address.is_some() & signature.is_some() & fields.is_some();
+    let verified = match (&address, &signature, &fields) {
+        (Some(addr), Some(sig), Some(fields)) => sig.verify(addr, fields),
+        _ => false,
+    };
+
+    (parsed & verified) as u64
+}
+
+/// Converts a signature string into its binary encoding, the fixed-width SIGNATURE_BYTES_SIZE
+/// little-endian bytes snarkVM uses to serialize signatures.
+#[no_mangle]
+pub extern "C" fn signature_to_bytes(signature: *const u8, signature_len: usize) -> u64 {
+    let Some(signature) =
+        parse_from_raw::<Signature<CurrentNetwork>>(signature, signature_len, "signature")
+    else {
+        return 0;
+    };
+
+    let Some(bytes) = ok_or_log(signature.to_bytes_le(), "failed to serialize signature") else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(bytes)
+}
+
+/// Converts the binary encoding of a signature created by signature_to_bytes back into its
+/// string form. The input must be exactly SIGNATURE_BYTES_SIZE bytes long.
+#[no_mangle]
+pub extern "C" fn signature_from_bytes(bytes: *const u8, bytes_len: usize) -> u64 {
+    if bytes_len != SIGNATURE_BYTES_SIZE {
+        log(format!(
+            "signature bytes must be {SIGNATURE_BYTES_SIZE} bytes long"
+        ));
+        return 0;
+    }
+
+    let bytes = unsafe { slice::from_raw_parts(bytes, bytes_len) };
+    let Some(signature) = ok_or_log(
+        Signature::<CurrentNetwork>::from_bytes_le(bytes),
+        "failed to parse signature from bytes",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(signature.to_string().into_bytes())
+}
+
+/// Verifies a list of signatures over the same message (see verify_signature), where the
+/// signature at every position of the framed `signatures` list must be valid for the address at
+/// the same position of the framed `addresses` list.
+///
+/// Returns 1 only if both lists are non-empty, have the same length and every pair verifies, and 0
+/// otherwise. Unlike verify_signature this short-circuits on the first invalid pair, the policy
+/// the result gates (e.g. m-of-n) is expected to be enforced by the caller.
+#[no_mangle]
+pub extern "C" fn verify_all_signatures(
+    message: *const u8,
+    message_len: usize,
+    addresses: *const u8,
+    addresses_len: usize,
+    signatures: *const u8,
+    signatures_len: usize,
+) -> u64 {
+    let message_bytes = unsafe { slice::from_raw_parts(message, message_len) };
+    let Some(fields) = ok_or_log(
+        message_fields(message_bytes),
+        "failed to parse u128 plaintext value from bytes",
+    ) else {
+        return 0;
+    };
+
+    let addresses_buf = unsafe { slice::from_raw_parts(addresses, addresses_len) };
+    let Some(addresses) = parse_frames::<Address<CurrentNetwork>>(addresses_buf, "addresses")
+    else {
+        return 0;
+    };
+    let signatures_buf = unsafe { slice::from_raw_parts(signatures, signatures_len) };
+    let Some(signatures) = parse_frames::<Signature<CurrentNetwork>>(signatures_buf, "signatures")
+    else {
+        return 0;
+    };
+
+    if addresses.is_empty() || addresses.len() != signatures.len() {
+        log("expected the same non-zero number of addresses and signatures");
+        return 0;
+    }
+
+    addresses
+        .iter()
+        .zip(signatures.iter())
+        .all(|(address, signature)| signature.verify(address, &fields)) as u64
+}
+
+/// Returned by `verify_against_any` if none of the addresses verifies the signature
+pub const SIGNER_NOT_FOUND: u64 = u64::MAX;
+
+/// Verifies a signature over a message signed with `sign` against a framed list of candidate
+/// addresses, for signers known to be one of a set. A signature can only be valid for the address
+/// of the compute key it embeds, so instead of verifying against every candidate this looks that
+/// address up and verifies once.
+///
+/// Returns the index of the first address the signature is valid for, or `SIGNER_NOT_FOUND` if
+/// there's none, and also if an input doesn't parse, which is told apart by the last error being
+/// set, as it's cleared at the start.
+#[no_mangle]
+pub extern "C" fn verify_against_any(
+    addresses: *const u8,
+    addresses_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+    message: *const u8,
+    message_len: usize,
+) -> u64 {
+    clear_last_error();
+
+    let addresses_buf = unsafe { slice::from_raw_parts(addresses, addresses_len) };
+    let Some(addresses) = parse_frames::<Address<CurrentNetwork>>(addresses_buf, "addresses")
+    else {
+        return SIGNER_NOT_FOUND;
+    };
+    let Some(signature) =
+        parse_from_raw::<Signature<CurrentNetwork>>(signature, signature_len, "signature")
+    else {
+        return SIGNER_NOT_FOUND;
+    };
+    let message_bytes = unsafe { slice::from_raw_parts(message, message_len) };
+    let Some(fields) = ok_or_log(
+        message_fields(message_bytes),
+        "failed to parse u128 plaintext value from bytes",
+    ) else {
+        return SIGNER_NOT_FOUND;
+    };
+
+    let signer = signature.to_address();
+    match addresses.iter().position(|address| *address == signer) {
+        Some(index) if signature.verify(&addresses[index], &fields) => index as u64,
+        _ => SIGNER_NOT_FOUND,
+    }
+}
+
+/// Signs a message like `sign`, but derives the signature nonce from the private key and the
+/// message instead of sampling it from entropy (see `deterministic_nonce_rng`), in the spirit of
+/// RFC 6979. Signing the same message with the same key always returns the same signature, and the
+/// signature verifies with verify_signature.
+#[no_mangle]
+pub extern "C" fn deterministic_sign(
+    private_key: *const u8,
+    private_key_len: usize,
+    message: *const u8,
+    message_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(address) = ok_or_log(
+        Address::try_from(&private_key),
+        "failed to convert a private key to address",
+    ) else {
+        return 0;
+    };
+
+    let message_bytes = unsafe { slice::from_raw_parts(message, message_len) };
+    let Some(fields) = ok_or_log(
+        message_fields(message_bytes),
+        "failed to parse u128 plaintext value from bytes",
+    ) else {
+        return 0;
+    };
+
+    let Some(mut rng) = ok_or_log(
+        deterministic_nonce_rng("AleoUtilsSignNonce0", &private_key, &fields),
+        "failed to derive signature nonce",
+    ) else {
+        return 0;
+    };
+    let Some(signature) = ok_or_log(
+        private_key.sign(&fields, &mut rng),
+        "failed to sign fields with private key",
+    ) else {
+        return 0;
+    };
+
+    if !signature.verify(&address, &fields) {
+        log("signature self check failed");
+        return 0;
+    }
+
+    forget_buf_ptr_len(signature.to_string().into_bytes())
+}
+
+/// Signs a message given as a framed list of field strings directly, the way snarkVM signs a
+/// `&[Field]`, for messages that don't fit into the single u128 `sign` accepts. Like `sign`, the
+/// signature is verified before it's returned. Returns the signature string, or 0 on failure.
+#[no_mangle]
+pub extern "C" fn sign_fields(
+    private_key: *const u8,
+    private_key_len: usize,
+    fields: *const u8,
+    fields_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(address) = ok_or_log(
+        Address::try_from(&private_key),
+        "failed to convert a private key to address",
+    ) else {
+        return 0;
+    };
+    let fields_buf = unsafe { slice::from_raw_parts(fields, fields_len) };
+    let Some(fields) = parse_frames::<Field<CurrentNetwork>>(fields_buf, "message fields") else {
+        return 0;
+    };
+
+    let Some(signature) = ok_or_log(
+        private_key.sign(&fields, &mut StdRng::from_entropy()),
+        "failed to sign fields with private key",
+    ) else {
+        return 0;
+    };
+
+    if !signature.verify(&address, &fields) {
+        log("signature self check failed");
+        return 0;
+    }
+
+    forget_buf_ptr_len(signature.to_string().into_bytes())
+}
+
+/// Verifies a signature over a message given as a framed list of field strings (see
+/// `sign_fields`) against an address. Returns 1 if the signature is valid and 0 otherwise. Like
+/// `verify_signature`, all arguments are parsed before the results are combined, so the result
+/// doesn't short-circuit on the first input that's rejected.
+#[no_mangle]
+pub extern "C" fn verify_signature_fields(
+    address: *const u8,
+    address_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+    fields: *const u8,
+    fields_len: usize,
+) -> u64 {
+    let address = parse_from_raw::<Address<CurrentNetwork>>(address, address_len, "address");
+    let signature =
+        parse_from_raw::<Signature<CurrentNetwork>>(signature, signature_len, "signature");
+    let fields_buf = unsafe { slice::from_raw_parts(fields, fields_len) };
+    let fields = parse_frames::<Field<CurrentNetwork>>(fields_buf, "message fields");
+
+    let parsed = address.is_some() & signature.is_some() & fields.is_some();
+    let verified = match (&address, &signature, &fields) {
+        (Some(addr), Some(sig), Some(fields)) => sig.verify(addr, fields),
+        _ => false,
+    };
+
+    (parsed & verified) as u64
+}
+
+/// Signs a message like `sign`, but binds a caller-chosen replay-protection nonce (e.g. a counter
+/// or a session id) into the signature: the signed fields are the message fields followed by the
+/// nonce bytes packed like `string_to_fields` packs them (length field first, then 31-byte
+/// chunks). The same message signed under different nonces yields distinct signatures, each valid
+/// only for its own nonce with `verify_signature_with_nonce`. The signature is verified before
+/// it's returned.
+#[no_mangle]
+pub extern "C" fn sign_message_with_nonce(
+    private_key: *const u8,
+    private_key_len: usize,
+    message: *const u8,
+    message_len: usize,
+    nonce: *const u8,
+    nonce_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(address) = ok_or_log(
+        Address::try_from(&private_key),
+        "failed to convert a private key to address",
+    ) else {
+        return 0;
+    };
+
+    let message_bytes = unsafe { slice::from_raw_parts(message, message_len) };
+    let nonce = unsafe { slice::from_raw_parts(nonce, nonce_len) };
+    let Some(fields) = ok_or_log(
+        message_with_nonce_fields(message_bytes, nonce),
+        "failed to build message fields with nonce",
+    ) else {
+        return 0;
+    };
+
+    let Some(signature) = ok_or_log(
+        private_key.sign(&fields, &mut StdRng::from_entropy()),
+        "failed to sign fields with private key",
+    ) else {
+        return 0;
+    };
+
+    if !signature.verify(&address, &fields) {
+        log("signature self check failed");
+        return 0;
+    }
+
+    forget_buf_ptr_len(signature.to_string().into_bytes())
+}
+
+/// Verifies a signature created with `sign_message_with_nonce` over a message and nonce against an
+/// address. Returns 1 if the signature is valid for exactly this message and nonce, and 0
+/// otherwise. As in `verify_signature`, all arguments are parsed before the results are combined.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn verify_signature_with_nonce(
+    address: *const u8,
+    address_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+    message: *const u8,
+    message_len: usize,
+    nonce: *const u8,
+    nonce_len: usize,
+) -> u64 {
+    let address = parse_from_raw::<Address<CurrentNetwork>>(address, address_len, "address");
+    let signature =
+        parse_from_raw::<Signature<CurrentNetwork>>(signature, signature_len, "signature");
+
+    let message_bytes = unsafe { slice::from_raw_parts(message, message_len) };
+    let nonce = unsafe { slice::from_raw_parts(nonce, nonce_len) };
+    let fields = ok_or_log(
+        message_with_nonce_fields(message_bytes, nonce),
+        "failed to build message fields with nonce",
+    );
+
+    let parsed = address.is_some() & signature.is_some() & fields.is_some();
+    let verified = match (&address, &signature, &fields) {
+        (Some(addr), Some(sig), Some(fields)) => sig.verify(addr, fields),
+        _ => false,
+    };
+
+    (parsed & verified) as u64
+}
+
+/// Returns what a signature commits to without needing the signed message: a framed list of the
+/// signer's address (derived from the compute key embedded in the signature) and the challenge
+/// scalar, the hash of the nonce commitment, the keys and the message the response answers.
+/// Returns 0 if the signature doesn't parse.
+#[no_mangle]
+pub extern "C" fn signature_inspect(signature: *const u8, signature_len: usize) -> u64 {
+    let Some(signature) =
+        parse_from_raw::<Signature<CurrentNetwork>>(signature, signature_len, "signature")
+    else {
+        return 0;
+    };
+
+    let address = signature.to_address().to_string();
+    let challenge = signature.challenge().to_string();
+
+    forget_buf_ptr_len(encode_frames(&[address, challenge]))
+}
+
+/// Hashes an arbitrary message with the chosen algorithm, reduces the digest to a field (see
+/// `hashed_message_field`) and signs that single field, so callers control the pre-hash step.
+/// The algorithm is one of the hash module's operation codes:
+///
+/// - BHP256 (0), BHP512 (1), BHP768 (2), BHP1024 (3): BHP over the message bits
+/// - PED64 (4), PED128 (5): Pedersen over the message bits, up to 8 and 16 bytes
+/// - PSD2 (6), PSD4 (7), PSD8 (8): Poseidon over the message packed into fields
+/// - KECCAK256 (9), KECCAK384 (10), KECCAK512 (11), SHA3_256 (12), SHA3_384 (13),
+///   SHA3_512 (14): the standard digest truncated to 31 bytes
+///
+/// The nonce is derived from the private key, the seed and the digest (see
+/// `deterministic_nonce_rng`, the domain is "AleoUtilsSignHashed0"), so the same key, seed and
+/// message always give the same signature, and reusing a seed across messages doesn't reuse the
+/// nonce. The seed may be empty. The signature is verified against the key's address before it's
+/// returned. Returns the signature string, or 0 on failure.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn sign_hashed_message(
+    private_key: *const u8,
+    private_key_len: usize,
+    message: *const u8,
+    message_len: usize,
+    hash_algorithm: u32,
+    seed: *const u8,
+    seed_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(address) = ok_or_log(
+        Address::try_from(&private_key),
+        "failed to convert a private key to address",
+    ) else {
+        return 0;
+    };
+
+    let message_bytes = unsafe { slice::from_raw_parts(message, message_len) };
+    let Some(digest) = ok_or_log(
+        hashed_message_field(message_bytes, hash_algorithm),
+        "failed to hash message",
+    ) else {
+        return 0;
+    };
+
+    let seed = unsafe { slice::from_raw_parts(seed, seed_len) };
+    let Some(mut nonce_input) = ok_or_log(pack_bytes(seed), "failed to pack seed") else {
+        return 0;
+    };
+    nonce_input.push(digest);
+    let Some(mut rng) = ok_or_log(
+        deterministic_nonce_rng("AleoUtilsSignHashed0", &private_key, &nonce_input),
+        "failed to derive signature nonce",
+    ) else {
+        return 0;
+    };
+
+    let Some(signature) = ok_or_log(
+        private_key.sign(&[digest], &mut rng),
+        "failed to sign fields with private key",
+    ) else {
+        return 0;
+    };
+
+    if !signature.verify(&address, &[digest]) {
+        log("signature self check failed");
+        return 0;
+    }
+
+    forget_buf_ptr_len(signature.to_string().into_bytes())
+}
+
+/// Verifies a signature created with `sign_hashed_message` over a message against an address,
+/// hashing the message with the same algorithm code. The seed isn't needed. Returns 1 if the
+/// signature is valid and 0 otherwise, including when the message doesn't hash with the
+/// algorithm. As in `verify_signature`, all arguments are parsed and the message is hashed before
+/// the results are combined.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub extern "C" fn verify_hashed_message(
+    address: *const u8,
+    address_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+    message: *const u8,
+    message_len: usize,
+    hash_algorithm: u32,
+) -> u64 {
+    let address = parse_from_raw::<Address<CurrentNetwork>>(address, address_len, "address");
+    let signature =
+        parse_from_raw::<Signature<CurrentNetwork>>(signature, signature_len, "signature");
+
+    let message_bytes = unsafe { slice::from_raw_parts(message, message_len) };
+    let digest = ok_or_log(
+        hashed_message_field(message_bytes, hash_algorithm),
+        "failed to hash message",
+    );
+
+    let parsed = address.is_some() & signature.is_some() & digest.is_some();
+    let verified = match (&address, &signature, &digest) {
+        (Some(addr), Some(sig), Some(digest)) => sig.verify(addr, &[*digest]),
+        _ => false,
+    };
+
+    (parsed & verified) as u64
+}
@@ -0,0 +1,49 @@
+use alloc::string::ToString;
+
+use rand::{rngs::StdRng, SeedableRng};
+use snarkvm_console::{
+    prelude::{Network, Result, Uniform},
+    types::Scalar,
+};
+
+use crate::{
+    log::ok_or_log,
+    memory::{encode_frames, forget_buf_ptr_len, parse_from_raw},
+    network::CurrentNetwork,
+    record::{CiphertextRecord, PlaintextRecord},
+};
+
+// Encrypts a record to its owner with a fresh randomizer r, replacing the nonce of the record with
+// G * r the way snarkVM does for output records. Returns the ciphertext and the plaintext record
+// with the new nonce.
+fn encrypt_to_owner(record: &PlaintextRecord) -> Result<(CiphertextRecord, PlaintextRecord)> {
+    let randomizer = Scalar::<CurrentNetwork>::rand(&mut StdRng::from_entropy());
+    let nonce = CurrentNetwork::g_scalar_multiply(&randomizer);
+
+    let record =
+        PlaintextRecord::from_plaintext(record.owner().clone(), record.data().clone(), nonce)?;
+    let ciphertext = record.encrypt(randomizer)?;
+
+    Ok((ciphertext, record))
+}
+
+/// Encrypts a plaintext record to its owner, so tests get record ciphertexts without a node. The
+/// `_nonce` of the input record is ignored: a fresh randomizer r is sampled and the record nonce is
+/// set to G * r, as snarkVM does for the records a transition outputs.
+///
+/// Returns a framed list of the record ciphertext string and the plaintext record string with the
+/// new nonce, or 0 on failure.
+#[no_mangle]
+pub extern "C" fn encrypt_record(record: *const u8, record_len: usize) -> u64 {
+    let Some(record) = parse_from_raw::<PlaintextRecord>(record, record_len, "record") else {
+        return 0;
+    };
+
+    let Some((ciphertext, record)) =
+        ok_or_log(encrypt_to_owner(&record), "failed to encrypt record")
+    else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(encode_frames(&[ciphertext.to_string(), record.to_string()]))
+}
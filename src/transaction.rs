@@ -0,0 +1,33 @@
+use alloc::{string::ToString, vec::Vec};
+use core::slice;
+
+use snarkvm_console::prelude::FromBytes;
+use snarkvm_ledger_block::Transaction;
+
+use crate::{
+    log::ok_or_log,
+    memory::{encode_frames, forget_buf_ptr_len},
+    network::CurrentNetwork,
+};
+
+/// Deserializes a transaction from its canonical little-endian bytes, as nodes store and serve
+/// it, and returns the IDs of its transitions (`au1...` strings) as a framed list, in the order
+/// they appear in the transaction: the transitions of an execution followed by the fee
+/// transition, if any. Returns 0 if the bytes aren't a valid transaction.
+#[no_mangle]
+pub extern "C" fn transaction_transition_ids(tx_bytes: *const u8, tx_bytes_len: usize) -> u64 {
+    let tx_buf = unsafe { slice::from_raw_parts(tx_bytes, tx_bytes_len) };
+    let Some(transaction) = ok_or_log(
+        Transaction::<CurrentNetwork>::from_bytes_le(tx_buf),
+        "failed to deserialize transaction",
+    ) else {
+        return 0;
+    };
+
+    let ids = transaction
+        .transition_ids()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>();
+
+    forget_buf_ptr_len(encode_frames(&ids))
+}
@@ -0,0 +1,268 @@
+use alloc::string::ToString;
+use core::slice;
+
+use snarkvm_console::{
+    account::{PrivateKey, ViewKey},
+    prelude::{bail, Inverse, Network, Result},
+    program::{Request, ValueType},
+    types::{Field, Group, Scalar},
+};
+
+use crate::{
+    log::{clear_last_error, log, ok_or_log},
+    memory::{encode_frames, forget_buf_ptr_len, parse_frames, parse_from_raw},
+    network::CurrentNetwork,
+    record::{record_microcredits, PlaintextRecord},
+};
+
+// Recovers the transition public key from the transition view key and the view key of the signer.
+// snarkVM samples a nonce r for every request and sets tpk = G * r and tvk = (address * r).x, where
+// address = G * view_key, so tvk is the x-coordinate of tpk * view_key. The x-coordinate determines
+// the point of the prime-order subgroup uniquely, and multiplying it by the inverse of the view key
+// yields the tpk.
+fn tpk_from_tvk(
+    view_key: &ViewKey<CurrentNetwork>,
+    tvk: Field<CurrentNetwork>,
+) -> Result<Group<CurrentNetwork>> {
+    let tvk_point = Group::<CurrentNetwork>::from_x_coordinate(tvk)?;
+
+    Ok(tvk_point * view_key.inverse()?)
+}
+
+// Derives the transition view key of a transition from its transition public key and the view key
+// of the signer: tvk = (tpk * view_key).x, which equals snarkVM's (address * r).x for tpk = G * r.
+fn tvk_from_tpk(
+    view_key: &ViewKey<CurrentNetwork>,
+    tpk: Group<CurrentNetwork>,
+) -> Field<CurrentNetwork> {
+    (tpk * **view_key).to_x_coordinate()
+}
+
+// Derives the encryption randomizer and nonce of an output record the way snarkVM's `Response`
+// does: randomizer = HashToScalarPSD2(tvk, index) and nonce = G * randomizer, where `index` is the
+// position of the output among all inputs and outputs of the transition, read as a field.
+fn output_randomizer(
+    tvk: Field<CurrentNetwork>,
+    index: u16,
+) -> Result<(Scalar<CurrentNetwork>, Group<CurrentNetwork>)> {
+    let randomizer = CurrentNetwork::hash_to_scalar_psd2(&[tvk, Field::from_u16(index)])?;
+
+    Ok((randomizer, CurrentNetwork::g_scalar_multiply(&randomizer)))
+}
+
+// Sums the microcredits of credits records, failing on records without a u64 `microcredits` entry
+// and on totals that overflow a u64
+fn total_microcredits(records: &[PlaintextRecord]) -> Result<u64> {
+    let mut total: u64 = 0;
+    for record in records {
+        let Some(amount) = record_microcredits(record)? else {
+            bail!("record has no microcredits entry");
+        };
+        let Some(sum) = total.checked_add(amount) else {
+            bail!("record balances overflow a u64");
+        };
+        total = sum;
+    }
+
+    Ok(total)
+}
+
+// Checks that the input records of a transfer hold exactly the microcredits of its output records
+// plus the fee
+fn balance_invariant_holds(
+    inputs: &[PlaintextRecord],
+    outputs: &[PlaintextRecord],
+    fee: u64,
+) -> Result<bool> {
+    let input_total = total_microcredits(inputs)?;
+    let Some(output_total) = total_microcredits(outputs)?.checked_add(fee) else {
+        bail!("output balances and fee overflow a u64");
+    };
+
+    Ok(input_total == output_total)
+}
+
+/// Computes the transition public key (tpk) of a transition signed with a private key, from its
+/// transition view key (see `tpk_from_tvk` for the derivation).
+///
+/// - `private_key` - the private key the transition's request was signed with
+/// - `tvk` - the transition view key, a field string
+///
+/// Returns the tpk as a group string, or 0 if an input fails to parse or the tvk isn't the
+/// x-coordinate of a point in the prime-order subgroup.
+#[no_mangle]
+pub extern "C" fn compute_tpk(
+    private_key: *const u8,
+    private_key_len: usize,
+    tvk: *const u8,
+    tvk_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(tvk) = parse_from_raw::<Field<CurrentNetwork>>(tvk, tvk_len, "transition view key")
+    else {
+        return 0;
+    };
+
+    let Some(view_key) = ok_or_log(
+        ViewKey::try_from(private_key),
+        "failed to convert a private key to view key",
+    ) else {
+        return 0;
+    };
+    let Some(tpk) = ok_or_log(
+        tpk_from_tvk(&view_key, tvk),
+        "failed to recover transition public key",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(tpk.to_string().into_bytes())
+}
+
+/// Computes the transition view key (tvk) of a transition signed with a private key, from its
+/// transition public key (see `tvk_from_tpk` for the derivation). The tvk is needed to encrypt and
+/// decrypt the private inputs and outputs of the transition. This is the inverse of `compute_tpk`.
+///
+/// - `private_key` - the private key the transition's request was signed with
+/// - `tpk` - the transition public key, a group string
+///
+/// Returns the tvk as a field string, or 0 if an input fails to parse.
+#[no_mangle]
+pub extern "C" fn compute_tvk(
+    private_key: *const u8,
+    private_key_len: usize,
+    tpk: *const u8,
+    tpk_len: usize,
+) -> u64 {
+    let Some(private_key) =
+        parse_from_raw::<PrivateKey<CurrentNetwork>>(private_key, private_key_len, "private key")
+    else {
+        return 0;
+    };
+    let Some(tpk) = parse_from_raw::<Group<CurrentNetwork>>(tpk, tpk_len, "transition public key")
+    else {
+        return 0;
+    };
+
+    let Some(view_key) = ok_or_log(
+        ViewKey::try_from(private_key),
+        "failed to convert a private key to view key",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(tvk_from_tpk(&view_key, tpk).to_string().into_bytes())
+}
+
+/// Checks the balance invariant of a credits transfer before proving it: the microcredits of the
+/// input records must equal the microcredits of the output records plus the fee.
+///
+/// - `inputs` - a framed list of the plaintext credits records the transfer consumes
+/// - `outputs` - a framed list of the plaintext credits records the transfer creates
+/// - `fee` - the fee in microcredits
+///
+/// Returns 1 if the invariant holds and 0 otherwise. As the last error is cleared at the start, a 0
+/// with the last error set means a record failed to parse or has no u64 `microcredits` entry, or a
+/// side of the invariant overflows a u64.
+#[no_mangle]
+pub extern "C" fn verify_balance_invariant(
+    inputs: *const u8,
+    inputs_len: usize,
+    outputs: *const u8,
+    outputs_len: usize,
+    fee: u64,
+) -> u64 {
+    clear_last_error();
+
+    let inputs_buf = unsafe { slice::from_raw_parts(inputs, inputs_len) };
+    let Some(inputs) = parse_frames::<PlaintextRecord>(inputs_buf, "input records") else {
+        return 0;
+    };
+    let outputs_buf = unsafe { slice::from_raw_parts(outputs, outputs_len) };
+    let Some(outputs) = parse_frames::<PlaintextRecord>(outputs_buf, "output records") else {
+        return 0;
+    };
+
+    ok_or_log(
+        balance_invariant_holds(&inputs, &outputs, fee),
+        "failed to check balance invariant",
+    )
+    .unwrap_or(false) as u64
+}
+
+/// Derives the encryption randomizer and the nonce of an output record of a transition, the two
+/// values constructing the record always needs together (see `output_randomizer` for the
+/// derivation).
+///
+/// - `private_key` - unused and not parsed, as both values depend on the transition view key only,
+///   kept so the signature matches `compute_tpk` and `compute_tvk`
+/// - `tvk` - the transition view key, a field string
+/// - `index` - the position of the output among all inputs and outputs of the transition, i.e. the
+///   number of inputs plus the index of the output, at most u16::MAX as in snarkVM
+///
+/// Returns a framed list of the randomizer as a scalar string and the nonce as a group string, or 0
+/// if the tvk fails to parse or the index is out of range.
+#[no_mangle]
+pub extern "C" fn derive_output_randomizers(
+    _private_key: *const u8,
+    _private_key_len: usize,
+    tvk: *const u8,
+    tvk_len: usize,
+    index: u64,
+) -> u64 {
+    let Ok(index) = u16::try_from(index) else {
+        log("output index exceeds u16::MAX");
+        return 0;
+    };
+    let Some(tvk) = parse_from_raw::<Field<CurrentNetwork>>(tvk, tvk_len, "transition view key")
+    else {
+        return 0;
+    };
+
+    let Some((randomizer, nonce)) = ok_or_log(
+        output_randomizer(tvk, index),
+        "failed to derive output randomizer",
+    ) else {
+        return 0;
+    };
+
+    forget_buf_ptr_len(encode_frames(&[randomizer.to_string(), nonce.to_string()]))
+}
+
+/// Verifies a request, the signed part of an authorization, before it's forwarded for proving. This
+/// runs snarkVM's `Request::verify`, which checks the caller's signature over the function ID and
+/// the input IDs, the transition view key commitment, and that every input matches its ID and the
+/// input type of the function.
+///
+/// - `request` - the request, as the JSON string snarkVM serializes it to
+/// - `input_types` - a framed list of the function's input types in order, e.g. `address.public`
+///   or `credits.record`
+///
+/// The request is checked as the root request of its authorization, the call the user signed for:
+/// requests of nested calls are signed as non-root and don't verify.
+///
+/// Returns 1 if the request is valid and 0 otherwise. Both inputs are parsed before the result is
+/// computed, and the last error is cleared at the start, so a 0 with the last error set means an
+/// input failed to parse and a 0 with an empty last error means the request is invalid.
+#[no_mangle]
+pub extern "C" fn verify_request(
+    request: *const u8,
+    request_len: usize,
+    input_types: *const u8,
+    input_types_len: usize,
+) -> u64 {
+    clear_last_error();
+
+    let request = parse_from_raw::<Request<CurrentNetwork>>(request, request_len, "request");
+    let input_types_buf = unsafe { slice::from_raw_parts(input_types, input_types_len) };
+    let input_types = parse_frames::<ValueType<CurrentNetwork>>(input_types_buf, "input types");
+
+    match (request, input_types) {
+        (Some(request), Some(input_types)) => request.verify(&input_types, true) as u64,
+        _ => 0,
+    }
+}
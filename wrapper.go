@@ -8,6 +8,7 @@ import (
 	"errors"
 	"fmt"
 	"log"
+	"math"
 
 	"github.com/tetratelabs/wazero"
 	"github.com/tetratelabs/wazero/api"
@@ -23,10 +24,94 @@ const (
 	PRIVATE_KEY_SIZE          = 59
 	ADDRESS_SIZE              = 63
 	SIGNATURE_SIZE            = 216
+	SIGNATURE_BYTES_SIZE      = 128
 	MESSAGE_FORMAT_BLOCK_SIZE = 16 * 32
 	MAX_FORMAT_MESSAGE_CHUNKS = 32
+	SEED_SIZE                 = 32
+	MAX_DERIVE_RANGE          = 1000
+	MAX_NEW_KEYS              = 1000
 )
 
+// MERKLE_LEAF_NOT_FOUND is returned by merkle_find_leaf if the target isn't among the leaves
+const MERKLE_LEAF_NOT_FOUND uint64 = math.MaxUint64
+
+// SIGNER_NOT_FOUND is returned by verify_against_any if none of the addresses verifies the signature
+const SIGNER_NOT_FOUND uint64 = math.MaxUint64
+
+// Address formats of GetAddressExt
+const (
+	ADDRESS_FORMAT_BECH32 uint32 = iota
+	ADDRESS_FORMAT_RAW
+)
+
+// snarkVM network IDs accepted by AddressForNetwork
+const (
+	NETWORK_MAINNET uint32 = iota
+	NETWORK_TESTNET
+)
+
+// Size of the raw address encoding returned for ADDRESS_FORMAT_RAW
+const ADDRESS_RAW_SIZE = 32
+
+// FFI contract the bindings are written against, checked against the module's abi_info when a
+// session is created
+const (
+	ABI_POINTER_WIDTH     = 32
+	ABI_ALLOC_HEADER_SIZE = 8
+	ABI_PACKED_PTR_SHIFT  = 0
+	ABI_PACKED_LEN_SHIFT  = 32
+)
+
+// Hash and commitment operation codes
+const (
+	HASH_BHP256 uint32 = iota
+	HASH_BHP512
+	HASH_BHP768
+	HASH_BHP1024
+	HASH_PED64
+	HASH_PED128
+	HASH_PSD2
+	HASH_PSD4
+	HASH_PSD8
+	HASH_KECCAK256
+	HASH_KECCAK384
+	HASH_KECCAK512
+	HASH_SHA3_256
+	HASH_SHA3_384
+	HASH_SHA3_512
+)
+
+// Literal type codes of LiteralToBytes and BytesToLiteral, the variant indices of snarkVM's
+// LiteralType
+const (
+	LITERAL_BOOLEAN uint32 = 1
+	LITERAL_I8      uint32 = 4
+	LITERAL_I16     uint32 = 5
+	LITERAL_I32     uint32 = 6
+	LITERAL_I64     uint32 = 7
+	LITERAL_I128    uint32 = 8
+	LITERAL_U8      uint32 = 9
+	LITERAL_U16     uint32 = 10
+	LITERAL_U32     uint32 = 11
+	LITERAL_U64     uint32 = 12
+	LITERAL_U128    uint32 = 13
+)
+
+// Kinds of strings reported by ClassifyBech32
+const (
+	STRING_UNKNOWN uint64 = iota
+	STRING_ADDRESS
+	STRING_PRIVATE_KEY
+	STRING_VIEW_KEY
+	STRING_SIGNATURE
+	STRING_TRANSACTION_ID
+	STRING_TRANSITION_ID
+	STRING_BLOCK_HASH
+)
+
+// UNBOUNDED_INPUT is returned by MaxInputBytes for operations that accept inputs of any size
+const UNBOUNDED_INPUT = ^uint64(0)
+
 // Wrapper is an interface for Aleo Wrapper session manager. Create an instance of a Wrapper using
 // NewWrapper, then create a new Session to use the signing functionality.
 type Wrapper interface {
@@ -90,7 +175,8 @@ func NewWrapper() (wrapper Wrapper, closeFn func(), err error) {
 		return nil, nil, fmt.Errorf("failed to instantiate host module: %w", hbErr)
 	}
 
-	moduleConfig := wazero.NewModuleConfig().WithRandSource(rand.Reader)
+	// the real clock is only read by hash_benchmark, wazero's default one is fake
+	moduleConfig := wazero.NewModuleConfig().WithRandSource(rand.Reader).WithSysNanotime()
 
 	cmod, err := runtime.CompileModule(ctx, wasmBytes)
 	if err != nil {
@@ -123,15 +209,126 @@ func (s *aleoWrapper) NewSession() (Session, error) {
 
 	// Resolve and validate all required exports explicitly.
 	required := map[string]api.Function{
-		"new_private_key":            mod.ExportedFunction("new_private_key"),
-		"get_address":                mod.ExportedFunction("get_address"),
-		"sign":                       mod.ExportedFunction("sign"),
-		"alloc":                      mod.ExportedFunction("alloc"),
-		"dealloc":                    mod.ExportedFunction("dealloc"),
-		"hash_message":               mod.ExportedFunction("hash_message"),
-		"hash_message_bytes":         mod.ExportedFunction("hash_message_bytes"),
-		"format_message":             mod.ExportedFunction("format_message"),
-		"formatted_message_to_bytes": mod.ExportedFunction("formatted_message_to_bytes"),
+		"new_private_key":             mod.ExportedFunction("new_private_key"),
+		"get_address":                 mod.ExportedFunction("get_address"),
+		"sign":                        mod.ExportedFunction("sign"),
+		"alloc":                       mod.ExportedFunction("alloc"),
+		"dealloc":                     mod.ExportedFunction("dealloc"),
+		"hash_message":                mod.ExportedFunction("hash_message"),
+		"hash_message_bytes":          mod.ExportedFunction("hash_message_bytes"),
+		"format_message":              mod.ExportedFunction("format_message"),
+		"formatted_message_to_bytes":  mod.ExportedFunction("formatted_message_to_bytes"),
+		"hash_to_address":             mod.ExportedFunction("hash_to_address"),
+		"max_input_bytes":             mod.ExportedFunction("max_input_bytes"),
+		"derive_child_key":            mod.ExportedFunction("derive_child_key"),
+		"derive_address_range":        mod.ExportedFunction("derive_address_range"),
+		"record_leaf_hash":            mod.ExportedFunction("record_leaf_hash"),
+		"verify_signature":            mod.ExportedFunction("verify_signature"),
+		"string_to_fields":            mod.ExportedFunction("string_to_fields"),
+		"fields_to_string":            mod.ExportedFunction("fields_to_string"),
+		"base_field_modulus":          mod.ExportedFunction("base_field_modulus"),
+		"scalar_field_modulus":        mod.ExportedFunction("scalar_field_modulus"),
+		"compute_record_commitment":   mod.ExportedFunction("compute_record_commitment"),
+		"get_view_key":                mod.ExportedFunction("get_view_key"),
+		"compute_record_tag":          mod.ExportedFunction("compute_record_tag"),
+		"scan_session_new":            mod.ExportedFunction("scan_session_new"),
+		"scan_session_tag":            mod.ExportedFunction("scan_session_tag"),
+		"scan_session_close":          mod.ExportedFunction("scan_session_close"),
+		"record_matches_commitment":   mod.ExportedFunction("record_matches_commitment"),
+		"last_error":                  mod.ExportedFunction("last_error"),
+		"clear_last_error":            mod.ExportedFunction("clear_last_error"),
+		"signature_to_bytes":          mod.ExportedFunction("signature_to_bytes"),
+		"signature_from_bytes":        mod.ExportedFunction("signature_from_bytes"),
+		"verify_all_signatures":       mod.ExportedFunction("verify_all_signatures"),
+		"deterministic_sign":          mod.ExportedFunction("deterministic_sign"),
+		"parse_program_inputs":        mod.ExportedFunction("parse_program_inputs"),
+		"decimal_to_field":            mod.ExportedFunction("decimal_to_field"),
+		"field_to_decimal":            mod.ExportedFunction("field_to_decimal"),
+		"abi_info":                    mod.ExportedFunction("abi_info"),
+		"compute_tpk":                 mod.ExportedFunction("compute_tpk"),
+		"ciphertext_commitment":       mod.ExportedFunction("ciphertext_commitment"),
+		"get_address_ext":             mod.ExportedFunction("get_address_ext"),
+		"hash_bytes":                  mod.ExportedFunction("hash_bytes"),
+		"hash_multi":                  mod.ExportedFunction("hash_multi"),
+		"free_secret":                 mod.ExportedFunction("free_secret"),
+		"allocation_count":            mod.ExportedFunction("allocation_count"),
+		"get_compute_key":             mod.ExportedFunction("get_compute_key"),
+		"address_from_components":     mod.ExportedFunction("address_from_components"),
+		"sign_fields":                 mod.ExportedFunction("sign_fields"),
+		"verify_signature_fields":     mod.ExportedFunction("verify_signature_fields"),
+		"record_owner":                mod.ExportedFunction("record_owner"),
+		"enable_buffer_pool":          mod.ExportedFunction("enable_buffer_pool"),
+		"heap_allocation_count":       mod.ExportedFunction("heap_allocation_count"),
+		"field_count_for_bytes":       mod.ExportedFunction("field_count_for_bytes"),
+		"sign_message_with_nonce":     mod.ExportedFunction("sign_message_with_nonce"),
+		"verify_signature_with_nonce": mod.ExportedFunction("verify_signature_with_nonce"),
+		"value_to_bits":               mod.ExportedFunction("value_to_bits"),
+		"normalize_program_id":        mod.ExportedFunction("normalize_program_id"),
+		"hkdf_derive":                 mod.ExportedFunction("hkdf_derive"),
+		"signature_inspect":           mod.ExportedFunction("signature_inspect"),
+		"set_max_alloc":               mod.ExportedFunction("set_max_alloc"),
+		"verify_address_checksum":     mod.ExportedFunction("verify_address_checksum"),
+		"private_key_components":      mod.ExportedFunction("private_key_components"),
+		"new_private_keys":            mod.ExportedFunction("new_private_keys"),
+		"sum_record_balances":         mod.ExportedFunction("sum_record_balances"),
+		"compute_fee_commitment":      mod.ExportedFunction("compute_fee_commitment"),
+		"validate_value_type":         mod.ExportedFunction("validate_value_type"),
+		"view_key_secret":             mod.ExportedFunction("view_key_secret"),
+		"decrypt_record":              mod.ExportedFunction("decrypt_record"),
+		"hash_concat":                 mod.ExportedFunction("hash_concat"),
+		"hash_with_domain":            mod.ExportedFunction("hash_with_domain"),
+		"merkle_root":                 mod.ExportedFunction("merkle_root"),
+		"merkle_multi_proof":          mod.ExportedFunction("merkle_multi_proof"),
+		"merkle_verify_multi":         mod.ExportedFunction("merkle_verify_multi"),
+		"merkle_find_leaf":            mod.ExportedFunction("merkle_find_leaf"),
+		"signature_to_json":           mod.ExportedFunction("signature_to_json"),
+		"ciphertexts_equal_plaintext": mod.ExportedFunction("ciphertexts_equal_plaintext"),
+		"pedersen_open":               mod.ExportedFunction("pedersen_open"),
+		"pedersen_verify":             mod.ExportedFunction("pedersen_verify"),
+		"address_from_label":          mod.ExportedFunction("address_from_label"),
+		"verify_balance_invariant":    mod.ExportedFunction("verify_balance_invariant"),
+		"verify_request":              mod.ExportedFunction("verify_request"),
+		"compute_tvk":                 mod.ExportedFunction("compute_tvk"),
+		"transaction_transition_ids":  mod.ExportedFunction("transaction_transition_ids"),
+		"address_for_network":         mod.ExportedFunction("address_for_network"),
+		"derive_output_randomizers":   mod.ExportedFunction("derive_output_randomizers"),
+		"validate_record":             mod.ExportedFunction("validate_record"),
+		"compute_state_root":          mod.ExportedFunction("compute_state_root"),
+		"group_is_in_subgroup":        mod.ExportedFunction("group_is_in_subgroup"),
+		"incr_merkle_new":             mod.ExportedFunction("incr_merkle_new"),
+		"incr_merkle_append":          mod.ExportedFunction("incr_merkle_append"),
+		"incr_merkle_free":            mod.ExportedFunction("incr_merkle_free"),
+		"decrypt_and_verify_record":   mod.ExportedFunction("decrypt_and_verify_record"),
+		"message_key":                 mod.ExportedFunction("message_key"),
+		"program_fingerprint":         mod.ExportedFunction("program_fingerprint"),
+		"program_functions":           mod.ExportedFunction("program_functions"),
+		"program_function_signature":  mod.ExportedFunction("program_function_signature"),
+		"compute_serial_number":       mod.ExportedFunction("compute_serial_number"),
+		"compute_serial_and_tag":      mod.ExportedFunction("compute_serial_and_tag"),
+		"bytes_to_field_array":        mod.ExportedFunction("bytes_to_field_array"),
+		"literal_to_bytes":            mod.ExportedFunction("literal_to_bytes"),
+		"bytes_to_literal":            mod.ExportedFunction("bytes_to_literal"),
+		"hash_benchmark":              mod.ExportedFunction("hash_benchmark"),
+		"values_equal":                mod.ExportedFunction("values_equal"),
+		"address_shard":               mod.ExportedFunction("address_shard"),
+		"encrypt_to_address":          mod.ExportedFunction("encrypt_to_address"),
+		"decrypt_from_address":        mod.ExportedFunction("decrypt_from_address"),
+		"stealth_address":             mod.ExportedFunction("stealth_address"),
+		"stealth_scan":                mod.ExportedFunction("stealth_scan"),
+		"compute_key_is_valid":        mod.ExportedFunction("compute_key_is_valid"),
+		"field_chain_new":             mod.ExportedFunction("field_chain_new"),
+		"field_chain_absorb":          mod.ExportedFunction("field_chain_absorb"),
+		"field_chain_free":            mod.ExportedFunction("field_chain_free"),
+		"classify_bech32":             mod.ExportedFunction("classify_bech32"),
+		"sign_hashed_message":         mod.ExportedFunction("sign_hashed_message"),
+		"verify_hashed_message":       mod.ExportedFunction("verify_hashed_message"),
+		"state_roots_equal":           mod.ExportedFunction("state_roots_equal"),
+		"record_is_owner":             mod.ExportedFunction("record_is_owner"),
+		"records_ownership_batch":     mod.ExportedFunction("records_ownership_batch"),
+		"account_to_json":             mod.ExportedFunction("account_to_json"),
+		"verify_against_any":          mod.ExportedFunction("verify_against_any"),
+		"check_hash_input":            mod.ExportedFunction("check_hash_input"),
+		"rerandomize_ciphertext":      mod.ExportedFunction("rerandomize_ciphertext"),
 	}
 
 	missing := make([]string, 0)
@@ -158,6 +355,12 @@ func (s *aleoWrapper) NewSession() (Session, error) {
 		hashMessageBytes: required["hash_message_bytes"],
 		formatMessage:    required["format_message"],
 		recoverMessage:   required["formatted_message_to_bytes"],
+		exports:          required,
+	}
+
+	if err := session.checkABI(); err != nil {
+		_ = mod.Close(context.Background())
+		return nil, err
 	}
 
 	return session, nil
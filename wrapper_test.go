@@ -1,11 +1,20 @@
 package aleo_utils
 
 import (
+	"bytes"
 	_ "embed"
+	"encoding/binary"
+	"encoding/hex"
+	"encoding/json"
 	"errors"
+	"fmt"
 	"log"
+	"math"
+	"math/big"
 	"reflect"
+	"strings"
 	"testing"
+	"time"
 )
 
 func TestAleoWrapper_NewAleoWrapper(t *testing.T) {
@@ -316,3 +325,5688 @@ func TestAleoWrapper_RecoverMessage(t *testing.T) {
 		t.Fatal("session should return error on any function call after it was closed")
 	}
 }
+
+func TestAleoWrapper_HashToAddress(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name    string
+		message []byte
+		wantErr bool
+	}{
+		{
+			name:    "literal",
+			message: []byte("1u128"),
+			wantErr: false,
+		},
+		{
+			name:    "struct",
+			message: []byte("{ a: 1u8, b: 2field }"),
+			wantErr: false,
+		},
+		{
+			name:    "invalid value",
+			message: []byte("not a value"),
+			wantErr: true,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			gotAddress, err := s.HashToAddress(tt.message)
+			if (err != nil) != tt.wantErr {
+				t.Errorf("AleoWrapper.HashToAddress() error = %v, wantErr %v", err, tt.wantErr)
+				return
+			}
+			if err != nil {
+				return
+			}
+			if len(gotAddress) != ADDRESS_SIZE || !strings.HasPrefix(gotAddress, "aleo1") {
+				t.Errorf("AleoWrapper.HashToAddress() = %v, want a valid address", gotAddress)
+			}
+
+			// the hash is deterministic
+			again, err := s.HashToAddress(tt.message)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if again != gotAddress {
+				t.Errorf("AleoWrapper.HashToAddress() = %v, then %v, want equal", gotAddress, again)
+			}
+		})
+	}
+
+	a, _ := s.HashToAddress([]byte("1u128"))
+	b, _ := s.HashToAddress([]byte("2u128"))
+	if a == b {
+		t.Error("AleoWrapper.HashToAddress() returned the same address for different inputs")
+	}
+
+	s.Close()
+
+	_, err = s.HashToAddress([]byte("1u128"))
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_MaxInputBytes(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name      string
+		operation uint32
+		want      uint64
+		wantErr   bool
+	}{
+		{name: "bhp256", operation: HASH_BHP256, want: UNBOUNDED_INPUT},
+		{name: "bhp512", operation: HASH_BHP512, want: UNBOUNDED_INPUT},
+		{name: "bhp768", operation: HASH_BHP768, want: UNBOUNDED_INPUT},
+		{name: "bhp1024", operation: HASH_BHP1024, want: UNBOUNDED_INPUT},
+		{name: "pedersen64", operation: HASH_PED64, want: 8},
+		{name: "pedersen128", operation: HASH_PED128, want: 16},
+		{name: "poseidon2", operation: HASH_PSD2, want: UNBOUNDED_INPUT},
+		{name: "poseidon4", operation: HASH_PSD4, want: UNBOUNDED_INPUT},
+		{name: "poseidon8", operation: HASH_PSD8, want: UNBOUNDED_INPUT},
+		{name: "keccak256", operation: HASH_KECCAK256, want: UNBOUNDED_INPUT},
+		{name: "sha3-512", operation: HASH_SHA3_512, want: UNBOUNDED_INPUT},
+		{name: "unknown", operation: 1000, wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.MaxInputBytes(tt.operation)
+			if (err != nil) != tt.wantErr {
+				t.Errorf("AleoWrapper.MaxInputBytes() error = %v, wantErr %v", err, tt.wantErr)
+				return
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.MaxInputBytes() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.MaxInputBytes(HASH_PED64)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_DeriveAddressRange(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	seed := make([]byte, SEED_SIZE)
+	for i := range seed {
+		seed[i] = byte(i)
+	}
+
+	const start, count = 5, 4
+	addresses, err := s.DeriveAddressRange(seed, start, count)
+	if err != nil {
+		t.Fatalf("AleoWrapper.DeriveAddressRange() error = %v", err)
+	}
+	if len(addresses) != count {
+		t.Fatalf("AleoWrapper.DeriveAddressRange() returned %d addresses, want %d", len(addresses), count)
+	}
+
+	// every entry of the range matches an individual derivation
+	for i, address := range addresses {
+		key, err := s.DeriveChildKey(seed, uint32(start+i))
+		if err != nil {
+			t.Fatalf("AleoWrapper.DeriveChildKey() error = %v", err)
+		}
+		want, err := s.GetAddress(key)
+		if err != nil {
+			t.Fatalf("AleoWrapper.GetAddress() error = %v", err)
+		}
+		if address != want {
+			t.Errorf("AleoWrapper.DeriveAddressRange()[%d] = %v, want %v", i, address, want)
+		}
+	}
+
+	if addresses[0] == addresses[1] {
+		t.Error("AleoWrapper.DeriveAddressRange() derived the same address for different indices")
+	}
+
+	if _, err := s.DeriveAddressRange(seed, 0, MAX_DERIVE_RANGE+1); err == nil {
+		t.Error("AleoWrapper.DeriveAddressRange() should reject ranges above the maximum")
+	}
+	if _, err := s.DeriveAddressRange(seed, ^uint32(0), 2); err == nil {
+		t.Error("AleoWrapper.DeriveAddressRange() should reject ranges overflowing the key index")
+	}
+	if _, err := s.DeriveChildKey(seed[:16], 0); err == nil {
+		t.Error("AleoWrapper.DeriveChildKey() should reject short seeds")
+	}
+
+	s.Close()
+
+	_, err = s.DeriveAddressRange(seed, 0, 1)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_RecordLeafHash(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name       string
+		commitment string
+		index      uint8
+		wantErr    bool
+	}{
+		{name: "zero", commitment: "0field", index: 0},
+		{name: "first output after one input", commitment: "1234567890field", index: 1},
+		{name: "same commitment, other index", commitment: "1234567890field", index: 2},
+		{name: "last index", commitment: "1234567890field", index: math.MaxUint8},
+		{name: "not a field", commitment: "1234567890u64", wantErr: true},
+		{name: "empty", commitment: "", wantErr: true},
+	}
+	hashes := make(map[string]string)
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.RecordLeafHash(tt.commitment, tt.index)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.RecordLeafHash() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if err != nil {
+				return
+			}
+			if !strings.HasSuffix(got, "field") || got == tt.commitment {
+				t.Errorf("AleoWrapper.RecordLeafHash() = %v, want a field different from the commitment", got)
+			}
+			if other, ok := hashes[got]; ok {
+				t.Errorf("AleoWrapper.RecordLeafHash() = %v for both %q and %q", got, other, tt.name)
+			}
+			hashes[got] = tt.name
+
+			again, err := s.RecordLeafHash(tt.commitment, tt.index)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if again != got {
+				t.Errorf("AleoWrapper.RecordLeafHash() = %v, then %v, want equal", got, again)
+			}
+		})
+	}
+
+	// Transition leaf indices are u8, the Go signature can't express larger ones
+	result, err := s.(*aleoWrapperSession).call("record_leaf_hash", []byte("0field"), uint32(256))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if result != 0 {
+		t.Error("record_leaf_hash should reject an index above 255")
+	}
+
+	s.Close()
+
+	_, err = s.RecordLeafHash("0field", 0)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_VerifySignature(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	_, otherAddress, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	formattedMessage, err := s.FormatMessage([]byte("test"), 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	message, err := s.HashMessage(formattedMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherMessage := append([]byte{}, message...)
+	otherMessage[0] ^= 1
+
+	signature, err := s.Sign(key, message)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name      string
+		address   string
+		signature string
+		message   []byte
+		want      bool
+	}{
+		{name: "valid", address: address, signature: signature, message: message, want: true},
+		{name: "different message", address: address, signature: signature, message: otherMessage, want: false},
+		{name: "different address", address: otherAddress, signature: signature, message: message, want: false},
+		{name: "invalid address", address: "aleo1invalid", signature: signature, message: message, want: false},
+		{name: "invalid signature", address: address, signature: "sign1invalid", message: message, want: false},
+		{name: "invalid message", address: address, signature: signature, message: nil, want: false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.VerifySignature(tt.address, tt.signature, tt.message)
+			if err != nil {
+				t.Fatalf("AleoWrapper.VerifySignature() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.VerifySignature() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.VerifySignature(address, signature, message)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_StringToFields(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name       string
+		str        string
+		wantFields int
+	}{
+		{name: "empty", str: "", wantFields: 1},
+		{name: "ascii", str: "hello aleo", wantFields: 2},
+		{name: "exactly one chunk", str: strings.Repeat("a", 31), wantFields: 2},
+		{name: "multibyte", str: "Zürich → 東京 🚀, more than one 31-byte chunk", wantFields: 3},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			fields, err := s.StringToFields(tt.str)
+			if err != nil {
+				t.Fatalf("AleoWrapper.StringToFields() error = %v", err)
+			}
+			if len(fields) != tt.wantFields {
+				t.Errorf("AleoWrapper.StringToFields() returned %d fields, want %d", len(fields), tt.wantFields)
+			}
+			for _, field := range fields {
+				if !strings.HasSuffix(field, "field") {
+					t.Errorf("AleoWrapper.StringToFields() returned %v, want a field literal", field)
+				}
+			}
+
+			got, err := s.FieldsToString(fields)
+			if err != nil {
+				t.Fatalf("AleoWrapper.FieldsToString() error = %v", err)
+			}
+			if got != tt.str {
+				t.Errorf("AleoWrapper.FieldsToString() = %v, want %v", got, tt.str)
+			}
+		})
+	}
+
+	if _, err := s.FieldsToString([]string{"2field"}); err == nil {
+		t.Error("AleoWrapper.FieldsToString() should reject a length without data fields")
+	}
+	if _, err := s.FieldsToString([]string{"1u8"}); err == nil {
+		t.Error("AleoWrapper.FieldsToString() should reject non-field values")
+	}
+
+	s.Close()
+
+	_, err = s.StringToFields("")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_FieldModulus(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	base, err := s.BaseFieldModulus()
+	if err != nil {
+		t.Fatalf("AleoWrapper.BaseFieldModulus() error = %v", err)
+	}
+	scalar, err := s.ScalarFieldModulus()
+	if err != nil {
+		t.Fatalf("AleoWrapper.ScalarFieldModulus() error = %v", err)
+	}
+
+	for name, modulus := range map[string]*big.Int{"base": base, "scalar": scalar} {
+		if modulus.Sign() <= 0 {
+			t.Errorf("%s field modulus = %v, want positive", name, modulus)
+		}
+		if !modulus.ProbablyPrime(20) {
+			t.Errorf("%s field modulus = %v, want a prime", name, modulus)
+		}
+	}
+	if scalar.Cmp(base) >= 0 {
+		t.Errorf("scalar field modulus %v should be smaller than the base field modulus %v", scalar, base)
+	}
+
+	s.Close()
+
+	_, err = s.BaseFieldModulus()
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+// testRecord returns a plaintext credits record owned by owner.
+func testRecord(owner string, microcredits uint64, nonce string) string {
+	return fmt.Sprintf("{ owner: %s.private, microcredits: %du64.private, _nonce: %s.public }", owner, microcredits, nonce)
+}
+
+// encryptRecord encrypts a plaintext record to its owner with the encrypt_record test export and
+// returns the ciphertext and the plaintext record with its fresh nonce. The export is only built
+// with the test-exports feature (./build.sh --test), so it isn't among the exports a session
+// resolves and is looked up here.
+func encryptRecord(tb testing.TB, s Session, record string) (ciphertext string, plaintext string) {
+	tb.Helper()
+
+	session := s.(*aleoWrapperSession)
+	fn := session.mod.ExportedFunction("encrypt_record")
+	if fn == nil {
+		tb.Fatal("missing wasm export: encrypt_record, build the module with ./build.sh --test")
+	}
+	session.exports["encrypt_record"] = fn
+	out, err := session.callOutput("encrypt_record", []byte(record))
+	if err != nil {
+		tb.Fatal(err)
+	}
+	items, err := decodeStringFrames(out)
+	if err != nil {
+		tb.Fatal(err)
+	}
+	if len(items) != 2 {
+		tb.Fatalf("encrypt_record: expected 2 items, got %d", len(items))
+	}
+
+	return items[0], items[1]
+}
+
+func TestAleoWrapper_ComputeRecordCommitment(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	record := testRecord(address, 1500000, "0group")
+
+	commitment, err := s.ComputeRecordCommitment(record, "credits.aleo", "credits")
+	if err != nil {
+		t.Fatalf("AleoWrapper.ComputeRecordCommitment() error = %v", err)
+	}
+	if !strings.HasSuffix(commitment, "field") {
+		t.Errorf("AleoWrapper.ComputeRecordCommitment() = %v, want a field", commitment)
+	}
+
+	again, err := s.ComputeRecordCommitment(record, "credits.aleo", "credits")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if again != commitment {
+		t.Errorf("AleoWrapper.ComputeRecordCommitment() = %v, then %v, want equal", commitment, again)
+	}
+
+	tests := []struct {
+		name       string
+		record     string
+		programID  string
+		recordName string
+		wantErr    bool
+	}{
+		{name: "different amount", record: testRecord(address, 1, "0group"), programID: "credits.aleo", recordName: "credits"},
+		{name: "different program", record: record, programID: "token.aleo", recordName: "credits"},
+		{name: "different record name", record: record, programID: "credits.aleo", recordName: "token"},
+		{name: "invalid record", record: "{ microcredits: 1u64.private }", programID: "credits.aleo", recordName: "credits", wantErr: true},
+		{name: "invalid program ID", record: record, programID: "credits", recordName: "credits", wantErr: true},
+		{name: "invalid record name", record: record, programID: "credits.aleo", recordName: "1credits", wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.ComputeRecordCommitment(tt.record, tt.programID, tt.recordName)
+			if (err != nil) != tt.wantErr {
+				t.Errorf("AleoWrapper.ComputeRecordCommitment() error = %v, wantErr %v", err, tt.wantErr)
+				return
+			}
+			if err == nil && got == commitment {
+				t.Errorf("AleoWrapper.ComputeRecordCommitment() = %v, want a different commitment", got)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.ComputeRecordCommitment(record, "credits.aleo", "credits")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ScanSession(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatalf("AleoWrapper.GetViewKey() error = %v", err)
+	}
+	if !strings.HasPrefix(string(viewKey), "AViewKey1") {
+		t.Fatalf("AleoWrapper.GetViewKey() = %s, want AViewKey1 prefix", viewKey)
+	}
+
+	scan, err := s.NewScanSession(viewKey)
+	if err != nil {
+		t.Fatalf("AleoWrapper.NewScanSession() error = %v", err)
+	}
+
+	tags := make(map[string]bool)
+	for i := 0; i < 10; i++ {
+		commitment := fmt.Sprintf("%dfield", i)
+
+		want, err := s.ComputeRecordTag(viewKey, commitment)
+		if err != nil {
+			t.Fatalf("AleoWrapper.ComputeRecordTag() error = %v", err)
+		}
+		got, err := scan.Tag(commitment)
+		if err != nil {
+			t.Fatalf("ScanSession.Tag() error = %v", err)
+		}
+		if got != want {
+			t.Errorf("ScanSession.Tag(%s) = %v, want %v", commitment, got, want)
+		}
+		tags[got] = true
+	}
+	if len(tags) != 10 {
+		t.Errorf("ScanSession.Tag() returned %d distinct tags for 10 commitments", len(tags))
+	}
+
+	if _, err := scan.Tag("not a field"); err == nil {
+		t.Error("ScanSession.Tag() should fail on an invalid commitment")
+	}
+	if _, err := s.NewScanSession([]byte("AViewKey1invalid")); err == nil {
+		t.Error("AleoWrapper.NewScanSession() should fail on an invalid view key")
+	}
+
+	if err := scan.Close(); err != nil {
+		t.Fatalf("ScanSession.Close() error = %v", err)
+	}
+	if err := scan.Close(); err != nil {
+		t.Fatalf("second ScanSession.Close() error = %v", err)
+	}
+	if _, err := scan.Tag("1field"); err == nil {
+		t.Error("ScanSession.Tag() should fail after the scan session was closed")
+	}
+
+	s.Close()
+
+	_, err = s.NewScanSession(viewKey)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func BenchmarkScanTags(b *testing.B) {
+	const commitmentCount = 10000
+
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		b.Fatal(err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		b.Fatal(err)
+	}
+	defer s.Close()
+
+	key, _, err := s.NewPrivateKey()
+	if err != nil {
+		b.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		b.Fatal(err)
+	}
+
+	commitments := make([]string, commitmentCount)
+	for i := range commitments {
+		commitments[i] = fmt.Sprintf("%dfield", i)
+	}
+
+	b.Run("per call", func(b *testing.B) {
+		for n := 0; n < b.N; n++ {
+			for _, commitment := range commitments {
+				if _, err := s.ComputeRecordTag(viewKey, commitment); err != nil {
+					b.Fatal(err)
+				}
+			}
+		}
+	})
+
+	b.Run("session", func(b *testing.B) {
+		for n := 0; n < b.N; n++ {
+			scan, err := s.NewScanSession(viewKey)
+			if err != nil {
+				b.Fatal(err)
+			}
+			for _, commitment := range commitments {
+				if _, err := scan.Tag(commitment); err != nil {
+					b.Fatal(err)
+				}
+			}
+			scan.Close()
+		}
+	})
+}
+
+func TestAleoWrapper_RecordMatchesCommitment(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	record := testRecord(address, 1500000, "0group")
+	commitment, err := s.ComputeRecordCommitment(record, "credits.aleo", "credits")
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherCommitment, err := s.ComputeRecordCommitment(testRecord(address, 1, "0group"), "credits.aleo", "credits")
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name       string
+		record     string
+		commitment string
+		want       bool
+		wantErr    bool
+	}{
+		{name: "matching", record: record, commitment: commitment, want: true},
+		{name: "mismatched", record: record, commitment: otherCommitment, want: false},
+		{name: "invalid record", record: "{ owner: aleo1invalid.private }", commitment: commitment, wantErr: true},
+		{name: "invalid commitment", record: record, commitment: "commitment", wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.RecordMatchesCommitment(tt.record, tt.commitment, "credits.aleo", "credits")
+			if (err != nil) != tt.wantErr {
+				t.Errorf("AleoWrapper.RecordMatchesCommitment() error = %v, wantErr %v", err, tt.wantErr)
+				return
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.RecordMatchesCommitment() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	// a mismatch following a parse failure must not pick up the stale error
+	if _, err := s.RecordMatchesCommitment(record, "commitment", "credits.aleo", "credits"); err == nil {
+		t.Fatal("AleoWrapper.RecordMatchesCommitment() should fail on an invalid commitment")
+	}
+	matches, err := s.RecordMatchesCommitment(record, otherCommitment, "credits.aleo", "credits")
+	if err != nil || matches {
+		t.Errorf("AleoWrapper.RecordMatchesCommitment() = %v, %v, want false, nil", matches, err)
+	}
+
+	s.Close()
+
+	_, err = s.RecordMatchesCommitment(record, commitment, "credits.aleo", "credits")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_SignatureBytes(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	formattedMessage, err := s.FormatMessage([]byte("test"), 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	message, err := s.HashMessage(formattedMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+	signature, err := s.Sign(key, message)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	signatureBytes, err := s.SignatureToBytes(signature)
+	if err != nil {
+		t.Fatalf("AleoWrapper.SignatureToBytes() error = %v", err)
+	}
+	if len(signatureBytes) != SIGNATURE_BYTES_SIZE {
+		t.Fatalf("AleoWrapper.SignatureToBytes() returned %d bytes, want %d", len(signatureBytes), SIGNATURE_BYTES_SIZE)
+	}
+
+	roundTrip, err := s.SignatureFromBytes(signatureBytes)
+	if err != nil {
+		t.Fatalf("AleoWrapper.SignatureFromBytes() error = %v", err)
+	}
+	if roundTrip != signature {
+		t.Errorf("AleoWrapper.SignatureFromBytes() = %v, want %v", roundTrip, signature)
+	}
+	valid, err := s.VerifySignature(address, roundTrip, message)
+	if err != nil || !valid {
+		t.Errorf("AleoWrapper.VerifySignature() = %v, %v on round tripped signature", valid, err)
+	}
+
+	if _, err := s.SignatureToBytes("sign1invalid"); err == nil {
+		t.Error("AleoWrapper.SignatureToBytes() should fail on an invalid signature")
+	}
+
+	tests := []struct {
+		name  string
+		input []byte
+	}{
+		{name: "empty", input: []byte{}},
+		{name: "short", input: signatureBytes[:SIGNATURE_BYTES_SIZE-1]},
+		{name: "long", input: append(append([]byte{}, signatureBytes...), 0)},
+		{name: "invalid encoding", input: bytes.Repeat([]byte{0xff}, SIGNATURE_BYTES_SIZE)},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, err := s.SignatureFromBytes(tt.input); err == nil {
+				t.Errorf("AleoWrapper.SignatureFromBytes() should fail on %s input", tt.name)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.SignatureToBytes(signature)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_VerifyAllSignatures(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	formattedMessage, err := s.FormatMessage([]byte("test"), 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	message, err := s.HashMessage(formattedMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	addresses := make([]string, 3)
+	signatures := make([]string, 3)
+	for i := range addresses {
+		key, address, err := s.NewPrivateKey()
+		if err != nil {
+			t.Fatal(err)
+		}
+		signature, err := s.Sign(key, message)
+		if err != nil {
+			t.Fatal(err)
+		}
+		addresses[i] = address
+		signatures[i] = signature
+	}
+	swapped := []string{signatures[1], signatures[0], signatures[2]}
+
+	tests := []struct {
+		name       string
+		addresses  []string
+		signatures []string
+		want       bool
+		wantErr    bool
+	}{
+		{name: "all valid", addresses: addresses, signatures: signatures, want: true},
+		{name: "single valid", addresses: addresses[:1], signatures: signatures[:1], want: true},
+		{name: "one invalid", addresses: addresses, signatures: swapped, want: false},
+		{name: "invalid signature", addresses: addresses[:1], signatures: []string{"sign1invalid"}, want: false},
+		{name: "count mismatch", addresses: addresses, signatures: signatures[:2], wantErr: true},
+		{name: "empty", addresses: []string{}, signatures: []string{}, wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.VerifyAllSignatures(message, tt.addresses, tt.signatures)
+			if (err != nil) != tt.wantErr {
+				t.Errorf("AleoWrapper.VerifyAllSignatures() error = %v, wantErr %v", err, tt.wantErr)
+				return
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.VerifyAllSignatures() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.VerifyAllSignatures(message, addresses, signatures)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_DeterministicSign(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKey, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	formattedMessage, err := s.FormatMessage([]byte("test"), 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	message, err := s.HashMessage(formattedMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherMessage := append([]byte{}, message...)
+	otherMessage[0] ^= 1
+
+	signature, err := s.DeterministicSign(key, message)
+	if err != nil {
+		t.Fatalf("AleoWrapper.DeterministicSign() error = %v", err)
+	}
+	again, err := s.DeterministicSign(key, message)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if again != signature {
+		t.Errorf("AleoWrapper.DeterministicSign() = %v, then %v, want identical signatures", signature, again)
+	}
+
+	valid, err := s.VerifySignature(address, signature, message)
+	if err != nil || !valid {
+		t.Errorf("AleoWrapper.VerifySignature() = %v, %v, want a valid deterministic signature", valid, err)
+	}
+
+	otherMessageSignature, err := s.DeterministicSign(key, otherMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKeySignature, err := s.DeterministicSign(otherKey, message)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if otherMessageSignature == signature || otherKeySignature == signature {
+		t.Error("AleoWrapper.DeterministicSign() should return different signatures for different messages and keys")
+	}
+
+	if _, err := s.DeterministicSign([]byte("APrivateKey1invalid"), message); err == nil {
+		t.Error("AleoWrapper.DeterministicSign() should fail on an invalid private key")
+	}
+	if _, err := s.DeterministicSign(key, []byte{1, 2, 3}); err == nil {
+		t.Error("AleoWrapper.DeterministicSign() should fail on a message that isn't a u128")
+	}
+
+	s.Close()
+
+	_, err = s.DeterministicSign(key, message)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ParseProgramInputs(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name      string
+		inputs    string
+		want      []string
+		wantErr   bool
+		wantToken string
+	}{
+		{
+			name:   "mixed literal types",
+			inputs: "5u64 " + address + " true -3i8 1field 2scalar",
+			want:   []string{"5u64", address, "true", "-3i8", "1field", "2scalar"},
+		},
+		{
+			name:   "commas and extra whitespace",
+			inputs: " 1u8,2u16 ,\t3u32\n",
+			want:   []string{"1u8", "2u16", "3u32"},
+		},
+		{
+			name:   "canonicalized",
+			inputs: "1_000u64",
+			want:   []string{"1000u64"},
+		},
+		{name: "unknown type", inputs: "5u64 5u65 true", wantErr: true, wantToken: "5u65"},
+		{name: "out of range", inputs: "256u8", wantErr: true, wantToken: "256u8"},
+		{name: "missing type", inputs: "true 42", wantErr: true, wantToken: "42"},
+		{name: "empty", inputs: " , ", wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.ParseProgramInputs(tt.inputs)
+			if (err != nil) != tt.wantErr {
+				t.Errorf("AleoWrapper.ParseProgramInputs() error = %v, wantErr %v", err, tt.wantErr)
+				return
+			}
+			if err != nil {
+				if !strings.Contains(err.Error(), tt.wantToken) {
+					t.Errorf("AleoWrapper.ParseProgramInputs() error = %v, want it to name %q", err, tt.wantToken)
+				}
+				return
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("AleoWrapper.ParseProgramInputs() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.ParseProgramInputs("5u64")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_DecimalToField(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	modulus, err := s.BaseFieldModulus()
+	if err != nil {
+		t.Fatal(err)
+	}
+	offset := func(multiple, delta int64) string {
+		value := new(big.Int).Mul(modulus, big.NewInt(multiple))
+		return value.Add(value, big.NewInt(delta)).String()
+	}
+	maxElement := offset(1, -1)
+
+	tests := []struct {
+		name    string
+		decimal string
+		want    string
+		wantErr bool
+	}{
+		{name: "zero", decimal: "0", want: "0field"},
+		{name: "small", decimal: "42", want: "42field"},
+		{name: "leading zeros", decimal: "0000000000000000000000042", want: "42field"},
+		{name: "u64 max", decimal: "18446744073709551615", want: "18446744073709551615field"},
+		{name: "modulus - 1", decimal: maxElement, want: maxElement + "field"},
+		{name: "modulus", decimal: offset(1, 0), want: "0field"},
+		{name: "modulus + 5", decimal: offset(1, 5), want: "5field"},
+		{name: "many times the modulus", decimal: offset(1000003, 7), want: "7field"},
+		{name: "empty", decimal: "", wantErr: true},
+		{name: "negative", decimal: "-1", wantErr: true},
+		{name: "suffix", decimal: "1field", wantErr: true},
+		{name: "non-numeric", decimal: "12a4", wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.DecimalToField(tt.decimal)
+			if (err != nil) != tt.wantErr {
+				t.Errorf("AleoWrapper.DecimalToField() error = %v, wantErr %v", err, tt.wantErr)
+				return
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.DecimalToField() = %v, want %v", got, tt.want)
+			}
+			if err != nil {
+				return
+			}
+
+			decimal, err := s.FieldToDecimal(got)
+			if err != nil {
+				t.Fatalf("AleoWrapper.FieldToDecimal() error = %v", err)
+			}
+			if decimal+"field" != got {
+				t.Errorf("AleoWrapper.FieldToDecimal() = %v, want %v", decimal, strings.TrimSuffix(got, "field"))
+			}
+		})
+	}
+
+	if _, err := s.FieldToDecimal("42"); err == nil {
+		t.Error("AleoWrapper.FieldToDecimal() should fail on a value without the field suffix")
+	}
+
+	s.Close()
+
+	_, err = s.DecimalToField("42")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ABIInfo(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	info, err := s.ABIInfo()
+	if err != nil {
+		t.Fatalf("AleoWrapper.ABIInfo() error = %v", err)
+	}
+	if info.AllocHeaderSize != 8 {
+		t.Errorf("AleoWrapper.ABIInfo().AllocHeaderSize = %d, want 8", info.AllocHeaderSize)
+	}
+	want := ABIInfo{PointerWidth: 32, LittleEndian: true, AllocHeaderSize: 8, PackedPtrShift: 0, PackedLenShift: 32}
+	if info != want {
+		t.Errorf("AleoWrapper.ABIInfo() = %+v, want %+v", info, want)
+	}
+
+	s.Close()
+
+	_, err = s.ABIInfo()
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ComputeTPK(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// the identity is its own x-coordinate preimage and stays the identity under any scalar
+	tpk, err := s.ComputeTPK(key, "0field")
+	if err != nil {
+		t.Fatalf("AleoWrapper.ComputeTPK() error = %v", err)
+	}
+	if tpk != "0group" {
+		t.Errorf("AleoWrapper.ComputeTPK() = %v, want 0group", tpk)
+	}
+
+	tests := []struct {
+		name string
+		key  []byte
+		tvk  string
+	}{
+		{name: "invalid private key", key: []byte("APrivateKey1invalid"), tvk: "0field"},
+		{name: "invalid tvk", key: key, tvk: "0group"},
+		{name: "empty tvk", key: key, tvk: ""},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, err := s.ComputeTPK(tt.key, tt.tvk); err == nil {
+				t.Errorf("AleoWrapper.ComputeTPK() should fail on %s", tt.name)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.ComputeTPK(key, "0field")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_CiphertextCommitment(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	record := testRecord(address, 1500000, "0group")
+
+	ciphertext, _ := encryptRecord(t, s, record)
+	otherCiphertext, _ := encryptRecord(t, s, record)
+
+	checksum, err := s.CiphertextCommitment(ciphertext)
+	if err != nil {
+		t.Fatalf("AleoWrapper.CiphertextCommitment() error = %v", err)
+	}
+	if !strings.HasSuffix(checksum, "field") {
+		t.Errorf("AleoWrapper.CiphertextCommitment() = %v, want a field", checksum)
+	}
+	again, err := s.CiphertextCommitment(ciphertext)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if again != checksum {
+		t.Errorf("AleoWrapper.CiphertextCommitment() = %v, then %v, want equal", checksum, again)
+	}
+	otherChecksum, err := s.CiphertextCommitment(otherCiphertext)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if otherChecksum == checksum {
+		t.Error("AleoWrapper.CiphertextCommitment() should differ for ciphertexts with different nonces")
+	}
+
+	for _, invalid := range []string{"", "record1invalid", plaintext} {
+		if _, err := s.CiphertextCommitment(invalid); err == nil {
+			t.Errorf("AleoWrapper.CiphertextCommitment(%q) should fail", invalid)
+		}
+	}
+
+	s.Close()
+
+	_, err = s.CiphertextCommitment(ciphertext)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+// leBytesToField converts little-endian bytes into a field literal.
+func leBytesToField(le []byte) string {
+	be := make([]byte, len(le))
+	for i, b := range le {
+		be[len(le)-1-i] = b
+	}
+
+	return new(big.Int).SetBytes(be).String() + "field"
+}
+
+func TestAleoWrapper_GetAddressExt(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tpks := make(map[string]bool)
+	for i := 0; i < 2; i++ {
+		key, address, err := s.NewPrivateKey()
+		if err != nil {
+			t.Fatal(err)
+		}
+
+		bech32, err := s.GetAddressExt(key, ADDRESS_FORMAT_BECH32)
+		if err != nil {
+			t.Fatalf("AleoWrapper.GetAddressExt(ADDRESS_FORMAT_BECH32) error = %v", err)
+		}
+		if string(bech32) != address {
+			t.Errorf("AleoWrapper.GetAddressExt(ADDRESS_FORMAT_BECH32) = %s, want %s", bech32, address)
+		}
+
+		raw, err := s.GetAddressExt(key, ADDRESS_FORMAT_RAW)
+		if err != nil {
+			t.Fatalf("AleoWrapper.GetAddressExt(ADDRESS_FORMAT_RAW) error = %v", err)
+		}
+		if len(raw) != ADDRESS_RAW_SIZE {
+			t.Fatalf("AleoWrapper.GetAddressExt(ADDRESS_FORMAT_RAW) returned %d bytes, want %d", len(raw), ADDRESS_RAW_SIZE)
+		}
+
+		// the raw address is the x-coordinate of G * view_key, so treating it as a tvk for the
+		// same key recovers the generator, whichever key is used
+		tpk, err := s.ComputeTPK(key, leBytesToField(raw))
+		if err != nil {
+			t.Fatalf("AleoWrapper.ComputeTPK() error = %v", err)
+		}
+		tpks[tpk] = true
+
+		if _, err := s.GetAddressExt(key, 2); err == nil {
+			t.Error("AleoWrapper.GetAddressExt() should fail on an unknown format")
+		}
+	}
+	if len(tpks) != 1 {
+		t.Errorf("raw addresses of different keys should map to the same generator, got %v", tpks)
+	}
+
+	if _, err := s.GetAddressExt([]byte("APrivateKey1invalid"), ADDRESS_FORMAT_RAW); err == nil {
+		t.Error("AleoWrapper.GetAddressExt() should fail on an invalid private key")
+	}
+
+	s.Close()
+
+	_, err = s.GetAddressExt([]byte("APrivateKey1invalid"), ADDRESS_FORMAT_BECH32)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_HashMulti(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	input := []byte("abc")
+
+	// standard test vectors of the byte-oriented hashes
+	vectors := map[uint32]string{
+		HASH_KECCAK256: "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+		HASH_SHA3_256:  "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+	}
+	for operation, want := range vectors {
+		digest, err := s.HashBytes(operation, input)
+		if err != nil {
+			t.Fatalf("AleoWrapper.HashBytes(%d) error = %v", operation, err)
+		}
+		if got := hex.EncodeToString(digest); got != want {
+			t.Errorf("AleoWrapper.HashBytes(%d) = %v, want %v", operation, got, want)
+		}
+	}
+
+	operations := []uint32{
+		HASH_KECCAK256, HASH_SHA3_256, HASH_KECCAK384, HASH_KECCAK512, HASH_SHA3_384, HASH_SHA3_512,
+		HASH_BHP256, HASH_BHP512, HASH_BHP768, HASH_BHP1024, HASH_PED64, HASH_PED128,
+		HASH_PSD2, HASH_PSD4, HASH_PSD8, HASH_KECCAK256,
+	}
+	digestSizes := map[uint32]int{HASH_KECCAK384: 48, HASH_SHA3_384: 48, HASH_KECCAK512: 64, HASH_SHA3_512: 64}
+
+	digests, err := s.HashMulti(operations, input)
+	if err != nil {
+		t.Fatalf("AleoWrapper.HashMulti() error = %v", err)
+	}
+	if len(digests) != len(operations) {
+		t.Fatalf("AleoWrapper.HashMulti() returned %d digests, want %d", len(digests), len(operations))
+	}
+	for i, operation := range operations {
+		want, err := s.HashBytes(operation, input)
+		if err != nil {
+			t.Fatalf("AleoWrapper.HashBytes(%d) error = %v", operation, err)
+		}
+		if !bytes.Equal(digests[i], want) {
+			t.Errorf("AleoWrapper.HashMulti() digest %d = %x, want %x", i, digests[i], want)
+		}
+
+		wantSize, ok := digestSizes[operation]
+		if !ok {
+			wantSize = 32
+		}
+		if len(digests[i]) != wantSize {
+			t.Errorf("AleoWrapper.HashMulti() digest %d is %d bytes long, want %d", i, len(digests[i]), wantSize)
+		}
+	}
+
+	tests := []struct {
+		name       string
+		operations []uint32
+		input      []byte
+	}{
+		{name: "no operations", operations: []uint32{}, input: input},
+		{name: "unknown operation", operations: []uint32{HASH_SHA3_256, 1000}, input: input},
+		{name: "oversized pedersen input", operations: []uint32{HASH_SHA3_256, HASH_PED64}, input: make([]byte, 9)},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, err := s.HashMulti(tt.operations, tt.input); err == nil {
+				t.Errorf("AleoWrapper.HashMulti() should fail on %s", tt.name)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.HashMulti(operations, input)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_FreeSecret(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+	ws := s.(*aleoWrapperSession)
+
+	before, err := s.AllocationCount()
+	if err != nil {
+		t.Fatalf("AleoWrapper.AllocationCount() error = %v", err)
+	}
+
+	packed, err := ws.call("new_private_key")
+	if err != nil || packed == 0 {
+		t.Fatalf("new_private_key = %d, %v", packed, err)
+	}
+	ptr, length := decodeLenPtr(packed)
+	if length != PRIVATE_KEY_SIZE {
+		t.Fatalf("new_private_key returned %d bytes, want %d", length, PRIVATE_KEY_SIZE)
+	}
+
+	allocated, err := s.AllocationCount()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if allocated != before+1 {
+		t.Errorf("AleoWrapper.AllocationCount() = %d after new_private_key, want %d", allocated, before+1)
+	}
+
+	if _, err := ws.call("free_secret", packed); err != nil {
+		t.Fatalf("free_secret error = %v", err)
+	}
+
+	// the allocator keeps its free list links in the first bytes of a freed block, everything
+	// after them must have been zeroized
+	freed, ok := ws.mod.Memory().Read(ptr, length)
+	if !ok {
+		t.Fatal("failed to read freed memory")
+	}
+	if !bytes.Equal(freed[16:], make([]byte, len(freed)-16)) {
+		t.Errorf("freed private key memory wasn't zeroized: %x", freed)
+	}
+
+	after, err := s.AllocationCount()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if after != before {
+		t.Errorf("AleoWrapper.AllocationCount() = %d after free_secret, want %d", after, before)
+	}
+
+	// sessions release secrets through free_secret too
+	if _, _, err := s.NewPrivateKey(); err != nil {
+		t.Fatal(err)
+	}
+	if after, err = s.AllocationCount(); err != nil || after != before {
+		t.Errorf("AleoWrapper.AllocationCount() = %d, %v after NewPrivateKey, want %d", after, err, before)
+	}
+
+	s.Close()
+
+	_, err = s.AllocationCount()
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_AddressFromComponents(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	pkSig, prSig, skPrf, err := s.GetComputeKey(key)
+	if err != nil {
+		t.Fatalf("AleoWrapper.GetComputeKey() error = %v", err)
+	}
+
+	got, err := s.AddressFromComponents(pkSig, prSig, skPrf)
+	if err != nil {
+		t.Fatalf("AleoWrapper.AddressFromComponents() error = %v", err)
+	}
+	if got != address {
+		t.Errorf("AleoWrapper.AddressFromComponents() = %v, want %v", got, address)
+	}
+
+	otherKey, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherPkSig, _, otherSkPrf, err := s.GetComputeKey(otherKey)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name  string
+		pkSig string
+		prSig string
+		skPrf string
+	}{
+		{name: "mixed keys", pkSig: otherPkSig, prSig: prSig, skPrf: skPrf},
+		{name: "mismatched sk_prf", pkSig: pkSig, prSig: prSig, skPrf: otherSkPrf},
+		{name: "invalid pk_sig", pkSig: "1field", prSig: prSig, skPrf: skPrf},
+		{name: "invalid pr_sig", pkSig: pkSig, prSig: "", skPrf: skPrf},
+		{name: "invalid sk_prf", pkSig: pkSig, prSig: prSig, skPrf: pkSig},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, err := s.AddressFromComponents(tt.pkSig, tt.prSig, tt.skPrf); err == nil {
+				t.Errorf("AleoWrapper.AddressFromComponents() should fail on %s", tt.name)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.AddressFromComponents(pkSig, prSig, skPrf)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_SignFields(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	_, otherAddress, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	fields := []string{"1field", "2field", "3field", "4field", "5field"}
+	signature, err := s.SignFields(key, fields)
+	if err != nil {
+		t.Fatalf("AleoWrapper.SignFields() error = %v", err)
+	}
+
+	tampered := append([]string{}, fields...)
+	tampered[2] = "33field"
+
+	tests := []struct {
+		name      string
+		address   string
+		signature string
+		fields    []string
+		want      bool
+	}{
+		{name: "round trip", address: address, signature: signature, fields: fields, want: true},
+		{name: "tampered field", address: address, signature: signature, fields: tampered, want: false},
+		{name: "truncated message", address: address, signature: signature, fields: fields[:4], want: false},
+		{name: "other address", address: otherAddress, signature: signature, fields: fields, want: false},
+		{name: "invalid field", address: address, signature: signature, fields: []string{"1u8"}, want: false},
+		{name: "invalid signature", address: address, signature: "sign1invalid", fields: fields, want: false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.VerifySignatureFields(tt.address, tt.signature, tt.fields)
+			if err != nil {
+				t.Fatalf("AleoWrapper.VerifySignatureFields() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.VerifySignatureFields() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	if _, err := s.SignFields(key, []string{"1field", "invalid"}); err == nil {
+		t.Error("AleoWrapper.SignFields() should fail on an invalid field")
+	}
+
+	s.Close()
+
+	_, err = s.SignFields(key, fields)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_RecordOwner(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name    string
+		record  string
+		want    string
+		wantErr error
+	}{
+		{name: "credits record", record: testRecord(address, 1500000, "0group"), want: address},
+		{
+			name:   "public owner",
+			record: fmt.Sprintf("{ owner: %s.public, microcredits: 5u64.private, _nonce: 0group.public }", address),
+			want:   address,
+		},
+		{
+			name:    "no owner",
+			record:  "{ microcredits: 5u64.private, _nonce: 0group.public }",
+			wantErr: ErrRecordNoOwner,
+		},
+		{
+			name:    "owner only in a longer name",
+			record:  "{ previous_owner: 1u8.private, _nonce: 0group.public }",
+			wantErr: ErrRecordNoOwner,
+		},
+		{
+			name:    "owner only in a nested struct",
+			record:  fmt.Sprintf("{ data: { owner: %s.private }, _nonce: 0group.public }", address),
+			wantErr: ErrRecordNoOwner,
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.RecordOwner(tt.record)
+			if !errors.Is(err, tt.wantErr) {
+				t.Errorf("AleoWrapper.RecordOwner() error = %v, wantErr %v", err, tt.wantErr)
+				return
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.RecordOwner() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	_, err = s.RecordOwner("{ owner: aleo1invalid.private, _nonce: 0group.public }")
+	if err == nil || errors.Is(err, ErrRecordNoOwner) {
+		t.Errorf("AleoWrapper.RecordOwner() error = %v, want a parse error", err)
+	}
+
+	s.Close()
+
+	_, err = s.RecordOwner(testRecord(address, 1, "0group"))
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_EnableBufferPool(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	if err := s.EnableBufferPool(true); err != nil {
+		t.Fatalf("AleoWrapper.EnableBufferPool() error = %v", err)
+	}
+	// warm up the pool, after that repeated calls of the same size reuse its buffers
+	for i := 0; i < 2; i++ {
+		if _, err := s.GetAddress(key); err != nil {
+			t.Fatal(err)
+		}
+	}
+	before, err := s.HeapAllocationCount()
+	if err != nil {
+		t.Fatalf("AleoWrapper.HeapAllocationCount() error = %v", err)
+	}
+	for i := 0; i < 10; i++ {
+		got, err := s.GetAddress(key)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if got != address {
+			t.Fatalf("AleoWrapper.GetAddress() = %v with the buffer pool, want %v", got, address)
+		}
+	}
+	after, err := s.HeapAllocationCount()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if after != before {
+		t.Errorf("AleoWrapper.HeapAllocationCount() grew from %d to %d with a warm buffer pool", before, after)
+	}
+
+	if err := s.EnableBufferPool(false); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := s.GetAddress(key); err != nil {
+		t.Fatal(err)
+	}
+	if after, err = s.HeapAllocationCount(); err != nil || after <= before {
+		t.Errorf("AleoWrapper.HeapAllocationCount() = %d, %v with the pool off, want > %d", after, err, before)
+	}
+
+	s.Close()
+
+	err = s.EnableBufferPool(true)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func BenchmarkSignBufferPool(b *testing.B) {
+	for _, pooled := range []bool{false, true} {
+		b.Run(fmt.Sprintf("pool=%v", pooled), func(b *testing.B) {
+			wrapper, closeFn, err := NewWrapper()
+			if err != nil {
+				b.Fatal(err)
+			}
+			defer closeFn()
+
+			s, err := wrapper.NewSession()
+			if err != nil {
+				b.Fatal(err)
+			}
+			defer s.Close()
+
+			key, _, err := s.NewPrivateKey()
+			if err != nil {
+				b.Fatal(err)
+			}
+			formattedMessage, err := s.FormatMessage([]byte("benchmark"), 1)
+			if err != nil {
+				b.Fatal(err)
+			}
+			message, err := s.HashMessage(formattedMessage)
+			if err != nil {
+				b.Fatal(err)
+			}
+			if err := s.EnableBufferPool(pooled); err != nil {
+				b.Fatal(err)
+			}
+
+			before, err := s.HeapAllocationCount()
+			if err != nil {
+				b.Fatal(err)
+			}
+			b.ResetTimer()
+			for n := 0; n < b.N; n++ {
+				if _, err := s.Sign(key, message); err != nil {
+					b.Fatal(err)
+				}
+			}
+			b.StopTimer()
+
+			after, err := s.HeapAllocationCount()
+			if err != nil {
+				b.Fatal(err)
+			}
+			b.ReportMetric(float64(after-before)/float64(b.N), "wasm-allocs/op")
+		})
+	}
+}
+
+func TestAleoWrapper_FieldCountForBytes(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		byteLen uint32
+		want    uint64
+	}{
+		{byteLen: 0, want: 1},
+		{byteLen: 1, want: 2},
+		{byteLen: 30, want: 2},
+		{byteLen: 31, want: 2},
+		{byteLen: 32, want: 3},
+		{byteLen: 62, want: 3},
+		{byteLen: 63, want: 4},
+		{byteLen: 310, want: 11},
+	}
+	for _, tt := range tests {
+		t.Run(fmt.Sprint(tt.byteLen), func(t *testing.T) {
+			got, err := s.FieldCountForBytes(tt.byteLen)
+			if err != nil {
+				t.Fatalf("AleoWrapper.FieldCountForBytes() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.FieldCountForBytes(%d) = %d, want %d", tt.byteLen, got, tt.want)
+			}
+
+			fields, err := s.StringToFields(strings.Repeat("a", int(tt.byteLen)))
+			if err != nil {
+				t.Fatal(err)
+			}
+			if uint64(len(fields)) != got {
+				t.Errorf("AleoWrapper.StringToFields() returned %d fields, FieldCountForBytes %d", len(fields), got)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.FieldCountForBytes(1)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_SignMessageWithNonce(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	formattedMessage, err := s.FormatMessage([]byte("test"), 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	message, err := s.HashMessage(formattedMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	nonceA := []byte("nonce-a")
+	nonceB := []byte("nonce-b")
+
+	signatureA, err := s.SignMessageWithNonce(key, message, nonceA)
+	if err != nil {
+		t.Fatalf("AleoWrapper.SignMessageWithNonce() error = %v", err)
+	}
+	signatureB, err := s.SignMessageWithNonce(key, message, nonceB)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name      string
+		signature string
+		nonce     []byte
+		want      bool
+	}{
+		{name: "nonce A", signature: signatureA, nonce: nonceA, want: true},
+		{name: "nonce B", signature: signatureB, nonce: nonceB, want: true},
+		{name: "A under B", signature: signatureA, nonce: nonceB, want: false},
+		{name: "B under A", signature: signatureB, nonce: nonceA, want: false},
+		{name: "empty nonce", signature: signatureA, nonce: nil, want: false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			valid, err := s.VerifySignatureWithNonce(address, tt.signature, message, tt.nonce)
+			if err != nil {
+				t.Fatalf("AleoWrapper.VerifySignatureWithNonce() error = %v", err)
+			}
+			if valid != tt.want {
+				t.Errorf("AleoWrapper.VerifySignatureWithNonce() = %v, want %v", valid, tt.want)
+			}
+		})
+	}
+
+	valid, err := s.VerifySignature(address, signatureA, message)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if valid {
+		t.Error("AleoWrapper.VerifySignature() should reject a signature bound to a nonce")
+	}
+
+	if _, err := s.SignMessageWithNonce(key, []byte{1, 2, 3}, nonceA); err == nil {
+		t.Error("AleoWrapper.SignMessageWithNonce() should fail on a message that isn't a u128")
+	}
+
+	s.Close()
+
+	_, err = s.SignMessageWithNonce(key, message, nonceA)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ValueToBits(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// The literal's own bits come last, after the plaintext type header
+	tests := []struct {
+		name         string
+		littleEndian bool
+		want         []byte
+	}{
+		{name: "little endian", littleEndian: true, want: []byte{1, 0, 1, 1, 0, 0, 0, 0}},
+		{name: "big endian", littleEndian: false, want: []byte{0, 0, 0, 0, 1, 1, 0, 1}},
+	}
+	lengths := map[int]bool{}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			bits, err := s.ValueToBits("13u8", tt.littleEndian)
+			if err != nil {
+				t.Fatalf("AleoWrapper.ValueToBits() error = %v", err)
+			}
+			if len(bits) < len(tt.want) {
+				t.Fatalf("AleoWrapper.ValueToBits() returned %d bits, want at least %d", len(bits), len(tt.want))
+			}
+			for i, bit := range bits {
+				if bit > 1 {
+					t.Fatalf("AleoWrapper.ValueToBits() bit %d = %d, want 0 or 1", i, bit)
+				}
+			}
+			if got := bits[len(bits)-len(tt.want):]; !bytes.Equal(got, tt.want) {
+				t.Errorf("AleoWrapper.ValueToBits() value bits = %v, want %v", got, tt.want)
+			}
+			lengths[len(bits)] = true
+		})
+	}
+	if len(lengths) != 1 {
+		t.Errorf("AleoWrapper.ValueToBits() returned different lengths per endianness: %v", lengths)
+	}
+
+	if _, err := s.ValueToBits("13", true); err == nil {
+		t.Error("AleoWrapper.ValueToBits() should fail on a literal without a type")
+	}
+
+	s.Close()
+
+	_, err = s.ValueToBits("13u8", true)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_NormalizeProgramID(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name      string
+		programID string
+		want      string
+		wantErr   bool
+	}{
+		{name: "valid", programID: "token.aleo", want: "token.aleo"},
+		{name: "underscores and digits", programID: "credits_v2.aleo", want: "credits_v2.aleo"},
+		{name: "missing suffix", programID: "token", wantErr: true},
+		{name: "wrong suffix", programID: "token.eth", wantErr: true},
+		{name: "illegal characters", programID: "tok-en.aleo", wantErr: true},
+		{name: "leading digit", programID: "1token.aleo", wantErr: true},
+		{name: "empty", programID: "", wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.NormalizeProgramID(tt.programID)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.NormalizeProgramID() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.NormalizeProgramID() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.NormalizeProgramID("token.aleo")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_HKDFDerive(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// Inputs of the RFC 5869 test cases 1 and 3, with SHA3-256 in place of SHA-256
+	ikm := bytes.Repeat([]byte{0x0b}, 22)
+	salt, _ := hex.DecodeString("000102030405060708090a0b0c")
+	info, _ := hex.DecodeString("f0f1f2f3f4f5f6f7f8f9")
+
+	tests := []struct {
+		name    string
+		salt    []byte
+		info    []byte
+		outLen  uint32
+		want    string
+		wantErr bool
+	}{
+		{
+			name:   "salt and info",
+			salt:   salt,
+			info:   info,
+			outLen: 42,
+			want:   "0c5160501d65021deaf2c14f5abce04c5bd2635abceeba61c2edb6e8ed72674900557728f2c9f2c4c179",
+		},
+		{
+			name:   "empty salt and info",
+			outLen: 42,
+			want:   "bc1342cdd75c05e8b0c3ae609ce4410684d197232875073499b30cdfe2de2853c1c1bed63d725e885e78",
+		},
+		{
+			name:   "truncated",
+			salt:   salt,
+			info:   info,
+			outLen: 16,
+			want:   "0c5160501d65021deaf2c14f5abce04c",
+		},
+		{name: "zero length", outLen: 0, wantErr: true},
+		{name: "too long", outLen: 255*32 + 1, wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.HKDFDerive(ikm, tt.salt, tt.info, tt.outLen)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.HKDFDerive() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if tt.wantErr {
+				return
+			}
+			if hex.EncodeToString(got) != tt.want {
+				t.Errorf("AleoWrapper.HKDFDerive() = %x, want %v", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.HKDFDerive(ikm, salt, info, 32)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_SignatureInspect(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	formattedMessage, err := s.FormatMessage([]byte("test"), 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	message, err := s.HashMessage(formattedMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	signature, err := s.Sign(key, message)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	signer, challenge, err := s.SignatureInspect(signature)
+	if err != nil {
+		t.Fatalf("AleoWrapper.SignatureInspect() error = %v", err)
+	}
+	if signer != address {
+		t.Errorf("AleoWrapper.SignatureInspect() address = %v, want %v", signer, address)
+	}
+	if !strings.HasSuffix(challenge, "scalar") {
+		t.Errorf("AleoWrapper.SignatureInspect() challenge = %v, want a scalar literal", challenge)
+	}
+
+	if _, _, err := s.SignatureInspect("sign1invalid"); err == nil {
+		t.Error("AleoWrapper.SignatureInspect() should fail on an invalid signature")
+	}
+
+	s.Close()
+
+	_, _, err = s.SignatureInspect(signature)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_SetMaxAlloc(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	before, err := s.AllocationCount()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	if err := s.SetMaxAlloc(64); err != nil {
+		t.Fatalf("AleoWrapper.SetMaxAlloc() error = %v", err)
+	}
+	if _, err := s.StringToFields(strings.Repeat("a", 64)); err != nil {
+		t.Errorf("AleoWrapper.StringToFields() error = %v, want an argument at the limit to be accepted", err)
+	}
+	if _, err := s.StringToFields(strings.Repeat("a", 65)); err == nil {
+		t.Error("AleoWrapper.StringToFields() should fail on an argument over the allocation limit")
+	}
+	message, err := s.LastError()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !strings.Contains(message, "set_max_alloc") {
+		t.Errorf("AleoWrapper.LastError() = %q, want the allocation limit error", message)
+	}
+
+	if err := s.SetMaxAlloc(0); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := s.StringToFields(strings.Repeat("a", 65)); err != nil {
+		t.Errorf("AleoWrapper.StringToFields() error = %v, want no limit after SetMaxAlloc(0)", err)
+	}
+
+	after, err := s.AllocationCount()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if after != before {
+		t.Errorf("AleoWrapper.AllocationCount() = %d, want %d", after, before)
+	}
+
+	s.Close()
+
+	err = s.SetMaxAlloc(64)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_VerifyAddressChecksum(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	corrupted := []byte(address)
+	if corrupted[len(corrupted)-1] == 'q' {
+		corrupted[len(corrupted)-1] = 'p'
+	} else {
+		corrupted[len(corrupted)-1] = 'q'
+	}
+
+	tests := []struct {
+		name    string
+		address string
+		wantErr error
+	}{
+		{name: "generated address", address: address},
+		{name: "known address", address: "aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px"},
+		{name: "uppercase address", address: strings.ToUpper(address)},
+		{name: "bad checksum", address: string(corrupted), wantErr: ErrAddressChecksum},
+		{name: "mixed case", address: "A" + address[1:], wantErr: ErrAddressChecksum},
+		{name: "no separator", address: "aleoqqqqqqqq", wantErr: ErrAddressChecksum},
+		// BIP 350 bech32m test vector
+		{name: "other human-readable part", address: "split1checkupstagehandshakeupstreamerranterredcaperredlc445v", wantErr: ErrAddressHRP},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			err := s.VerifyAddressChecksum(tt.address)
+			if !errors.Is(err, tt.wantErr) {
+				t.Errorf("AleoWrapper.VerifyAddressChecksum() error = %v, want %v", err, tt.wantErr)
+			}
+		})
+	}
+
+	s.Close()
+
+	err = s.VerifyAddressChecksum(address)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_PrivateKeyComponents(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	seed := bytes.Repeat([]byte{7}, 32)
+	key, err := s.DeriveChildKey(seed, 0)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKey, err := s.DeriveChildKey(seed, 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	skSig, rSig, err := s.PrivateKeyComponents(key)
+	if err != nil {
+		t.Fatalf("AleoWrapper.PrivateKeyComponents() error = %v", err)
+	}
+	for _, component := range []string{skSig, rSig} {
+		if !strings.HasSuffix(component, "scalar") {
+			t.Errorf("AleoWrapper.PrivateKeyComponents() component = %v, want a scalar literal", component)
+		}
+	}
+	if skSig == rSig {
+		t.Error("AleoWrapper.PrivateKeyComponents() returned the same scalar for sk_sig and r_sig")
+	}
+
+	// The components are derived from the key seed, so they're the same for the same key and
+	// differ for a different one
+	againSkSig, againRSig, err := s.PrivateKeyComponents(key)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if againSkSig != skSig || againRSig != rSig {
+		t.Error("AleoWrapper.PrivateKeyComponents() should return the same components for the same key")
+	}
+	otherSkSig, otherRSig, err := s.PrivateKeyComponents(otherKey)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if otherSkSig == skSig || otherRSig == rSig {
+		t.Error("AleoWrapper.PrivateKeyComponents() should return different components for different keys")
+	}
+	if _, _, err := s.PrivateKeyComponents([]byte("APrivateKey1invalid")); err == nil {
+		t.Error("AleoWrapper.PrivateKeyComponents() should fail on an invalid private key")
+	}
+
+	s.Close()
+
+	_, _, err = s.PrivateKeyComponents(key)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_NewPrivateKeys(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	seed := bytes.Repeat([]byte{3}, SEED_SIZE)
+	seeded, err := s.NewPrivateKeys(5, seed)
+	if err != nil {
+		t.Fatalf("AleoWrapper.NewPrivateKeys() error = %v", err)
+	}
+	again, err := s.NewPrivateKeys(5, seed)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !reflect.DeepEqual(seeded, again) {
+		t.Error("AleoWrapper.NewPrivateKeys() should return the same keys for the same seed")
+	}
+
+	random, err := s.NewPrivateKeys(5, nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, keys := range [][][]byte{seeded, random} {
+		if len(keys) != 5 {
+			t.Fatalf("AleoWrapper.NewPrivateKeys() returned %d keys, want 5", len(keys))
+		}
+		seen := map[string]bool{}
+		for _, key := range keys {
+			if !strings.HasPrefix(string(key), "APrivateKey1") {
+				t.Errorf("AleoWrapper.NewPrivateKeys() key = %s, want a private key", key)
+			}
+			if _, err := s.GetAddress(key); err != nil {
+				t.Errorf("AleoWrapper.GetAddress() error = %v", err)
+			}
+			seen[string(key)] = true
+		}
+		if len(seen) != len(keys) {
+			t.Errorf("AleoWrapper.NewPrivateKeys() returned %d distinct keys, want %d", len(seen), len(keys))
+		}
+	}
+
+	for _, tt := range []struct {
+		name  string
+		count uint32
+		seed  []byte
+	}{
+		{name: "zero count", count: 0},
+		{name: "count over the limit", count: MAX_NEW_KEYS + 1},
+		{name: "short seed", count: 1, seed: []byte{1, 2, 3}},
+	} {
+		if _, err := s.NewPrivateKeys(tt.count, tt.seed); err == nil {
+			t.Errorf("AleoWrapper.NewPrivateKeys() should fail on %s", tt.name)
+		}
+	}
+
+	s.Close()
+
+	_, err = s.NewPrivateKeys(1, nil)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_SumRecordBalances(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	tokenRecord := fmt.Sprintf("{ owner: %s.private, amount: 100u64.private, _nonce: 0group.public }", address)
+
+	tests := []struct {
+		name    string
+		records []string
+		want    uint64
+		wantErr bool
+	}{
+		{
+			name:    "several records",
+			records: []string{testRecord(address, 1500000, "0group"), testRecord(address, 250, "0group"), testRecord(address, 1, "0group")},
+			want:    1500251,
+		},
+		{
+			name:    "public balance",
+			records: []string{fmt.Sprintf("{ owner: %s.public, microcredits: 42u64.public, _nonce: 0group.public }", address)},
+			want:    42,
+		},
+		{name: "skips other records", records: []string{tokenRecord, testRecord(address, 7, "0group")}, want: 7},
+		{name: "no records", records: nil, want: 0},
+		{
+			name:    "overflow",
+			records: []string{testRecord(address, math.MaxUint64, "0group"), testRecord(address, 1, "0group")},
+			wantErr: true,
+		},
+		{
+			name:    "microcredits not a u64",
+			records: []string{fmt.Sprintf("{ owner: %s.private, microcredits: 5u32.private, _nonce: 0group.public }", address)},
+			wantErr: true,
+		},
+		{name: "malformed record", records: []string{"{ owner: }"}, wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.SumRecordBalances(tt.records)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.SumRecordBalances() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.SumRecordBalances() = %d, want %d", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.SumRecordBalances([]string{testRecord(address, 1, "0group")})
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ComputeFeeCommitment(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	commitment, err := s.ComputeFeeCommitment(1500000, key, "0group")
+	if err != nil {
+		t.Fatalf("AleoWrapper.ComputeFeeCommitment() error = %v", err)
+	}
+	again, err := s.ComputeFeeCommitment(1500000, key, "0group")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if again != commitment {
+		t.Errorf("AleoWrapper.ComputeFeeCommitment() = %v, then %v, want a stable commitment", commitment, again)
+	}
+
+	want, err := s.ComputeRecordCommitment(testRecord(address, 1500000, "0group"), "credits.aleo", "credits")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if commitment != want {
+		t.Errorf("AleoWrapper.ComputeFeeCommitment() = %v, want the credits record commitment %v", commitment, want)
+	}
+
+	otherAmount, err := s.ComputeFeeCommitment(1, key, "0group")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if otherAmount == commitment {
+		t.Error("AleoWrapper.ComputeFeeCommitment() should return different commitments for different amounts")
+	}
+
+	if _, err := s.ComputeFeeCommitment(1, key, "1field"); err == nil {
+		t.Error("AleoWrapper.ComputeFeeCommitment() should fail on a nonce that isn't a group element")
+	}
+	if _, err := s.ComputeFeeCommitment(1, []byte("APrivateKey1invalid"), "0group"); err == nil {
+		t.Error("AleoWrapper.ComputeFeeCommitment() should fail on an invalid private key")
+	}
+
+	s.Close()
+
+	_, err = s.ComputeFeeCommitment(1, key, "0group")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ValidateValueType(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name         string
+		value        string
+		expectedType string
+		wantErr      string
+	}{
+		{name: "matching literal", value: "5u64", expectedType: "u64"},
+		{name: "matching field", value: "1field", expectedType: "field"},
+		{name: "matching array", value: "[1u8, 2u8]", expectedType: "[u8; 2u32]"},
+		{name: "struct", value: "{ amount: 5u64 }", expectedType: "MyStruct"},
+		{name: "mismatching literal", value: "5u32", expectedType: "u64", wantErr: "expected a u64 literal, found a u32 literal"},
+		{name: "literal for a struct", value: "5u64", expectedType: "MyStruct", wantErr: "found a literal"},
+		{name: "array length", value: "[1u8]", expectedType: "[u8; 2u32]", wantErr: "expected an array of 2 elements"},
+		{name: "array element", value: "[1u8, 2u16]", expectedType: "[u8; 2u32]", wantErr: "expected a u8 literal"},
+		{name: "invalid value", value: "5", expectedType: "u64", wantErr: "failed to parse value"},
+		{name: "invalid type", value: "5u64", expectedType: "1u64", wantErr: "failed to parse expected type"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			err := s.ValidateValueType(tt.value, tt.expectedType)
+			if tt.wantErr == "" {
+				if err != nil {
+					t.Errorf("AleoWrapper.ValidateValueType() error = %v", err)
+				}
+				return
+			}
+			if err == nil || !strings.Contains(err.Error(), tt.wantErr) {
+				t.Errorf("AleoWrapper.ValidateValueType() error = %v, want it to contain %q", err, tt.wantErr)
+			}
+		})
+	}
+
+	s.Close()
+
+	err = s.ValidateValueType("5u64", "u64")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ViewKeySecret(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKey, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherViewKey, err := s.GetViewKey(otherKey)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	secret, err := s.ViewKeySecret(viewKey)
+	if err != nil {
+		t.Fatalf("AleoWrapper.ViewKeySecret() error = %v", err)
+	}
+	if !strings.HasSuffix(string(secret), "scalar") {
+		t.Errorf("AleoWrapper.ViewKeySecret() = %s, want a scalar literal", secret)
+	}
+	otherSecret, err := s.ViewKeySecret(otherViewKey)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if bytes.Equal(secret, otherSecret) {
+		t.Error("AleoWrapper.ViewKeySecret() should return different secrets for different view keys")
+	}
+
+	// The view key the secret belongs to decrypts the records of its address, the other doesn't
+	ciphertext, plaintext := encryptRecord(t, s, testRecord(address, 1500000, "0group"))
+	record, err := s.DecryptRecord(ciphertext, viewKey)
+	if err != nil {
+		t.Fatalf("AleoWrapper.DecryptRecord() error = %v", err)
+	}
+	if record != plaintext {
+		t.Errorf("AleoWrapper.DecryptRecord() = %v, want %v", record, plaintext)
+	}
+	if _, err := s.DecryptRecord(ciphertext, otherViewKey); err == nil {
+		t.Error("AleoWrapper.DecryptRecord() should fail with the view key of another account")
+	}
+
+	if _, err := s.ViewKeySecret([]byte("AViewKey1invalid")); err == nil {
+		t.Error("AleoWrapper.ViewKeySecret() should fail on an invalid view key")
+	}
+
+	s.Close()
+
+	_, err = s.ViewKeySecret(viewKey)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_HashConcat(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	parts := [][]byte{[]byte("header:"), {}, []byte("payload"), {0, 1, 2, 3}}
+	joined := bytes.Join(parts, nil)
+
+	for _, operation := range []uint32{HASH_BHP256, HASH_BHP1024, HASH_PSD2, HASH_PSD8, HASH_KECCAK256, HASH_SHA3_512} {
+		t.Run(fmt.Sprint(operation), func(t *testing.T) {
+			got, err := s.HashConcat(operation, parts)
+			if err != nil {
+				t.Fatalf("AleoWrapper.HashConcat() error = %v", err)
+			}
+			want, err := s.HashBytes(operation, joined)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if !bytes.Equal(got, want) {
+				t.Errorf("AleoWrapper.HashConcat() = %x, want %x", got, want)
+			}
+		})
+	}
+
+	if _, err := s.HashConcat(HASH_SHA3_512+1, parts); err == nil {
+		t.Error("AleoWrapper.HashConcat() should fail on an unknown operation")
+	}
+
+	s.Close()
+
+	_, err = s.HashConcat(HASH_BHP256, parts)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_HashWithDomain(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	input := []byte("payload")
+	for _, operation := range []uint32{HASH_BHP256, HASH_PSD2, HASH_KECCAK256, HASH_SHA3_256} {
+		t.Run(fmt.Sprint(operation), func(t *testing.T) {
+			digestA, err := s.HashWithDomain(operation, []byte("protocol-a"), input)
+			if err != nil {
+				t.Fatalf("AleoWrapper.HashWithDomain() error = %v", err)
+			}
+			digestB, err := s.HashWithDomain(operation, []byte("protocol-b"), input)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if bytes.Equal(digestA, digestB) {
+				t.Error("AleoWrapper.HashWithDomain() should return different digests for different domains")
+			}
+
+			// Moving bytes between the domain and the input changes the length prefix
+			shifted, err := s.HashWithDomain(operation, []byte("protocol-"), append([]byte("a"), input...))
+			if err != nil {
+				t.Fatal(err)
+			}
+			if bytes.Equal(digestA, shifted) {
+				t.Error("AleoWrapper.HashWithDomain() should separate the domain from the input")
+			}
+
+			tagged := binary.LittleEndian.AppendUint32(nil, uint32(len("protocol-a")))
+			tagged = append(tagged, "protocol-a"...)
+			tagged = append(tagged, input...)
+			want, err := s.HashBytes(operation, tagged)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if !bytes.Equal(digestA, want) {
+				t.Errorf("AleoWrapper.HashWithDomain() = %x, want %x", digestA, want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.HashWithDomain(HASH_BHP256, []byte("protocol-a"), input)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_MerkleVerifyMulti(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	leaves := []string{"1field", "2field", "3field", "4field", "5field", "6field"}
+	root, err := s.MerkleRoot(leaves)
+	if err != nil {
+		t.Fatalf("AleoWrapper.MerkleRoot() error = %v", err)
+	}
+
+	indices := []uint32{1, 2, 5}
+	proof, err := s.MerkleMultiProof(leaves, indices)
+	if err != nil {
+		t.Fatalf("AleoWrapper.MerkleMultiProof() error = %v", err)
+	}
+
+	// The tree is padded to 8 leaves: the proof holds the depth and the siblings of leaves 1, 2
+	// and 5 and of their parent at index 2, the remaining nodes are computed from the leaves
+	items, err := decodeFrames(proof)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(items) != 5 {
+		t.Fatalf("AleoWrapper.MerkleMultiProof() returned %d items, want 5", len(items))
+	}
+	tamperedItems := append([][]byte{}, items...)
+	tamperedItems[1] = []byte("7field")
+
+	tests := []struct {
+		name    string
+		root    string
+		leaves  []string
+		indices []uint32
+		proof   []byte
+		want    bool
+	}{
+		{name: "valid", root: root, leaves: []string{"2field", "3field", "6field"}, indices: indices, proof: proof, want: true},
+		{name: "any order", root: root, leaves: []string{"6field", "2field", "3field"}, indices: []uint32{5, 1, 2}, proof: proof, want: true},
+		{name: "tampered leaf", root: root, leaves: []string{"2field", "3field", "7field"}, indices: indices, proof: proof},
+		{name: "wrong index", root: root, leaves: []string{"2field", "3field", "6field"}, indices: []uint32{1, 2, 4}, proof: proof},
+		{name: "tampered sibling", root: root, leaves: []string{"2field", "3field", "6field"}, indices: indices, proof: encodeFrames(tamperedItems)},
+		{name: "missing sibling", root: root, leaves: []string{"2field", "3field", "6field"}, indices: indices, proof: encodeFrames(items[:4])},
+		{name: "extra sibling", root: root, leaves: []string{"2field", "3field", "6field"}, indices: indices, proof: encodeFrames(append(append([][]byte{}, items...), []byte("1field")))},
+		{name: "wrong root", root: "1field", leaves: []string{"2field", "3field", "6field"}, indices: indices, proof: proof},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			valid, err := s.MerkleVerifyMulti(tt.root, tt.leaves, tt.indices, tt.proof)
+			if err != nil {
+				t.Fatalf("AleoWrapper.MerkleVerifyMulti() error = %v", err)
+			}
+			if valid != tt.want {
+				t.Errorf("AleoWrapper.MerkleVerifyMulti() = %v, want %v", valid, tt.want)
+			}
+		})
+	}
+
+	for _, indices := range [][]uint32{{6}, {1, 1}, {}} {
+		if _, err := s.MerkleMultiProof(leaves, indices); err == nil {
+			t.Errorf("AleoWrapper.MerkleMultiProof(%v) should fail", indices)
+		}
+	}
+
+	s.Close()
+
+	_, err = s.MerkleVerifyMulti(root, leaves[:1], []uint32{0}, proof)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_MerkleFindLeaf(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	leaves := []string{"1field", "2field", "3field", "2field"}
+
+	tests := []struct {
+		name      string
+		leaves    []string
+		target    string
+		wantIndex uint64
+		wantFound bool
+		wantErr   bool
+	}{
+		{name: "first", leaves: leaves, target: "1field", wantIndex: 0, wantFound: true},
+		{name: "first of duplicates", leaves: leaves, target: "2field", wantIndex: 1, wantFound: true},
+		{name: "last", leaves: leaves, target: "3field", wantIndex: 2, wantFound: true},
+		{name: "absent", leaves: leaves, target: "4field", wantFound: false},
+		{name: "no leaves", leaves: []string{}, target: "1field", wantFound: false},
+		{name: "invalid target", leaves: leaves, target: "1u64", wantErr: true},
+		{name: "invalid leaf", leaves: []string{"1field", "leaf"}, target: "1field", wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			index, found, err := s.MerkleFindLeaf(tt.leaves, tt.target)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.MerkleFindLeaf() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if found != tt.wantFound || index != tt.wantIndex {
+				t.Errorf("AleoWrapper.MerkleFindLeaf() = (%v, %v), want (%v, %v)", index, found, tt.wantIndex, tt.wantFound)
+			}
+		})
+	}
+
+	// An absent target is reported with the sentinel index
+	result, err := s.(*aleoWrapperSession).call("merkle_find_leaf", encodeStringFrames(leaves), []byte("4field"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if result != MERKLE_LEAF_NOT_FOUND {
+		t.Errorf("merkle_find_leaf() = %v, want MERKLE_LEAF_NOT_FOUND", result)
+	}
+
+	s.Close()
+
+	_, _, err = s.MerkleFindLeaf(leaves, "1field")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_SignatureToJSON(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	formattedMessage, err := s.FormatMessage([]byte("test"), 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	message, err := s.HashMessage(formattedMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+	signature, err := s.Sign(key, message)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	out, err := s.SignatureToJSON(signature)
+	if err != nil {
+		t.Fatalf("AleoWrapper.SignatureToJSON() error = %v", err)
+	}
+
+	var fields map[string]string
+	if err := json.Unmarshal([]byte(out), &fields); err != nil {
+		t.Fatalf("AleoWrapper.SignatureToJSON() = %s, not a JSON object of strings: %v", out, err)
+	}
+	if len(fields) != 3 {
+		t.Errorf("AleoWrapper.SignatureToJSON() has %d keys, want 3", len(fields))
+	}
+	_, challenge, err := s.SignatureInspect(signature)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if fields["address"] != address {
+		t.Errorf("AleoWrapper.SignatureToJSON() address = %v, want %v", fields["address"], address)
+	}
+	if fields["challenge"] != challenge {
+		t.Errorf("AleoWrapper.SignatureToJSON() challenge = %v, want %v", fields["challenge"], challenge)
+	}
+	if !strings.HasSuffix(fields["response"], "scalar") {
+		t.Errorf("AleoWrapper.SignatureToJSON() response = %v, want a scalar literal", fields["response"])
+	}
+
+	if _, err := s.SignatureToJSON("sign1invalid"); err == nil {
+		t.Error("AleoWrapper.SignatureToJSON() should fail on an invalid signature")
+	}
+
+	s.Close()
+
+	_, err = s.SignatureToJSON(signature)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_CiphertextsEqualPlaintext(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKey, otherAddress, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherViewKey, err := s.GetViewKey(otherKey)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// Each encryption samples a fresh nonce, so the same record gets different ciphertexts
+	ciphertext, _ := encryptRecord(t, s, testRecord(address, 1500000, "0group"))
+	sameCiphertext, _ := encryptRecord(t, s, testRecord(address, 1500000, "0group"))
+	differentCiphertext, _ := encryptRecord(t, s, testRecord(address, 2500000, "0group"))
+	otherOwnerCiphertext, _ := encryptRecord(t, s, testRecord(otherAddress, 1500000, "0group"))
+	if ciphertext == sameCiphertext {
+		t.Fatal("encrypt_record should return different ciphertexts for the same record")
+	}
+
+	tests := []struct {
+		name        string
+		viewKey     []byte
+		ciphertextA string
+		ciphertextB string
+		want        bool
+		wantErr     error
+	}{
+		{name: "same record", viewKey: viewKey, ciphertextA: ciphertext, ciphertextB: sameCiphertext, want: true},
+		{name: "same ciphertext", viewKey: viewKey, ciphertextA: ciphertext, ciphertextB: ciphertext, want: true},
+		{name: "different records", viewKey: viewKey, ciphertextA: ciphertext, ciphertextB: differentCiphertext, want: false},
+		{name: "other owner", viewKey: viewKey, ciphertextA: ciphertext, ciphertextB: otherOwnerCiphertext, wantErr: ErrRecordDecrypt},
+		{name: "wrong view key", viewKey: otherViewKey, ciphertextA: ciphertext, ciphertextB: sameCiphertext, wantErr: ErrRecordDecrypt},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			equal, err := s.CiphertextsEqualPlaintext(tt.viewKey, tt.ciphertextA, tt.ciphertextB)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("AleoWrapper.CiphertextsEqualPlaintext() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if equal != tt.want {
+				t.Errorf("AleoWrapper.CiphertextsEqualPlaintext() = %v, want %v", equal, tt.want)
+			}
+		})
+	}
+
+	// Malformed inputs are reported as parse errors, not as decryption failures
+	_, err = s.CiphertextsEqualPlaintext(viewKey, "record1invalid", ciphertext)
+	if err == nil || errors.Is(err, ErrRecordDecrypt) {
+		t.Errorf("AleoWrapper.CiphertextsEqualPlaintext() error = %v, want a parse error", err)
+	}
+
+	s.Close()
+
+	_, err = s.CiphertextsEqualPlaintext(viewKey, ciphertext, sameCiphertext)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_PedersenOpen(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	input := binary.LittleEndian.AppendUint64(nil, 1500000)
+	commitment, bits, randomizer, err := s.PedersenOpen(input, "12345scalar")
+	if err != nil {
+		t.Fatalf("AleoWrapper.PedersenOpen() error = %v", err)
+	}
+	if !strings.HasSuffix(commitment, "field") {
+		t.Errorf("AleoWrapper.PedersenOpen() commitment = %v, want a field literal", commitment)
+	}
+	if randomizer != "12345scalar" {
+		t.Errorf("AleoWrapper.PedersenOpen() randomizer = %v, want 12345scalar", randomizer)
+	}
+	if len(bits) != 64 {
+		t.Fatalf("AleoWrapper.PedersenOpen() returned %d bits, want 64", len(bits))
+	}
+	var value uint64
+	for i, bit := range bits {
+		value |= uint64(bit) << i
+	}
+	if value != 1500000 {
+		t.Errorf("AleoWrapper.PedersenOpen() bits encode %d, want 1500000", value)
+	}
+
+	otherCommitment, _, _, err := s.PedersenOpen(input, "54321scalar")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if otherCommitment == commitment {
+		t.Error("AleoWrapper.PedersenOpen() should return different commitments for different randomizers")
+	}
+
+	flippedBits := append([]byte{}, bits...)
+	flippedBits[0] ^= 1
+
+	tests := []struct {
+		name       string
+		commitment string
+		bits       []byte
+		randomizer string
+		want       bool
+	}{
+		{name: "valid", commitment: commitment, bits: bits, randomizer: randomizer, want: true},
+		{name: "wrong randomizer", commitment: commitment, bits: bits, randomizer: "54321scalar", want: false},
+		{name: "flipped bit", commitment: commitment, bits: flippedBits, randomizer: randomizer, want: false},
+		{name: "wrong commitment", commitment: otherCommitment, bits: bits, randomizer: randomizer, want: false},
+		{name: "non-binary bit", commitment: commitment, bits: append([]byte{2}, bits[1:]...), randomizer: randomizer, want: false},
+		{name: "invalid randomizer", commitment: commitment, bits: bits, randomizer: "12345field", want: false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			valid, err := s.PedersenVerify(tt.commitment, tt.bits, tt.randomizer)
+			if err != nil {
+				t.Fatalf("AleoWrapper.PedersenVerify() error = %v", err)
+			}
+			if valid != tt.want {
+				t.Errorf("AleoWrapper.PedersenVerify() = %v, want %v", valid, tt.want)
+			}
+		})
+	}
+
+	if _, _, _, err := s.PedersenOpen(make([]byte, 17), "12345scalar"); err == nil {
+		t.Error("AleoWrapper.PedersenOpen() should fail on an input longer than 16 bytes")
+	}
+
+	s.Close()
+
+	_, _, _, err = s.PedersenOpen(input, "12345scalar")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_AddressFromLabel(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	address, err := s.AddressFromLabel("faucet")
+	if err != nil {
+		t.Fatalf("AleoWrapper.AddressFromLabel() error = %v", err)
+	}
+	if len(address) != ADDRESS_SIZE || !strings.HasPrefix(address, "aleo1") {
+		t.Errorf("AleoWrapper.AddressFromLabel() = %v, want an aleo address", address)
+	}
+
+	// A new session must derive the same address
+	otherSession, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer otherSession.Close()
+
+	sameAddress, err := otherSession.AddressFromLabel("faucet")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if sameAddress != address {
+		t.Errorf("AleoWrapper.AddressFromLabel() = %v, want %v for the same label", sameAddress, address)
+	}
+
+	for _, label := range []string{"faucet2", "Faucet", ""} {
+		other, err := s.AddressFromLabel(label)
+		if err != nil {
+			t.Fatalf("AleoWrapper.AddressFromLabel(%q) error = %v", label, err)
+		}
+		if other == address {
+			t.Errorf("AleoWrapper.AddressFromLabel(%q) should differ from the address of \"faucet\"", label)
+		}
+	}
+
+	s.Close()
+
+	_, err = s.AddressFromLabel("faucet")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_VerifyBalanceInvariant(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, sender, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	_, receiver, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	inputs := []string{testRecord(sender, 1000000, "0group"), testRecord(sender, 500000, "0group")}
+	outputs := []string{testRecord(receiver, 1200000, "0group"), testRecord(sender, 284000, "0group")}
+
+	tests := []struct {
+		name    string
+		inputs  []string
+		outputs []string
+		fee     uint64
+		want    bool
+		wantErr bool
+	}{
+		{name: "balanced", inputs: inputs, outputs: outputs, fee: 16000, want: true},
+		{name: "no fee", inputs: inputs[:1], outputs: []string{testRecord(receiver, 1000000, "0group")}, fee: 0, want: true},
+		{name: "fee too low", inputs: inputs, outputs: outputs, fee: 15999, want: false},
+		{name: "fee too high", inputs: inputs, outputs: outputs, fee: 16001, want: false},
+		{name: "missing input", inputs: inputs[:1], outputs: outputs, fee: 16000, want: false},
+		{
+			name:    "inputs overflow",
+			inputs:  []string{testRecord(sender, math.MaxUint64, "0group"), testRecord(sender, 1, "0group")},
+			outputs: outputs,
+			fee:     16000,
+			wantErr: true,
+		},
+		{name: "outputs and fee overflow", inputs: inputs, outputs: []string{testRecord(receiver, math.MaxUint64, "0group")}, fee: 1, wantErr: true},
+		{name: "no microcredits", inputs: []string{fmt.Sprintf("{ owner: %s.private, _nonce: 0group.public }", sender)}, outputs: []string{}, fee: 0, wantErr: true},
+		{name: "invalid record", inputs: []string{"{ owner: 1u64 }"}, outputs: outputs, fee: 16000, wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			balanced, err := s.VerifyBalanceInvariant(tt.inputs, tt.outputs, tt.fee)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.VerifyBalanceInvariant() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if balanced != tt.want {
+				t.Errorf("AleoWrapper.VerifyBalanceInvariant() = %v, want %v", balanced, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.VerifyBalanceInvariant(inputs, outputs, 16000)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_VerifyRequest(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	inputTypes := []string{"address.public", "u64.public"}
+	tests := []struct {
+		name       string
+		request    string
+		inputTypes []string
+	}{
+		{name: "empty object", request: "{}", inputTypes: inputTypes},
+		{name: "not json", request: "request", inputTypes: inputTypes},
+		{name: "invalid input type", request: "{}", inputTypes: []string{"address.public", "u64.secret"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.VerifyRequest(tt.request, tt.inputTypes)
+			if err == nil || got {
+				t.Errorf("AleoWrapper.VerifyRequest() = (%v, %v), want a parse error", got, err)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.VerifyRequest("{}", inputTypes)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ComputeTVK(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKey, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// the identity tpk (r = 0) has the identity as its shared point, whose x-coordinate is 0
+	tvk, err := s.ComputeTVK(key, "0group")
+	if err != nil {
+		t.Fatalf("AleoWrapper.ComputeTVK() error = %v", err)
+	}
+	if tvk != "0field" {
+		t.Errorf("AleoWrapper.ComputeTVK() = %v, want 0field", tvk)
+	}
+
+	// A record nonce is G * r for a random r, like a tpk, so it makes a realistic tpk
+	_, plaintext := encryptRecord(t, s, testRecord(address, 1500000, "0group"))
+	_, nonce, found := strings.Cut(plaintext, "_nonce: ")
+	if !found {
+		t.Fatalf("encrypt_record plaintext = %v, want a nonce", plaintext)
+	}
+	tpk, _, _ := strings.Cut(nonce, ".")
+
+	tvk, err = s.ComputeTVK(key, tpk)
+	if err != nil {
+		t.Fatalf("AleoWrapper.ComputeTVK() error = %v", err)
+	}
+	recovered, err := s.ComputeTPK(key, tvk)
+	if err != nil {
+		t.Fatalf("AleoWrapper.ComputeTPK() error = %v", err)
+	}
+	if recovered != tpk {
+		t.Errorf("AleoWrapper.ComputeTPK(ComputeTVK()) = %v, want %v", recovered, tpk)
+	}
+	otherTVK, err := s.ComputeTVK(otherKey, tpk)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if otherTVK == tvk {
+		t.Error("AleoWrapper.ComputeTVK() should return different tvks for different signers")
+	}
+
+	tests := []struct {
+		name string
+		key  []byte
+		tpk  string
+	}{
+		{name: "invalid private key", key: []byte("APrivateKey1invalid"), tpk: tpk},
+		{name: "invalid tpk", key: key, tpk: "0field"},
+		{name: "empty tpk", key: key, tpk: ""},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, err := s.ComputeTVK(tt.key, tt.tpk); err == nil {
+				t.Errorf("AleoWrapper.ComputeTVK() should fail on %s", tt.name)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.ComputeTVK(key, tpk)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_TransactionTransitionIDs(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name    string
+		txBytes []byte
+	}{
+		{name: "empty", txBytes: []byte{}},
+		{name: "unknown version", txBytes: []byte{0xff, 0x01}},
+		{name: "unknown variant", txBytes: []byte{0x01, 0xff}},
+		{name: "truncated execution", txBytes: append([]byte{0x01, 0x01}, make([]byte, 16)...)},
+		{name: "transaction id string", txBytes: []byte("at1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq")},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, err := s.TransactionTransitionIDs(tt.txBytes); err == nil {
+				t.Errorf("AleoWrapper.TransactionTransitionIDs() should fail on %s input", tt.name)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.TransactionTransitionIDs([]byte{})
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_AddressForNetwork(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// The module is built for testnet, so its address is the one GetAddress derives
+	testnetAddress, err := s.AddressForNetwork(key, NETWORK_TESTNET)
+	if err != nil {
+		t.Fatalf("AleoWrapper.AddressForNetwork(NETWORK_TESTNET) error = %v", err)
+	}
+	if testnetAddress != address {
+		t.Errorf("AleoWrapper.AddressForNetwork(NETWORK_TESTNET) = %v, want %v", testnetAddress, address)
+	}
+
+	mainnetAddress, err := s.AddressForNetwork(key, NETWORK_MAINNET)
+	if err != nil {
+		t.Fatalf("AleoWrapper.AddressForNetwork(NETWORK_MAINNET) error = %v", err)
+	}
+	if len(mainnetAddress) != ADDRESS_SIZE || !strings.HasPrefix(mainnetAddress, "aleo1") {
+		t.Errorf("AleoWrapper.AddressForNetwork(NETWORK_MAINNET) = %v, want an aleo address", mainnetAddress)
+	}
+	if err := s.VerifyAddressChecksum(mainnetAddress); err != nil {
+		t.Errorf("AleoWrapper.AddressForNetwork(NETWORK_MAINNET) = %v, invalid checksum: %v", mainnetAddress, err)
+	}
+
+	if _, err := s.AddressForNetwork(key, 2); err == nil {
+		t.Error("AleoWrapper.AddressForNetwork() should fail on an unknown network ID")
+	}
+	if _, err := s.AddressForNetwork([]byte("APrivateKey1invalid"), NETWORK_MAINNET); err == nil {
+		t.Error("AleoWrapper.AddressForNetwork() should fail on an invalid private key")
+	}
+
+	// The Go side doesn't shadow the module's own network ID check
+	result, err := s.(*aleoWrapperSession).call("address_for_network", key, uint32(1<<16|1))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if result != 0 {
+		t.Error("address_for_network() should fail on a network ID that truncates to a known one")
+	}
+
+	s.Close()
+
+	_, err = s.AddressForNetwork(key, NETWORK_TESTNET)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_DeriveOutputRandomizers(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// With the zero randomizer the nonce would be the identity, which is never the case
+	randomizer, nonce, err := s.DeriveOutputRandomizers(key, "1field", 2)
+	if err != nil {
+		t.Fatalf("AleoWrapper.DeriveOutputRandomizers() error = %v", err)
+	}
+	if !strings.HasSuffix(randomizer, "scalar") || !strings.HasSuffix(nonce, "group") || nonce == "0group" {
+		t.Errorf("AleoWrapper.DeriveOutputRandomizers() = (%v, %v), want a scalar and a non-zero group", randomizer, nonce)
+	}
+
+	sameRandomizer, sameNonce, err := s.DeriveOutputRandomizers(key, "1field", 2)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if sameRandomizer != randomizer || sameNonce != nonce {
+		t.Error("AleoWrapper.DeriveOutputRandomizers() should be deterministic")
+	}
+
+	// The private key doesn't enter the derivation
+	otherKey, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKeyRandomizer, otherKeyNonce, err := s.DeriveOutputRandomizers(otherKey, "1field", 2)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if otherKeyRandomizer != randomizer || otherKeyNonce != nonce {
+		t.Error("AleoWrapper.DeriveOutputRandomizers() should not depend on the private key")
+	}
+	for _, other := range []struct {
+		tvk   string
+		index uint64
+	}{{tvk: "1field", index: 3}, {tvk: "2field", index: 2}} {
+		otherRandomizer, otherNonce, err := s.DeriveOutputRandomizers(key, other.tvk, other.index)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if otherRandomizer == randomizer || otherNonce == nonce {
+			t.Errorf("AleoWrapper.DeriveOutputRandomizers(%v, %d) should differ", other.tvk, other.index)
+		}
+	}
+
+	// The nonce G * randomizer is a subgroup point like any tpk, so ComputeTPK recovers it from the
+	// record view key (nonce * view key).x
+	recordViewKey, err := s.ComputeTVK(key, nonce)
+	if err != nil {
+		t.Fatal(err)
+	}
+	recovered, err := s.ComputeTPK(key, recordViewKey)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if recovered != nonce {
+		t.Errorf("AleoWrapper.ComputeTPK() = %v, want the nonce %v", recovered, nonce)
+	}
+
+	if _, _, err := s.DeriveOutputRandomizers(key, "1field", math.MaxUint16); err != nil {
+		t.Errorf("AleoWrapper.DeriveOutputRandomizers() error = %v at the largest index", err)
+	}
+	if _, _, err := s.DeriveOutputRandomizers(key, "1field", math.MaxUint16+1); err == nil {
+		t.Error("AleoWrapper.DeriveOutputRandomizers() should fail on an index beyond uint16")
+	}
+	if _, _, err := s.DeriveOutputRandomizers(key, "1group", 0); err == nil {
+		t.Error("AleoWrapper.DeriveOutputRandomizers() should fail on an invalid tvk")
+	}
+
+	s.Close()
+
+	_, _, err = s.DeriveOutputRandomizers(key, "1field", 2)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ValidateRecord(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	creditsType := "record credits:\n    owner as address.private;\n    microcredits as u64.private;"
+	record := testRecord(address, 1500000, "0group")
+
+	tests := []struct {
+		name       string
+		record     string
+		recordType string
+		wantErr    bool
+	}{
+		{name: "conforming", record: record, recordType: creditsType},
+		{name: "other type name", record: record, recordType: strings.Replace(creditsType, "credits:", "token:", 1)},
+		{
+			name:       "missing entry",
+			record:     fmt.Sprintf("{ owner: %s.private, _nonce: 0group.public }", address),
+			recordType: creditsType,
+			wantErr:    true,
+		},
+		{
+			name:       "extra entry",
+			record:     fmt.Sprintf("{ owner: %s.private, microcredits: 1u64.private, memo: 2field.private, _nonce: 0group.public }", address),
+			recordType: creditsType,
+			wantErr:    true,
+		},
+		{
+			name:       "wrong entry type",
+			record:     fmt.Sprintf("{ owner: %s.private, microcredits: 1u128.private, _nonce: 0group.public }", address),
+			recordType: creditsType,
+			wantErr:    true,
+		},
+		{
+			name:       "wrong entry visibility",
+			record:     fmt.Sprintf("{ owner: %s.private, microcredits: 1u64.public, _nonce: 0group.public }", address),
+			recordType: creditsType,
+			wantErr:    true,
+		},
+		{
+			name:       "wrong owner visibility",
+			record:     fmt.Sprintf("{ owner: %s.public, microcredits: 1u64.private, _nonce: 0group.public }", address),
+			recordType: creditsType,
+			wantErr:    true,
+		},
+		{name: "invalid record", record: "{ owner: 1u64 }", recordType: creditsType, wantErr: true},
+		{name: "invalid record type", record: record, recordType: "record credits: microcredits as u64;", wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if err := s.ValidateRecord(tt.record, tt.recordType); (err != nil) != tt.wantErr {
+				t.Errorf("AleoWrapper.ValidateRecord() error = %v, wantErr %v", err, tt.wantErr)
+			}
+		})
+	}
+
+	err = s.ValidateRecord(fmt.Sprintf("{ owner: %s.private, _nonce: 0group.public }", address), creditsType)
+	if err == nil || !strings.Contains(err.Error(), "microcredits") {
+		t.Errorf("AleoWrapper.ValidateRecord() error = %v, want it to name the missing entry", err)
+	}
+
+	s.Close()
+
+	err = s.ValidateRecord(record, creditsType)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ComputeStateRoot(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// The block hashes are bech32m encodings of the fields 1 to 4, computed with the BIP 350
+	// reference encoder
+	blockHashes := []string{
+		"ab1qyqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqjw98qd",
+		"ab1qgqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqcyu589",
+		"ab1qvqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq7zvza6",
+	}
+	root, err := s.ComputeStateRoot(blockHashes)
+	if err != nil {
+		t.Fatalf("AleoWrapper.ComputeStateRoot() error = %v", err)
+	}
+	if !strings.HasPrefix(root, "sr1") {
+		t.Errorf("AleoWrapper.ComputeStateRoot() = %v, want an sr1 state root", root)
+	}
+
+	sameRoot, err := s.ComputeStateRoot(append([]string(nil), blockHashes...))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if sameRoot != root {
+		t.Errorf("AleoWrapper.ComputeStateRoot() = %v, want %v for the same blocks", sameRoot, root)
+	}
+
+	// The tree commits to the order and the number of blocks
+	for _, other := range [][]string{
+		{blockHashes[1], blockHashes[0], blockHashes[2]},
+		blockHashes[:2],
+		append(append([]string(nil), blockHashes...), "ab1qsqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqvs8mf4"),
+	} {
+		otherRoot, err := s.ComputeStateRoot(other)
+		if err != nil {
+			t.Fatalf("AleoWrapper.ComputeStateRoot(%v) error = %v", other, err)
+		}
+		if otherRoot == root {
+			t.Errorf("AleoWrapper.ComputeStateRoot(%v) should differ from the root of %v", other, blockHashes)
+		}
+	}
+
+	if _, err := s.ComputeStateRoot([]string{blockHashes[0], "1field"}); err == nil {
+		t.Error("AleoWrapper.ComputeStateRoot() should fail on a field instead of a block hash")
+	}
+
+	s.Close()
+
+	_, err = s.ComputeStateRoot(blockHashes)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_GroupIsInSubgroup(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// A record nonce is G * r, a point of the prime-order subgroup
+	_, plaintext := encryptRecord(t, s, testRecord(address, 1500000, "0group"))
+	_, nonce, found := strings.Cut(plaintext, "_nonce: ")
+	if !found {
+		t.Fatalf("encrypt_record plaintext = %v, want a nonce", plaintext)
+	}
+	nonce, _, _ = strings.Cut(nonce, ".")
+
+	tests := []struct {
+		name  string
+		group string
+		want  bool
+	}{
+		{name: "subgroup element", group: nonce, want: true},
+		{name: "identity", group: "0group", want: false},
+		{name: "field", group: strings.TrimSuffix(nonce, "group") + "field", want: false},
+		{name: "empty", group: "", want: false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			valid, err := s.GroupIsInSubgroup(tt.group)
+			if err != nil {
+				t.Fatalf("AleoWrapper.GroupIsInSubgroup() error = %v", err)
+			}
+			if valid != tt.want {
+				t.Errorf("AleoWrapper.GroupIsInSubgroup(%v) = %v, want %v", tt.group, valid, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.GroupIsInSubgroup(nonce)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_NewIncrementalMerkle(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tree, err := s.NewIncrementalMerkle(3)
+	if err != nil {
+		t.Fatalf("AleoWrapper.NewIncrementalMerkle() error = %v", err)
+	}
+
+	// Once more than half of the 8 leaves are appended, the padded tree MerkleRoot builds from
+	// scratch has the same depth and the roots must match
+	leaves := make([]string, 0, 8)
+	roots := make(map[string]bool)
+	for i := 1; i <= 8; i++ {
+		leaf := fmt.Sprintf("%dfield", i)
+		leaves = append(leaves, leaf)
+
+		root, err := tree.Append(leaf)
+		if err != nil {
+			t.Fatalf("IncrementalMerkle.Append(%s) error = %v", leaf, err)
+		}
+		roots[root] = true
+		if i <= 4 {
+			continue
+		}
+
+		want, err := s.MerkleRoot(leaves)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if root != want {
+			t.Errorf("IncrementalMerkle.Append(%s) = %v, want MerkleRoot() = %v", leaf, root, want)
+		}
+	}
+	if len(roots) != 8 {
+		t.Errorf("IncrementalMerkle.Append() returned %d distinct roots for 8 leaves", len(roots))
+	}
+
+	if _, err := tree.Append("9field"); err == nil {
+		t.Error("IncrementalMerkle.Append() should fail on a full tree")
+	}
+
+	if err := tree.Close(); err != nil {
+		t.Fatalf("IncrementalMerkle.Close() error = %v", err)
+	}
+	if err := tree.Close(); err != nil {
+		t.Fatalf("second IncrementalMerkle.Close() error = %v", err)
+	}
+	if _, err := tree.Append("1field"); err == nil {
+		t.Error("IncrementalMerkle.Append() should fail after Close")
+	}
+
+	other, err := s.NewIncrementalMerkle(1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, err := other.Append("1group"); err == nil {
+		t.Error("IncrementalMerkle.Append() should fail on an invalid leaf")
+	}
+	if err := other.Close(); err != nil {
+		t.Fatal(err)
+	}
+
+	for _, depth := range []uint32{0, 33} {
+		if _, err := s.NewIncrementalMerkle(depth); err == nil {
+			t.Errorf("AleoWrapper.NewIncrementalMerkle(%d) should fail", depth)
+		}
+	}
+
+	s.Close()
+
+	_, err = s.NewIncrementalMerkle(3)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_DecryptAndVerifyRecord(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKey, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherViewKey, err := s.GetViewKey(otherKey)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	ciphertext, plaintext := encryptRecord(t, s, testRecord(address, 1500000, "0group"))
+	commitment, err := s.ComputeRecordCommitment(plaintext, "credits.aleo", "credits")
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	record, err := s.DecryptAndVerifyRecord(viewKey, ciphertext, commitment, "credits.aleo", "credits")
+	if err != nil {
+		t.Fatalf("AleoWrapper.DecryptAndVerifyRecord() error = %v", err)
+	}
+	if record != plaintext {
+		t.Errorf("AleoWrapper.DecryptAndVerifyRecord() = %v, want %v", record, plaintext)
+	}
+
+	// A ciphertext of another record of the same owner decrypts, but doesn't match
+	otherCiphertext, _ := encryptRecord(t, s, testRecord(address, 2500000, "0group"))
+
+	tests := []struct {
+		name       string
+		viewKey    []byte
+		ciphertext string
+		commitment string
+		recordName string
+		wantErr    error
+	}{
+		{name: "tampered commitment", viewKey: viewKey, ciphertext: ciphertext, commitment: "1field", recordName: "credits", wantErr: ErrRecordCommitment},
+		{name: "swapped ciphertext", viewKey: viewKey, ciphertext: otherCiphertext, commitment: commitment, recordName: "credits", wantErr: ErrRecordCommitment},
+		{name: "other record type", viewKey: viewKey, ciphertext: ciphertext, commitment: commitment, recordName: "token", wantErr: ErrRecordCommitment},
+		{name: "wrong view key", viewKey: otherViewKey, ciphertext: ciphertext, commitment: commitment, recordName: "credits", wantErr: ErrRecordDecrypt},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			record, err := s.DecryptAndVerifyRecord(tt.viewKey, tt.ciphertext, tt.commitment, "credits.aleo", tt.recordName)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("AleoWrapper.DecryptAndVerifyRecord() error = %v, want %v", err, tt.wantErr)
+			}
+			if record != "" {
+				t.Errorf("AleoWrapper.DecryptAndVerifyRecord() = %v, want no record", record)
+			}
+		})
+	}
+
+	_, err = s.DecryptAndVerifyRecord(viewKey, ciphertext, "1group", "credits.aleo", "credits")
+	if err == nil || errors.Is(err, ErrRecordCommitment) {
+		t.Errorf("AleoWrapper.DecryptAndVerifyRecord() error = %v, want a parse error", err)
+	}
+
+	s.Close()
+
+	_, err = s.DecryptAndVerifyRecord(viewKey, ciphertext, commitment, "credits.aleo", "credits")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_MessageKey(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	keyA, addressA, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	keyB, addressB, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	_, addressC, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	context := []byte("chat/42")
+	keyAB, err := s.MessageKey(keyA, addressB, context)
+	if err != nil {
+		t.Fatalf("AleoWrapper.MessageKey() error = %v", err)
+	}
+	if len(keyAB) != 32 {
+		t.Fatalf("AleoWrapper.MessageKey() returned %d bytes, want 32", len(keyAB))
+	}
+	keyBA, err := s.MessageKey(keyB, addressA, context)
+	if err != nil {
+		t.Fatalf("AleoWrapper.MessageKey() error = %v", err)
+	}
+	if !bytes.Equal(keyAB, keyBA) {
+		t.Errorf("AleoWrapper.MessageKey() = %x for A and %x for B, want the same key", keyAB, keyBA)
+	}
+
+	otherContextKey, err := s.MessageKey(keyA, addressB, []byte("chat/43"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if bytes.Equal(otherContextKey, keyAB) {
+		t.Error("AleoWrapper.MessageKey() should derive different keys for different contexts")
+	}
+	otherPeerKey, err := s.MessageKey(keyA, addressC, context)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if bytes.Equal(otherPeerKey, keyAB) {
+		t.Error("AleoWrapper.MessageKey() should derive different keys for different peers")
+	}
+
+	if _, err := s.MessageKey(keyA, "aleo1invalid", context); err == nil {
+		t.Error("AleoWrapper.MessageKey() should fail on an invalid peer address")
+	}
+	if _, err := s.MessageKey([]byte("APrivateKey1invalid"), addressB, context); err == nil {
+		t.Error("AleoWrapper.MessageKey() should fail on an invalid private key")
+	}
+
+	s.Close()
+
+	_, err = s.MessageKey(keyA, addressB, context)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+const testProgram = `program hello.aleo;
+
+function main:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;
+`
+
+func TestAleoWrapper_ProgramFingerprint(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	fingerprint, err := s.ProgramFingerprint(testProgram)
+	if err != nil {
+		t.Fatalf("AleoWrapper.ProgramFingerprint() error = %v", err)
+	}
+	if !strings.HasSuffix(fingerprint, "field") {
+		t.Errorf("AleoWrapper.ProgramFingerprint() = %v, want a field literal", fingerprint)
+	}
+
+	// Formatting and comments aren't part of the canonical encoding
+	reformatted := "// greeting program\n" + strings.ReplaceAll(testProgram, "    ", "\t")
+	sameFingerprint, err := s.ProgramFingerprint(reformatted)
+	if err != nil {
+		t.Fatalf("AleoWrapper.ProgramFingerprint() error = %v", err)
+	}
+	if sameFingerprint != fingerprint {
+		t.Errorf("AleoWrapper.ProgramFingerprint() = %v for the reformatted program, want %v", sameFingerprint, fingerprint)
+	}
+
+	for _, other := range []string{
+		strings.Replace(testProgram, "hello.aleo", "hello2.aleo", 1),
+		strings.Replace(testProgram, "add r0 r1", "add r1 r0", 1),
+	} {
+		otherFingerprint, err := s.ProgramFingerprint(other)
+		if err != nil {
+			t.Fatalf("AleoWrapper.ProgramFingerprint() error = %v", err)
+		}
+		if otherFingerprint == fingerprint {
+			t.Errorf("AleoWrapper.ProgramFingerprint() should differ for a changed program:\n%s", other)
+		}
+	}
+
+	_, err = s.ProgramFingerprint("program hello.aleo;\n\nfunction main:\n    add r0 r1 into r2;\n")
+	if err == nil {
+		t.Error("AleoWrapper.ProgramFingerprint() should fail on an invalid program")
+	}
+
+	s.Close()
+
+	_, err = s.ProgramFingerprint(testProgram)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ProgramFunctions(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tokenProgram := `program token.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+closure double:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.private;
+    cast r0 r1 into r2 as token.record;
+    output r2 as token.record;
+
+function transfer:
+    input r0 as token.record;
+    input r1 as address.private;
+    input r2 as u64.private;
+    sub r0.amount r2 into r3;
+    cast r1 r2 into r4 as token.record;
+    cast r0.owner r3 into r5 as token.record;
+    output r4 as token.record;
+    output r5 as token.record;
+`
+
+	tests := []struct {
+		name    string
+		source  string
+		want    []string
+		wantErr bool
+	}{
+		{"single function", testProgram, []string{"main"}, false},
+		{"two functions and a closure", tokenProgram, []string{"mint", "transfer"}, false},
+		{"invalid program", "program token.aleo;\n\nfunction mint:\n    input r0;\n", nil, true},
+		{"empty source", "", nil, true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.ProgramFunctions(tt.source)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.ProgramFunctions() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("AleoWrapper.ProgramFunctions() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.ProgramFunctions(testProgram)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ProgramFunctionSignature(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tokenProgram := `program token.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.public;
+    cast r0 r1 into r2 as token.record;
+    output r2 as token.record;
+
+function burn:
+    input r0 as token.record;
+`
+
+	tests := []struct {
+		name        string
+		source      string
+		function    string
+		wantInputs  []string
+		wantOutputs []string
+		wantErr     error
+	}{
+		{"main", testProgram, "main", []string{"u32.public", "u32.private"}, []string{"u32.private"}, nil},
+		{"record output", tokenProgram, "mint", []string{"address.private", "u64.public"}, []string{"token.record"}, nil},
+		{"no outputs", tokenProgram, "burn", []string{"token.record"}, []string{}, nil},
+		{"missing function", tokenProgram, "transfer", nil, nil, ErrFunctionNotFound},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			inputs, outputs, err := s.ProgramFunctionSignature(tt.source, tt.function)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("AleoWrapper.ProgramFunctionSignature() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(inputs, tt.wantInputs) {
+				t.Errorf("AleoWrapper.ProgramFunctionSignature() inputs = %v, want %v", inputs, tt.wantInputs)
+			}
+			if !reflect.DeepEqual(outputs, tt.wantOutputs) {
+				t.Errorf("AleoWrapper.ProgramFunctionSignature() outputs = %v, want %v", outputs, tt.wantOutputs)
+			}
+		})
+	}
+
+	// Parse errors aren't reported as a missing function
+	for _, tc := range [][2]string{{"program token.aleo;\n\nfunction mint:\n    input r0;\n", "mint"}, {testProgram, "1main"}} {
+		_, _, err = s.ProgramFunctionSignature(tc[0], tc[1])
+		if err == nil || errors.Is(err, ErrFunctionNotFound) {
+			t.Errorf("AleoWrapper.ProgramFunctionSignature(%q) error = %v, want a parse error", tc[1], err)
+		}
+	}
+
+	s.Close()
+
+	_, _, err = s.ProgramFunctionSignature(testProgram, "main")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ComputeSerialAndTag(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatalf("AleoWrapper.GetViewKey() error = %v", err)
+	}
+
+	serialNumbers := make(map[string]bool)
+	for i := 0; i < 5; i++ {
+		commitment := fmt.Sprintf("%dfield", i)
+
+		wantSerialNumber, err := s.ComputeSerialNumber(key, commitment)
+		if err != nil {
+			t.Fatalf("AleoWrapper.ComputeSerialNumber() error = %v", err)
+		}
+		wantTag, err := s.ComputeRecordTag(viewKey, commitment)
+		if err != nil {
+			t.Fatalf("AleoWrapper.ComputeRecordTag() error = %v", err)
+		}
+
+		serialNumber, tag, err := s.ComputeSerialAndTag(key, commitment)
+		if err != nil {
+			t.Fatalf("AleoWrapper.ComputeSerialAndTag() error = %v", err)
+		}
+		if serialNumber != wantSerialNumber {
+			t.Errorf("AleoWrapper.ComputeSerialAndTag(%s) serial number = %v, want %v", commitment, serialNumber, wantSerialNumber)
+		}
+		if tag != wantTag {
+			t.Errorf("AleoWrapper.ComputeSerialAndTag(%s) tag = %v, want %v", commitment, tag, wantTag)
+		}
+		if serialNumber == tag {
+			t.Errorf("AleoWrapper.ComputeSerialAndTag(%s) returned the same value twice", commitment)
+		}
+		serialNumbers[serialNumber] = true
+	}
+	if len(serialNumbers) != 5 {
+		t.Errorf("AleoWrapper.ComputeSerialAndTag() returned %d distinct serial numbers for 5 commitments", len(serialNumbers))
+	}
+
+	tests := []struct {
+		name       string
+		key        []byte
+		commitment string
+	}{
+		{"invalid key", []byte("APrivateKey1invalid"), "1field"},
+		{"invalid commitment", key, "not a field"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, _, err := s.ComputeSerialAndTag(tt.key, tt.commitment); err == nil {
+				t.Error("AleoWrapper.ComputeSerialAndTag() should fail")
+			}
+			if _, err := s.ComputeSerialNumber(tt.key, tt.commitment); err == nil {
+				t.Error("AleoWrapper.ComputeSerialNumber() should fail")
+			}
+		})
+	}
+
+	s.Close()
+
+	_, _, err = s.ComputeSerialAndTag(key, "1field")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_BytesToFieldArray(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	full := make([]byte, 31)
+	full[30] = 1
+
+	tests := []struct {
+		name       string
+		data       []byte
+		chunkBytes uint32
+		want       []string
+		wantErr    bool
+	}{
+		{"single bytes", []byte{1, 2, 3}, 1, []string{"1field", "2field", "3field"}, false},
+		{"little-endian chunks", []byte{1, 2, 3, 4}, 2, []string{"513field", "1027field"}, false},
+		// The last chunk is zero-padded at the most significant end, {5} reads as {5, 0, 0}
+		{"padded last chunk", []byte{1, 0, 0, 2, 0, 0, 5}, 3, []string{"1field", "2field", "5field"}, false},
+		{"trailing zero bytes look like padding", []byte{1, 0, 0, 2, 0, 0, 5, 0, 0}, 3, []string{"1field", "2field", "5field"}, false},
+		{"full field chunk", full, 31, []string{"1766847064778384329583297500742918515827483896875618958121606201292619776field"}, false},
+		{"empty input", []byte{}, 4, []string{}, false},
+		{"zero chunk size", []byte{1}, 0, nil, true},
+		{"chunk size over field capacity", []byte{1}, 32, nil, true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.BytesToFieldArray(tt.data, tt.chunkBytes)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.BytesToFieldArray() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("AleoWrapper.BytesToFieldArray() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.BytesToFieldArray([]byte{1}, 1)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_LiteralBytes(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		literal  string
+		typeCode uint32
+		bytes    []byte
+	}{
+		{"true", LITERAL_BOOLEAN, []byte{1}},
+		{"false", LITERAL_BOOLEAN, []byte{0}},
+		{"255u8", LITERAL_U8, []byte{0xff}},
+		{"-1i8", LITERAL_I8, []byte{0xff}},
+		{"513u16", LITERAL_U16, []byte{1, 2}},
+		{"-32768i16", LITERAL_I16, []byte{0, 0x80}},
+		{"4294967295u32", LITERAL_U32, []byte{0xff, 0xff, 0xff, 0xff}},
+		{"-2i32", LITERAL_I32, []byte{0xfe, 0xff, 0xff, 0xff}},
+		{"18446744073709551615u64", LITERAL_U64, bytes.Repeat([]byte{0xff}, 8)},
+		{"9223372036854775807i64", LITERAL_I64, append(bytes.Repeat([]byte{0xff}, 7), 0x7f)},
+		{"340282366920938463463374607431768211455u128", LITERAL_U128, bytes.Repeat([]byte{0xff}, 16)},
+		{"-170141183460469231731687303715884105728i128", LITERAL_I128, append(make([]byte, 15), 0x80)},
+		{"0u64", LITERAL_U64, make([]byte, 8)},
+	}
+	for _, tt := range tests {
+		t.Run(tt.literal, func(t *testing.T) {
+			got, err := s.LiteralToBytes(tt.literal)
+			if err != nil {
+				t.Fatalf("AleoWrapper.LiteralToBytes() error = %v", err)
+			}
+			if !bytes.Equal(got, tt.bytes) {
+				t.Errorf("AleoWrapper.LiteralToBytes() = %x, want %x", got, tt.bytes)
+			}
+
+			literal, err := s.BytesToLiteral(got, tt.typeCode)
+			if err != nil {
+				t.Fatalf("AleoWrapper.BytesToLiteral() error = %v", err)
+			}
+			if literal != tt.literal {
+				t.Errorf("AleoWrapper.BytesToLiteral() = %v, want %v", literal, tt.literal)
+			}
+		})
+	}
+
+	for _, literal := range []string{"1field", "aleo1invalid", "256u8", "5", ""} {
+		if _, err := s.LiteralToBytes(literal); err == nil {
+			t.Errorf("AleoWrapper.LiteralToBytes(%q) should fail", literal)
+		}
+	}
+
+	invalid := []struct {
+		name     string
+		data     []byte
+		typeCode uint32
+	}{
+		{"too short", []byte{1}, LITERAL_U16},
+		{"too long", []byte{1, 2, 3}, LITERAL_U16},
+		{"boolean byte out of range", []byte{2}, LITERAL_BOOLEAN},
+		{"field type code", make([]byte, 32), 2},
+		{"unknown type code", []byte{1}, 100},
+	}
+	for _, tt := range invalid {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, err := s.BytesToLiteral(tt.data, tt.typeCode); err == nil {
+				t.Error("AleoWrapper.BytesToLiteral() should fail")
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.LiteralToBytes("1u8")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_HashBenchmark(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	for _, operation := range []uint32{HASH_BHP256, HASH_PSD2, HASH_SHA3_256} {
+		start := time.Now()
+		elapsed, err := s.HashBenchmark(operation, 64, 10)
+		wall := time.Since(start)
+		if err != nil {
+			t.Fatalf("AleoWrapper.HashBenchmark(%d) error = %v", operation, err)
+		}
+		if elapsed <= 0 || elapsed > wall {
+			t.Errorf("AleoWrapper.HashBenchmark(%d) = %v, want a positive duration of at most %v", operation, elapsed, wall)
+		}
+	}
+
+	tests := []struct {
+		name       string
+		operation  uint32
+		inputSize  uint32
+		iterations uint32
+	}{
+		{"unknown operation", 100, 64, 1},
+		{"oversized pedersen input", HASH_PED64, 9, 1},
+		{"input above the benchmark cap", HASH_SHA3_256, 1<<20 + 1, 1},
+		{"zero iterations", HASH_BHP256, 64, 0},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, err := s.HashBenchmark(tt.operation, tt.inputSize, tt.iterations); err == nil {
+				t.Error("AleoWrapper.HashBenchmark() should fail")
+			}
+		})
+	}
+
+	// The input size counts against the allocation limit even though the host allocates nothing
+	if err := s.SetMaxAlloc(1024); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := s.HashBenchmark(HASH_BHP256, 1025, 1); err == nil {
+		t.Error("AleoWrapper.HashBenchmark() should fail above the allocation limit")
+	}
+	if _, err := s.HashBenchmark(HASH_BHP256, 1024, 1); err != nil {
+		t.Errorf("AleoWrapper.HashBenchmark() error = %v at the allocation limit", err)
+	}
+
+	s.Close()
+
+	_, err = s.HashBenchmark(HASH_BHP256, 64, 1)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ValuesEqual(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	_, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	record := testRecord(address, 1500000, "0group")
+	reorderedRecord := fmt.Sprintf("{\n  microcredits: 1500000u64.private,\n  owner: %s.private,\n  _nonce: 0group.public\n}", address)
+
+	tests := []struct {
+		name string
+		a    string
+		b    string
+		want bool
+	}{
+		{"same literal", "5u64", "5u64", true},
+		{"canonicalized literal", "1_000u64", "1000u64", true},
+		{"different literal", "5u64", "6u64", false},
+		{"different literal type", "5u64", "5u32", false},
+		{"reformatted struct", "{ a: 1u8, b: { c: true } }", "{a:1u8,\n\tb:{c:true}}", true},
+		{"reordered struct members", "{ a: 1u8, b: [2u8, 3u8] }", "{ b: [2u8, 3u8], a: 1u8 }", true},
+		{"different member value", "{ a: 1u8, b: 2u8 }", "{ a: 1u8, b: 3u8 }", false},
+		{"missing member", "{ a: 1u8, b: 2u8 }", "{ a: 1u8 }", false},
+		{"renamed member", "{ a: 1u8 }", "{ b: 1u8 }", false},
+		{"reordered array", "[1u8, 2u8]", "[2u8, 1u8]", false},
+		{"reordered record entries", record, reorderedRecord, true},
+		{"different record amount", record, testRecord(address, 1, "0group"), false},
+		{"record and struct", record, "{ owner: " + address + ", microcredits: 1500000u64 }", false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.ValuesEqual(tt.a, tt.b)
+			if err != nil {
+				t.Fatalf("AleoWrapper.ValuesEqual() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.ValuesEqual(%q, %q) = %v, want %v", tt.a, tt.b, got, tt.want)
+			}
+		})
+	}
+
+	if _, err := s.ValuesEqual("5u64", "not a value"); err == nil {
+		t.Error("AleoWrapper.ValuesEqual() should fail on a malformed value")
+	}
+	if _, err := s.ValuesEqual("{ a: }", "5u64"); err == nil {
+		t.Error("AleoWrapper.ValuesEqual() should fail on a malformed value")
+	}
+	// A parse error doesn't leak into the next comparison
+	if equal, err := s.ValuesEqual("5u64", "6u64"); equal || err != nil {
+		t.Errorf("AleoWrapper.ValuesEqual() = %v, %v after a parse error, want false, nil", equal, err)
+	}
+
+	s.Close()
+
+	_, err = s.ValuesEqual("5u64", "5u64")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_AddressShard(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// Expected shards computed independently from the SHA3-256 digest of the address string
+	known := "aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px"
+	tests := []struct {
+		numShards uint32
+		want      uint32
+	}{
+		{1, 0},
+		{7, 4},
+		{16, 0},
+		{1000, 16},
+	}
+	for _, tt := range tests {
+		got, err := s.AddressShard(known, tt.numShards)
+		if err != nil {
+			t.Fatalf("AleoWrapper.AddressShard() error = %v", err)
+		}
+		if got != tt.want {
+			t.Errorf("AleoWrapper.AddressShard(%d) = %v, want %v", tt.numShards, got, tt.want)
+		}
+	}
+
+	counts := make([]int, 4)
+	for i := 0; i < 40; i++ {
+		_, address, err := s.NewPrivateKey()
+		if err != nil {
+			t.Fatal(err)
+		}
+		shard, err := s.AddressShard(address, 4)
+		if err != nil {
+			t.Fatalf("AleoWrapper.AddressShard() error = %v", err)
+		}
+		if shard >= 4 {
+			t.Fatalf("AleoWrapper.AddressShard() = %v, want a shard below 4", shard)
+		}
+		counts[shard]++
+	}
+	for shard, count := range counts {
+		if count == 0 {
+			t.Errorf("AleoWrapper.AddressShard() assigned none of 40 addresses to shard %d", shard)
+		}
+	}
+
+	if _, err := s.AddressShard("aleo1invalid", 4); err == nil {
+		t.Error("AleoWrapper.AddressShard() should fail on an invalid address")
+	}
+	if _, err := s.AddressShard(known, 0); err == nil {
+		t.Error("AleoWrapper.AddressShard() should fail on a zero shard count")
+	}
+
+	s.Close()
+
+	_, err = s.AddressShard(known, 4)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_EncryptToAddress(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKey, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	payloads := [][]byte{
+		{},
+		[]byte("hello aleo"),
+		bytes.Repeat([]byte{0xab}, 100),
+	}
+	for _, payload := range payloads {
+		ciphertext, err := s.EncryptToAddress(address, payload)
+		if err != nil {
+			t.Fatalf("AleoWrapper.EncryptToAddress() error = %v", err)
+		}
+		if len(ciphertext) != 32+len(payload)+32 {
+			t.Errorf("AleoWrapper.EncryptToAddress() returned %d bytes for a %d byte payload", len(ciphertext), len(payload))
+		}
+
+		plaintext, err := s.DecryptFromAddress(key, ciphertext)
+		if err != nil {
+			t.Fatalf("AleoWrapper.DecryptFromAddress() error = %v", err)
+		}
+		if !bytes.Equal(plaintext, payload) {
+			t.Errorf("AleoWrapper.DecryptFromAddress() = %x, want %x", plaintext, payload)
+		}
+
+		if _, err := s.DecryptFromAddress(otherKey, ciphertext); err == nil {
+			t.Error("AleoWrapper.DecryptFromAddress() should fail with another account's key")
+		}
+	}
+
+	// A fresh ephemeral key is used for every encryption
+	first, err := s.EncryptToAddress(address, []byte("hello aleo"))
+	if err != nil {
+		t.Fatalf("AleoWrapper.EncryptToAddress() error = %v", err)
+	}
+	second, err := s.EncryptToAddress(address, []byte("hello aleo"))
+	if err != nil {
+		t.Fatalf("AleoWrapper.EncryptToAddress() error = %v", err)
+	}
+	if bytes.Equal(first, second) {
+		t.Error("AleoWrapper.EncryptToAddress() should be randomized")
+	}
+
+	for _, i := range []int{0, 40, len(first) - 1} {
+		tampered := append([]byte{}, first...)
+		tampered[i] ^= 1
+		if _, err := s.DecryptFromAddress(key, tampered); err == nil {
+			t.Errorf("AleoWrapper.DecryptFromAddress() should fail on a payload modified at byte %d", i)
+		}
+	}
+	if _, err := s.DecryptFromAddress(key, first[:63]); err == nil {
+		t.Error("AleoWrapper.DecryptFromAddress() should fail on a truncated payload")
+	}
+	if _, err := s.EncryptToAddress("aleo1invalid", []byte("hello aleo")); err == nil {
+		t.Error("AleoWrapper.EncryptToAddress() should fail on an invalid address")
+	}
+
+	s.Close()
+
+	_, err = s.EncryptToAddress(address, []byte("hello aleo"))
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_StealthAddress(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatalf("AleoWrapper.GetViewKey() error = %v", err)
+	}
+	otherKey, otherAddress, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherViewKey, err := s.GetViewKey(otherKey)
+	if err != nil {
+		t.Fatalf("AleoWrapper.GetViewKey() error = %v", err)
+	}
+
+	stealth, ephemeral, err := s.StealthAddress(address, "123456789scalar")
+	if err != nil {
+		t.Fatalf("AleoWrapper.StealthAddress() error = %v", err)
+	}
+	if !strings.HasPrefix(stealth, "aleo1") || stealth == address {
+		t.Errorf("AleoWrapper.StealthAddress() = %v, want a new aleo1 address", stealth)
+	}
+	if !strings.HasSuffix(ephemeral, "group") {
+		t.Errorf("AleoWrapper.StealthAddress() ephemeral public key = %v, want a group literal", ephemeral)
+	}
+
+	again, _, err := s.StealthAddress(address, "123456789scalar")
+	if err != nil {
+		t.Fatalf("AleoWrapper.StealthAddress() error = %v", err)
+	}
+	if again != stealth {
+		t.Errorf("AleoWrapper.StealthAddress() = %v for the same inputs, want %v", again, stealth)
+	}
+	otherEphemeralStealth, otherEphemeral, err := s.StealthAddress(address, "987654321scalar")
+	if err != nil {
+		t.Fatalf("AleoWrapper.StealthAddress() error = %v", err)
+	}
+	if otherEphemeralStealth == stealth {
+		t.Error("AleoWrapper.StealthAddress() should give a different address for another ephemeral scalar")
+	}
+	otherStealth, otherStealthEphemeral, err := s.StealthAddress(otherAddress, "123456789scalar")
+	if err != nil {
+		t.Fatalf("AleoWrapper.StealthAddress() error = %v", err)
+	}
+
+	tests := []struct {
+		name      string
+		viewKey   []byte
+		ephemeral string
+		stealth   string
+		want      bool
+	}{
+		{"recipient", viewKey, ephemeral, stealth, true},
+		{"recipient with another ephemeral key", viewKey, otherEphemeral, otherEphemeralStealth, true},
+		{"other account", otherViewKey, ephemeral, stealth, false},
+		{"other account's stealth address", viewKey, otherStealthEphemeral, otherStealth, false},
+		{"mismatched ephemeral key", viewKey, otherEphemeral, stealth, false},
+		{"public address", viewKey, ephemeral, address, false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.StealthScan(tt.viewKey, tt.ephemeral, tt.stealth)
+			if err != nil {
+				t.Fatalf("AleoWrapper.StealthScan() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.StealthScan() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	if _, _, err := s.StealthAddress(address, "0scalar"); err == nil {
+		t.Error("AleoWrapper.StealthAddress() should fail on a zero ephemeral scalar")
+	}
+	if _, _, err := s.StealthAddress("aleo1invalid", "123456789scalar"); err == nil {
+		t.Error("AleoWrapper.StealthAddress() should fail on an invalid address")
+	}
+	if _, err := s.StealthScan(viewKey, "not a group", stealth); err == nil {
+		t.Error("AleoWrapper.StealthScan() should fail on an invalid ephemeral public key")
+	}
+
+	s.Close()
+
+	_, _, err = s.StealthAddress(address, "123456789scalar")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ComputeKeyIsValid(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	pkSig, prSig, skPrf, err := s.GetComputeKey(key)
+	if err != nil {
+		t.Fatalf("AleoWrapper.GetComputeKey() error = %v", err)
+	}
+	otherKey, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherPkSig, _, otherSkPrf, err := s.GetComputeKey(otherKey)
+	if err != nil {
+		t.Fatalf("AleoWrapper.GetComputeKey() error = %v", err)
+	}
+
+	tests := []struct {
+		name    string
+		pkSig   string
+		prSig   string
+		skPrf   string
+		want    bool
+		wantErr bool
+	}{
+		{name: "valid", pkSig: pkSig, prSig: prSig, skPrf: skPrf, want: true},
+		{name: "swapped group elements", pkSig: prSig, prSig: pkSig, skPrf: skPrf},
+		{name: "other sk_prf", pkSig: pkSig, prSig: prSig, skPrf: otherSkPrf},
+		{name: "other pk_sig", pkSig: otherPkSig, prSig: prSig, skPrf: skPrf},
+		{name: "identity pk_sig", pkSig: "0group", prSig: prSig, skPrf: skPrf},
+		{name: "malformed pk_sig", pkSig: "not a group", prSig: prSig, skPrf: skPrf, wantErr: true},
+		{name: "malformed sk_prf", pkSig: pkSig, prSig: prSig, skPrf: "1field", wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.ComputeKeyIsValid(tt.pkSig, tt.prSig, tt.skPrf)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.ComputeKeyIsValid() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.ComputeKeyIsValid() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	// The components have to come as a list of exactly three
+	result, err := s.(*aleoWrapperSession).call("compute_key_is_valid", encodeStringFrames([]string{pkSig, prSig}))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if result != 0 {
+		t.Error("compute_key_is_valid should reject two components")
+	}
+
+	s.Close()
+
+	_, err = s.ComputeKeyIsValid(pkSig, prSig, skPrf)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_NewFieldChain(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	chain, err := s.NewFieldChain("0field")
+	if err != nil {
+		t.Fatalf("AleoWrapper.NewFieldChain() error = %v", err)
+	}
+
+	// Fold the same sequence by hand, starting a new chain at every head and absorbing one field
+	head := "0field"
+	heads := make(map[string]bool)
+	for i := 1; i <= 5; i++ {
+		field := fmt.Sprintf("%dfield", i)
+
+		got, err := chain.Absorb(field)
+		if err != nil {
+			t.Fatalf("FieldChain.Absorb(%s) error = %v", field, err)
+		}
+
+		step, err := s.NewFieldChain(head)
+		if err != nil {
+			t.Fatalf("AleoWrapper.NewFieldChain() error = %v", err)
+		}
+		want, err := step.Absorb(field)
+		if err != nil {
+			t.Fatalf("FieldChain.Absorb(%s) error = %v", field, err)
+		}
+		if err := step.Close(); err != nil {
+			t.Fatal(err)
+		}
+
+		if got != want {
+			t.Errorf("FieldChain.Absorb(%s) = %v, want %v", field, got, want)
+		}
+		if got == head {
+			t.Errorf("FieldChain.Absorb(%s) didn't change the head", field)
+		}
+		head = got
+		heads[got] = true
+	}
+	if len(heads) != 5 {
+		t.Errorf("FieldChain.Absorb() returned %d distinct heads for 5 fields", len(heads))
+	}
+
+	// A failed absorb leaves the head unchanged
+	if _, err := chain.Absorb("1group"); err == nil {
+		t.Error("FieldChain.Absorb() should fail on an invalid field")
+	}
+	next, err := chain.Absorb("6field")
+	if err != nil {
+		t.Fatal(err)
+	}
+	step, err := s.NewFieldChain(head)
+	if err != nil {
+		t.Fatal(err)
+	}
+	want, err := step.Absorb("6field")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if next != want {
+		t.Errorf("FieldChain.Absorb() = %v after a failed absorb, want %v", next, want)
+	}
+	if err := step.Close(); err != nil {
+		t.Fatal(err)
+	}
+
+	// The order of the absorbed fields matters
+	swapped, err := s.NewFieldChain("0field")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, err := swapped.Absorb("2field"); err != nil {
+		t.Fatal(err)
+	}
+	swappedHead, err := swapped.Absorb("1field")
+	if err != nil {
+		t.Fatal(err)
+	}
+	ordered, err := s.NewFieldChain("0field")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, err := ordered.Absorb("1field"); err != nil {
+		t.Fatal(err)
+	}
+	orderedHead, err := ordered.Absorb("2field")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if swappedHead == orderedHead {
+		t.Error("FieldChain.Absorb() should depend on the order of the fields")
+	}
+	if err := swapped.Close(); err != nil {
+		t.Fatal(err)
+	}
+	if err := ordered.Close(); err != nil {
+		t.Fatal(err)
+	}
+
+	if err := chain.Close(); err != nil {
+		t.Fatalf("FieldChain.Close() error = %v", err)
+	}
+	if err := chain.Close(); err != nil {
+		t.Fatalf("second FieldChain.Close() error = %v", err)
+	}
+	if _, err := chain.Absorb("1field"); err == nil {
+		t.Error("FieldChain.Absorb() should fail after Close")
+	}
+
+	if _, err := s.NewFieldChain("not a field"); err == nil {
+		t.Error("AleoWrapper.NewFieldChain() should fail on an invalid initial value")
+	}
+
+	s.Close()
+
+	_, err = s.NewFieldChain("0field")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_ClassifyBech32(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatalf("AleoWrapper.GetViewKey() error = %v", err)
+	}
+	signature, err := s.Sign(key, []byte("hello aleo"))
+	if err != nil {
+		t.Fatalf("AleoWrapper.Sign() error = %v", err)
+	}
+
+	// The IDs are bech32m encodings of the field 7, computed with the BIP 350 reference encoder
+	tests := []struct {
+		name    string
+		input   string
+		want    uint64
+		wantErr bool
+	}{
+		{name: "address", input: address, want: STRING_ADDRESS},
+		{name: "private key", input: string(key), want: STRING_PRIVATE_KEY},
+		{name: "view key", input: string(viewKey), want: STRING_VIEW_KEY},
+		{name: "signature", input: signature, want: STRING_SIGNATURE},
+		{name: "transaction ID", input: "at1quqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq2al588", want: STRING_TRANSACTION_ID},
+		{name: "transition ID", input: "au1quqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq4k036e", want: STRING_TRANSITION_ID},
+		{name: "block hash", input: "ab1quqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqx67gwa", want: STRING_BLOCK_HASH},
+		{name: "unknown prefix", input: "xyz1quqqqqqqqqqqqqqqqqqqqqqqqqq", want: STRING_UNKNOWN},
+		{name: "field literal", input: "7field", want: STRING_UNKNOWN},
+		{name: "truncated address", input: address[:len(address)-1], want: STRING_UNKNOWN, wantErr: true},
+		{name: "truncated private key", input: string(key[:20]), want: STRING_UNKNOWN, wantErr: true},
+		{name: "transaction ID with bad checksum", input: "at1quqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq2al589", want: STRING_UNKNOWN, wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.ClassifyBech32(tt.input)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.ClassifyBech32() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.ClassifyBech32() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.ClassifyBech32(address)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_SignHashedMessage(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	message := []byte("an arbitrary message that isn't a u128")
+	seed := []byte("seed")
+
+	for _, algorithm := range []uint32{HASH_PSD2, HASH_BHP256, HASH_KECCAK256, HASH_SHA3_512} {
+		t.Run(fmt.Sprintf("algorithm %d", algorithm), func(t *testing.T) {
+			signature, err := s.SignHashedMessage(key, message, algorithm, seed)
+			if err != nil {
+				t.Fatalf("AleoWrapper.SignHashedMessage() error = %v", err)
+			}
+
+			again, err := s.SignHashedMessage(key, message, algorithm, seed)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if again != signature {
+				t.Error("AleoWrapper.SignHashedMessage() should be deterministic for the same seed")
+			}
+			otherSeed, err := s.SignHashedMessage(key, message, algorithm, []byte("other seed"))
+			if err != nil {
+				t.Fatal(err)
+			}
+			if otherSeed == signature {
+				t.Error("AleoWrapper.SignHashedMessage() should depend on the seed")
+			}
+
+			tests := []struct {
+				name      string
+				signature string
+				message   []byte
+				algorithm uint32
+				want      bool
+			}{
+				{name: "valid", signature: signature, message: message, algorithm: algorithm, want: true},
+				{name: "other seed", signature: otherSeed, message: message, algorithm: algorithm, want: true},
+				{name: "other message", signature: signature, message: []byte("another message"), algorithm: algorithm, want: false},
+				{name: "other algorithm", signature: signature, message: message, algorithm: HASH_SHA3_256, want: false},
+			}
+			for _, tt := range tests {
+				valid, err := s.VerifyHashedMessage(address, tt.signature, tt.message, tt.algorithm)
+				if err != nil {
+					t.Fatalf("%s: AleoWrapper.VerifyHashedMessage() error = %v", tt.name, err)
+				}
+				if valid != tt.want {
+					t.Errorf("%s: AleoWrapper.VerifyHashedMessage() = %v, want %v", tt.name, valid, tt.want)
+				}
+			}
+		})
+	}
+
+	if _, err := s.SignHashedMessage(key, message, 99, seed); err == nil {
+		t.Error("AleoWrapper.SignHashedMessage() should fail on an unknown algorithm")
+	}
+	if _, err := s.SignHashedMessage(key, message, HASH_PED64, seed); err == nil {
+		t.Error("AleoWrapper.SignHashedMessage() should fail on a message too long for Pedersen64")
+	}
+
+	s.Close()
+
+	_, err = s.SignHashedMessage(key, message, HASH_PSD2, seed)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_StateRootsEqual(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// ab1 strings of the fields 1 and 2, see TestAleoWrapper_ComputeStateRoot
+	root, err := s.ComputeStateRoot([]string{"ab1qyqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqjw98qd"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherRoot, err := s.ComputeStateRoot([]string{"ab1qgqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqcyu589"})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// The sr1 strings are bech32m encodings of the fields 7 and 8, computed with the BIP 350
+	// reference encoder
+	tests := []struct {
+		name    string
+		a       string
+		b       string
+		want    bool
+		wantErr bool
+	}{
+		{name: "same root", a: root, b: root, want: true},
+		{name: "different roots", a: root, b: otherRoot, want: false},
+		{name: "leading zeros", a: "7field", b: "0007field", want: true},
+		{name: "digit separators", a: "1000field", b: "1_000field", want: true},
+		{name: "bech32 and field", a: "sr1quqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqg6akjn", b: "7field", want: true},
+		{name: "different bech32", a: "sr1quqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqg6akjn", b: "sr1pqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqg3ekc8", want: false},
+		{name: "not a field", a: root, b: "7u64", wantErr: true},
+		{name: "bad checksum", a: "sr1quqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqg6akjq", b: "7field", wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			equal, err := s.StateRootsEqual(tt.a, tt.b)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.StateRootsEqual() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if equal != tt.want {
+				t.Errorf("AleoWrapper.StateRootsEqual() = %v, want %v", equal, tt.want)
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.StateRootsEqual(root, root)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_RecordsOwnershipBatch(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatal(err)
+	}
+	_, otherAddress, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	owners := []string{address, otherAddress, otherAddress, address, address, otherAddress}
+	ciphertexts := make([]string, len(owners))
+	want := make([]bool, len(owners))
+	for i, owner := range owners {
+		ciphertexts[i], _ = encryptRecord(t, s, testRecord(owner, uint64(i+1), "0group"))
+		want[i] = owner == address
+	}
+
+	for i, ciphertext := range ciphertexts {
+		owned, err := s.RecordIsOwner(viewKey, ciphertext)
+		if err != nil {
+			t.Fatalf("AleoWrapper.RecordIsOwner() error = %v", err)
+		}
+		if owned != want[i] {
+			t.Errorf("AleoWrapper.RecordIsOwner(%d) = %v, want %v", i, owned, want[i])
+		}
+	}
+
+	owned, err := s.RecordsOwnershipBatch(viewKey, ciphertexts)
+	if err != nil {
+		t.Fatalf("AleoWrapper.RecordsOwnershipBatch() error = %v", err)
+	}
+	if !reflect.DeepEqual(owned, want) {
+		t.Errorf("AleoWrapper.RecordsOwnershipBatch() = %v, want %v", owned, want)
+	}
+
+	if _, err := s.RecordIsOwner(viewKey, "record1invalid"); err == nil {
+		t.Error("AleoWrapper.RecordIsOwner() should fail on an invalid ciphertext")
+	}
+	if _, err := s.RecordIsOwner([]byte("AViewKey1invalid"), ciphertexts[0]); err == nil {
+		t.Error("AleoWrapper.RecordIsOwner() should fail on an invalid view key")
+	}
+	if _, err := s.RecordsOwnershipBatch(viewKey, append(ciphertexts, "record1invalid")); err == nil {
+		t.Error("AleoWrapper.RecordsOwnershipBatch() should fail on an invalid ciphertext")
+	}
+
+	s.Close()
+
+	_, err = s.RecordsOwnershipBatch(viewKey, ciphertexts)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func BenchmarkRecordsOwnership(b *testing.B) {
+	const ciphertextCount = 100
+
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		b.Fatal(err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		b.Fatal(err)
+	}
+	defer s.Close()
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		b.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		b.Fatal(err)
+	}
+	_, otherAddress, err := s.NewPrivateKey()
+	if err != nil {
+		b.Fatal(err)
+	}
+
+	// One in ten records is owned, roughly what a wallet sync sees at best
+	ciphertexts := make([]string, ciphertextCount)
+	for i := range ciphertexts {
+		owner := otherAddress
+		if i%10 == 0 {
+			owner = address
+		}
+		ciphertexts[i], _ = encryptRecord(b, s, testRecord(owner, uint64(i+1), "0group"))
+	}
+
+	b.Run("per call", func(b *testing.B) {
+		for n := 0; n < b.N; n++ {
+			for _, ciphertext := range ciphertexts {
+				if _, err := s.RecordIsOwner(viewKey, ciphertext); err != nil {
+					b.Fatal(err)
+				}
+			}
+		}
+	})
+
+	b.Run("batch", func(b *testing.B) {
+		for n := 0; n < b.N; n++ {
+			if _, err := s.RecordsOwnershipBatch(viewKey, ciphertexts); err != nil {
+				b.Fatal(err)
+			}
+		}
+	})
+}
+
+func TestAleoWrapper_AccountToJSON(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatal(err)
+	}
+	pkSig, prSig, skPrf, err := s.GetComputeKey(key)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	out, err := s.AccountToJSON(key)
+	if err != nil {
+		t.Fatalf("AleoWrapper.AccountToJSON() error = %v", err)
+	}
+
+	var account map[string]json.RawMessage
+	if err := json.Unmarshal(out, &account); err != nil {
+		t.Fatalf("AleoWrapper.AccountToJSON() = %s is not a JSON object: %v", out, err)
+	}
+	if len(account) != 5 {
+		t.Errorf("AleoWrapper.AccountToJSON() has %d members, want 5", len(account))
+	}
+
+	strs := map[string]string{}
+	for _, name := range []string{"private_key", "view_key", "address"} {
+		if _, ok := account[name]; !ok {
+			t.Fatalf("AleoWrapper.AccountToJSON() is missing %q", name)
+		}
+		var value string
+		if err := json.Unmarshal(account[name], &value); err != nil {
+			t.Fatalf("AleoWrapper.AccountToJSON() %q isn't a string: %v", name, err)
+		}
+		strs[name] = value
+	}
+
+	var computeKey map[string]string
+	if err := json.Unmarshal(account["compute_key"], &computeKey); err != nil {
+		t.Fatalf("AleoWrapper.AccountToJSON() compute_key isn't an object of strings: %v", err)
+	}
+	var graphKey map[string]string
+	if err := json.Unmarshal(account["graph_key"], &graphKey); err != nil {
+		t.Fatalf("AleoWrapper.AccountToJSON() graph_key isn't an object of strings: %v", err)
+	}
+
+	tests := []struct {
+		name string
+		got  string
+		want string
+	}{
+		{name: "private_key", got: strs["private_key"], want: string(key)},
+		{name: "view_key", got: strs["view_key"], want: string(viewKey)},
+		{name: "address", got: strs["address"], want: address},
+		{name: "compute_key.pk_sig", got: computeKey["pk_sig"], want: pkSig},
+		{name: "compute_key.pr_sig", got: computeKey["pr_sig"], want: prSig},
+		{name: "compute_key.sk_prf", got: computeKey["sk_prf"], want: skPrf},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if tt.got != tt.want {
+				t.Errorf("AleoWrapper.AccountToJSON() %s = %v, want %v", tt.name, tt.got, tt.want)
+			}
+		})
+	}
+	if !strings.HasSuffix(graphKey["sk_tag"], "field") {
+		t.Errorf("AleoWrapper.AccountToJSON() graph_key.sk_tag = %v, want a field", graphKey["sk_tag"])
+	}
+
+	if _, err := s.AccountToJSON([]byte("APrivateKey1invalid")); err == nil {
+		t.Error("AleoWrapper.AccountToJSON() should fail on an invalid private key")
+	}
+
+	s.Close()
+
+	_, err = s.AccountToJSON(key)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_VerifyAgainstAny(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	addresses := make([]string, 5)
+	var signerKey []byte
+	for i := range addresses {
+		key, address, err := s.NewPrivateKey()
+		if err != nil {
+			t.Fatal(err)
+		}
+		addresses[i] = address
+		if i == 2 {
+			signerKey = key
+		}
+	}
+
+	formattedMessage, err := s.FormatMessage([]byte("test"), 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	message, err := s.HashMessage(formattedMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherFormattedMessage, err := s.FormatMessage([]byte("other"), 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherMessage, err := s.HashMessage(otherFormattedMessage)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	signature, err := s.Sign(signerKey, message)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name      string
+		addresses []string
+		message   []byte
+		wantIndex uint64
+		wantFound bool
+	}{
+		{name: "third of five", addresses: addresses, message: message, wantIndex: 2, wantFound: true},
+		{name: "first match wins", addresses: append([]string{addresses[2]}, addresses...), message: message, wantIndex: 0, wantFound: true},
+		{name: "signer missing", addresses: []string{addresses[0], addresses[1], addresses[3], addresses[4]}, message: message, wantFound: false},
+		{name: "other message", addresses: addresses, message: otherMessage, wantFound: false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			index, found, err := s.VerifyAgainstAny(tt.addresses, signature, tt.message)
+			if err != nil {
+				t.Fatalf("AleoWrapper.VerifyAgainstAny() error = %v", err)
+			}
+			if found != tt.wantFound || index != tt.wantIndex {
+				t.Errorf("AleoWrapper.VerifyAgainstAny() = (%v, %v), want (%v, %v)", index, found, tt.wantIndex, tt.wantFound)
+			}
+		})
+	}
+
+	if _, _, err := s.VerifyAgainstAny(append([]string{"aleo1invalid"}, addresses...), signature, message); err == nil {
+		t.Error("AleoWrapper.VerifyAgainstAny() should fail on an invalid address")
+	}
+	if _, _, err := s.VerifyAgainstAny(addresses, "sign1invalid", message); err == nil {
+		t.Error("AleoWrapper.VerifyAgainstAny() should fail on an invalid signature")
+	}
+
+	s.Close()
+
+	_, _, err = s.VerifyAgainstAny(addresses, signature, message)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_CheckHashInput(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// BHP absorbs its input iteratively, so it has no boundary to be one byte over
+	tests := []struct {
+		name      string
+		operation uint32
+		inputLen  uint32
+		want      bool
+		wantErr   bool
+	}{
+		{name: "pedersen64 empty", operation: HASH_PED64, inputLen: 0, want: true},
+		{name: "pedersen64 at capacity", operation: HASH_PED64, inputLen: 8, want: true},
+		{name: "pedersen64 one byte over", operation: HASH_PED64, inputLen: 9, want: false},
+		{name: "pedersen128 at capacity", operation: HASH_PED128, inputLen: 16, want: true},
+		{name: "pedersen128 one byte over", operation: HASH_PED128, inputLen: 17, want: false},
+		{name: "bhp256 large", operation: HASH_BHP256, inputLen: 1 << 20, want: true},
+		{name: "bhp512 max", operation: HASH_BHP512, inputLen: math.MaxUint32, want: true},
+		{name: "bhp768 max", operation: HASH_BHP768, inputLen: math.MaxUint32, want: true},
+		{name: "bhp1024 max", operation: HASH_BHP1024, inputLen: math.MaxUint32, want: true},
+		{name: "poseidon8 max", operation: HASH_PSD8, inputLen: math.MaxUint32, want: true},
+		{name: "sha3-256 max", operation: HASH_SHA3_256, inputLen: math.MaxUint32, want: true},
+		{name: "unknown", operation: 1000, inputLen: 1, wantErr: true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := s.CheckHashInput(tt.operation, tt.inputLen)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("AleoWrapper.CheckHashInput() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("AleoWrapper.CheckHashInput() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+
+	// The check agrees with the operations themselves at the boundary
+	for _, operation := range []uint32{HASH_PED64, HASH_PED128} {
+		limit, err := s.MaxInputBytes(operation)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if _, err := s.HashBytes(operation, make([]byte, limit)); err != nil {
+			t.Errorf("AleoWrapper.HashBytes(%d) should accept %d bytes: %v", operation, limit, err)
+		}
+		if _, err := s.HashBytes(operation, make([]byte, limit+1)); err == nil {
+			t.Errorf("AleoWrapper.HashBytes(%d) should reject %d bytes", operation, limit+1)
+		}
+	}
+
+	// Inputs far past a single BHP window still hash
+	for _, operation := range []uint32{HASH_BHP256, HASH_BHP512, HASH_BHP768, HASH_BHP1024} {
+		if _, err := s.HashBytes(operation, make([]byte, 4096)); err != nil {
+			t.Errorf("AleoWrapper.HashBytes(%d) should accept 4096 bytes: %v", operation, err)
+		}
+	}
+
+	s.Close()
+
+	_, err = s.CheckHashInput(HASH_PED64, 8)
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}
+
+func TestAleoWrapper_RerandomizeCiphertext(t *testing.T) {
+	wrapper, closeFn, err := NewWrapper()
+	if err != nil {
+		t.Fatalf("NewWrapper error = %v\n", err)
+	}
+	defer closeFn()
+
+	s, err := wrapper.NewSession()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	key, address, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	viewKey, err := s.GetViewKey(key)
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherKey, _, err := s.NewPrivateKey()
+	if err != nil {
+		t.Fatal(err)
+	}
+	otherViewKey, err := s.GetViewKey(otherKey)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	ciphertext, plaintext := encryptRecord(t, s, testRecord(address, 1500000, "0group"))
+	rerandomized, err := s.RerandomizeCiphertext(ciphertext, viewKey, "7scalar")
+	if err != nil {
+		t.Fatalf("AleoWrapper.RerandomizeCiphertext() error = %v", err)
+	}
+	if rerandomized == ciphertext {
+		t.Error("AleoWrapper.RerandomizeCiphertext() should return a different ciphertext")
+	}
+
+	// The owner decrypts the same entries, only the nonce changed
+	record, err := s.DecryptRecord(rerandomized, viewKey)
+	if err != nil {
+		t.Fatalf("AleoWrapper.DecryptRecord() error = %v", err)
+	}
+	entries := func(record string) string {
+		return record[:strings.Index(record, "_nonce")]
+	}
+	if entries(record) != entries(plaintext) {
+		t.Errorf("AleoWrapper.DecryptRecord() = %v, want the entries of %v", record, plaintext)
+	}
+	if record == plaintext {
+		t.Error("AleoWrapper.RerandomizeCiphertext() should change the record nonce")
+	}
+
+	again, err := s.RerandomizeCiphertext(rerandomized, viewKey, "7scalar")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if again == rerandomized {
+		t.Error("AleoWrapper.RerandomizeCiphertext() should return a new ciphertext for a re-randomized one")
+	}
+	if _, err := s.DecryptRecord(again, viewKey); err != nil {
+		t.Errorf("AleoWrapper.DecryptRecord() error = %v for a ciphertext re-randomized twice", err)
+	}
+
+	tests := []struct {
+		name       string
+		ciphertext string
+		viewKey    []byte
+		delta      string
+	}{
+		{name: "not the owner", ciphertext: ciphertext, viewKey: otherViewKey, delta: "7scalar"},
+		{name: "zero delta", ciphertext: ciphertext, viewKey: viewKey, delta: "0scalar"},
+		{name: "invalid delta", ciphertext: ciphertext, viewKey: viewKey, delta: "7field"},
+		{name: "invalid ciphertext", ciphertext: "record1invalid", viewKey: viewKey, delta: "7scalar"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, err := s.RerandomizeCiphertext(tt.ciphertext, tt.viewKey, tt.delta); err == nil {
+				t.Error("AleoWrapper.RerandomizeCiphertext() should fail")
+			}
+		})
+	}
+
+	s.Close()
+
+	_, err = s.RerandomizeCiphertext(ciphertext, viewKey, "7scalar")
+	if !errors.Is(err, ErrNoModule) {
+		t.Fatal("session should return error on any function call after it was closed")
+	}
+}